@@ -1,94 +1,195 @@
 // src/cli.rs
 use clap::{Arg, Command};
-use crate::output::OutputFormat;
-use crate::sorting::{SortOrder, PositionSort};  // 从 sorting 模块导入
+use clap::parser::ValueSource;
+use crate::output::{OutputFormat, EmptyAs, ErrorFormat, MemoryUnit, OperationOptions};
+use crate::sorting::{SortOrder, PositionSort, ProcessSort, SortKey, SummarySort};  // 从 sorting 模块导入
 use crate::features;
+use crate::filter::{WindowFilter, PidSet, TitleSource, WindowTargets, parse_ex_style};
+use crate::config::{EnvDefaults, FilterProfile};
+use crate::types::{RectMode, WindowRect, DuplicateKey};
+use crate::process::ProcessFields;
 
 pub struct CliConfig {
-    pub pid_filter: Option<String>,
-    pub name_filter: Option<String>,
-    pub title_filter: Option<String>,
+    pub filter: WindowFilter,
     pub has_window_filter: bool,
     pub no_window_filter: bool,
+    pub min_memory_percent: Option<f64>,
+    pub user: Option<String>,
+    pub bitness: Option<String>,
+    pub include_parents: bool,
     pub format: OutputFormat,
     pub verbose: bool,
+    pub ascii: bool,
+    pub strip_control: bool,
+    /// 读取完整的窗口标题，不在 511 个字符处截断
+    pub full_title: bool,
+    pub json: bool,
+    pub compact: bool,
+    pub error_format: ErrorFormat,
+    pub memory_unit: MemoryUnit,
+    pub table_width: Option<usize>,
+    pub no_truncate: bool,
+    pub tee: Option<String>,
+    pub sort: ProcessSort,
+    /// `--group-by name --summary`：按进程名汇总，值为汇总结果的排序依据
+    pub name_summary: Option<SummarySort>,
+    /// 进程列表的刷新间隔（秒，--watch）
+    pub watch: Option<u64>,
     pub subcommand: Option<SubCommand>,
 }
 
+impl CliConfig {
+    /// 进程列表需要的进程字段：标题列和 --has-window 需要枚举窗口，其余字段只在过滤或输出时查询
+    pub fn process_fields(&self) -> ProcessFields {
+        let all_fields = self.format.shows_all_fields();
+        ProcessFields {
+            windows: true,
+            bitness: all_fields || self.bitness.is_some(),
+            services: all_fields,
+            // 表格只在 --verbose 时显示用户
+            user: all_fields || self.user.is_some() || (self.verbose && matches!(self.format, OutputFormat::Table)),
+        }
+    }
+}
+
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum SubCommand {
+    /// 列出编译进来的特性及其在当前环境下的可用性
+    Features,
+    /// 交互式窗口浏览（`tui` 特性）
+    #[cfg(feature = "tui")]
+    Tui {
+        filter: WindowFilter,
+    },
+    /// 输出平台、权限、窗口枚举、特性和显示器等诊断信息
+    Doctor {
+        format: OutputFormat,
+    },
     WindowsGet {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         format: OutputFormat,
         sort_pid: SortOrder,
         sort_position: PositionSort,
+        /// `--sort` 组合排序键，在 sort_pid/sort_position 之后应用
+        sort_keys: Vec<SortKey>,
+        empty_as: EmptyAs,
+        watch: Option<u64>,
+        diff: bool,
+        rect_mode: RectMode,
+        monitor_index: Option<u32>,
+        group_by_monitor: bool,
+        summary_only: bool,
+        sort_count: SortOrder,
+        count_per_monitor: bool,
+        desktop: Option<u32>,
+        current_desktop: bool,
+        nested_rect: bool,
+        min_aspect: Option<f64>,
+        max_aspect: Option<f64>,
+        /// Some(true) 只显示响应的窗口，Some(false) 只显示未响应的窗口
+        responding: Option<bool>,
+        /// --near-center 的容差（像素），None 表示不按显示器中心过滤
+        near_center: Option<u32>,
+        /// --duplicates-only：只保留标题（或标题和类名）与其他窗口相同的窗口
+        duplicates: Option<DuplicateKey>,
+        /// --one：恰好匹配一个窗口，否则返回错误
+        one: bool,
     },
     WindowsMinimize {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         sort_position: PositionSort,
+        options: OperationOptions,
+        wait_for: Option<String>,
+        format: OutputFormat,
     },
     WindowsMaximize {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         sort_position: PositionSort,
+        options: OperationOptions,
+        wait_for: Option<String>,
+        format: OutputFormat,
     },
     WindowsRestore {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         sort_position: PositionSort,
+        options: OperationOptions,
+        wait_for: Option<String>,
+        format: OutputFormat,
+    },
+    WindowsHide {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        wait_for: Option<String>,
+        format: OutputFormat,
+        /// 跳过操作多个窗口时的确认提示
+        yes: bool,
+    },
+    WindowsShow {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        wait_for: Option<String>,
+        format: OutputFormat,
     },
     WindowsPositionSet {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         position: Option<String>,
         index: Option<String>,
         layout: Option<String>,
+        layout_file: Option<String>,
         x_start: Option<String>,
         y_start: Option<String>,
         x_step: Option<String>,
         y_step: Option<String>,
+        validate_onscreen: bool,
+        clamp: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
     },
     WindowsAlwaysOnTop {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         toggle: bool,
         off: bool,
+        force: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+        /// 只列出当前置顶的匹配窗口，不修改任何窗口
+        list: bool,
     },
     WindowsTransparency {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         level: u8,
         reset: bool,
+        gradient: Option<String>,
+        min_opacity: u8,
+        allow_invisible: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
     },
     WindowsResize {
-        pid: Option<String>,
-        name: Option<String>,
-        title: Option<String>,
+        filter: WindowFilter,
         all: bool,
         index: Option<String>,
         width: Option<String>,
@@ -97,19 +198,106 @@ pub enum SubCommand {
         keep_position: bool,
         center: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsSetBounds {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        rect: WindowRect,
+        /// `rect` 表示可见边框（不含阴影）
+        frame_bounds: bool,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsCapture {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        out: String,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsIcon {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        out: String,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsState {
+        filter: WindowFilter,
+        index: Option<String>,
+        sort_position: PositionSort,
+        format: OutputFormat,
+    },
+    #[cfg(feature = "undo")]
+    WindowsUndo {
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsPin {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        position: String,
+        duration: String,
+        interval: String,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsMoveToMonitor {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        monitor: u32,
+        center: bool,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsApply {
+        file: String,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsSet {
+        filter: WindowFilter,
+        all: bool,
+        index: Option<String>,
+        x: Option<i32>,
+        y: Option<i32>,
+        width: Option<i32>,
+        height: Option<i32>,
+        /// 不透明度百分比（0-100）
+        opacity: Option<u8>,
+        topmost: Option<bool>,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsResetAll {
+        filter: WindowFilter,
+        all: bool,
+        dry_run: bool,
+        options: OperationOptions,
+        format: OutputFormat,
+    },
+    WindowsDiff {
+        before: String,
+        after: String,
+        format: OutputFormat,
     },
 }
 
 // 删除原来的 SortOrder 和 PositionSort 定义，因为它们已移动到 sorting.rs
 
-// 统一的字段提取函数
-fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-    let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-    let name = matches.get_one::<String>("name").map(|s| s.to_string());
-    let title = matches.get_one::<String>("title").map(|s| s.to_string());
-    (pid, name, title)
-}
-
 // 构建主命令的通用参数
 fn build_common_args(command: Command) -> Command {
     command
@@ -118,7 +306,8 @@ fn build_common_args(command: Command) -> Command {
                 .short('p')
                 .long("pid")
                 .value_name("PID")
-                .help("Filter by process ID")
+                .value_parser(PidSet::parse)
+                .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
         )
         .arg(
             Arg::new("name")
@@ -134,6 +323,7 @@ fn build_common_args(command: Command) -> Command {
                 .value_name("TITLE")
                 .help("Filter by window title (contains)")
         )
+        .args(WindowFilter::exact_match_args())
         .arg(
             Arg::new("has_window")
                 .long("has-window")
@@ -147,14 +337,125 @@ fn build_common_args(command: Command) -> Command {
                 .help("Show only processes without windows")
                 .conflicts_with("has_window")
         )
+        .arg(
+            Arg::new("min_memory_percent")
+                .long("min-memory-percent")
+                .value_name("PERCENT")
+                .value_parser(clap::value_parser!(f64))
+                .help("Show only processes using at least PERCENT of total system memory")
+        )
+        .arg(
+            Arg::new("user")
+                .long("user")
+                .value_name("USER")
+                .help("Show only processes owned by USER (case-insensitive; Windows domain prefix optional)")
+        )
+        .arg(
+            Arg::new("bitness")
+                .long("bitness")
+                .value_name("ARCH")
+                .value_parser(["x86", "x64"])
+                .help("Show only 32-bit (x86) or 64-bit (x64) processes (Windows only)")
+        )
+        .arg(
+            Arg::new("include_parents")
+                .long("include-parents")
+                .visible_aliases(["parents", "ancestors"])
+                .action(clap::ArgAction::SetTrue)
+                .help("Also show the parent chain of every matched process up to the root, marked as ancestors")
+        )
+        .arg(
+            Arg::new("group_by")
+                .long("group-by")
+                .value_name("KEY")
+                .value_parser(["name"])
+                .requires("summary")
+                .help("Group processes by KEY (only \"name\"); use with --summary")
+        )
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .action(clap::ArgAction::SetTrue)
+                .requires("group_by")
+                .help("Print one row per group with the process count and total memory instead of listing processes")
+        )
+        .arg(
+            Arg::new("summary_sort")
+                .long("summary-sort")
+                .value_name("KEY")
+                .value_parser(clap::value_parser!(SummarySort))
+                .default_value("count")
+                .help("With --summary, sort groups by count or memory (descending; --reverse for ascending)")
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .value_name("SECONDS")
+                .num_args(0..=1)
+                .default_missing_value("2")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .conflicts_with("summary")
+                .help("Refresh the process list every SECONDS (default 2) until interrupted; the table shows each process's memory change since the previous refresh")
+        )
         .arg(
             Arg::new("format")
                 .short('f')
                 .long("format")
                 .value_name("FORMAT")
                 .value_parser(clap::value_parser!(OutputFormat))
-                .default_value("table")
-                .help("Output format")
+                .default_value("auto")
+                .help("Output format (auto: table on a terminal, json when piped)")
+        )
+        .arg(
+            Arg::new("sort_pid")
+                .long("sort-pid")
+                .value_name("ORDER")
+                .num_args(1)
+                .allow_hyphen_values(true)
+                .value_parser(["1", "-1", "0"])
+                .default_value("0")
+                .help("Sort by PID: 1 (ascending), -1 (descending), 0 (none)")
+        )
+        .arg(
+            Arg::new("sort_name")
+                .long("sort-name")
+                .value_name("ORDER")
+                .num_args(1)
+                .allow_hyphen_values(true)
+                .value_parser(["1", "-1", "0"])
+                .default_value("0")
+                .help("Sort by process name: 1 (ascending), -1 (descending), 0 (none)")
+        )
+        .arg(
+            Arg::new("sort_memory")
+                .long("sort-memory")
+                .value_name("ORDER")
+                .num_args(1)
+                .allow_hyphen_values(true)
+                .value_parser(["1", "-1", "0"])
+                .default_value("0")
+                .help("Sort by memory usage: 1 (ascending), -1 (descending), 0 (none); keys apply as name, memory, pid")
+        )
+        .arg(
+            Arg::new("reverse")
+                .long("reverse")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Reverse the final sort order (combines with per-key directions)")
+        )
+        .arg(
+            Arg::new("strict_sort")
+                .long("strict-sort")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Sort by the given key only: no title/PID fallback, ties keep enumeration order")
+        )
+        .arg(
+            Arg::new("no_sort")
+                .long("no-sort")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Keep results in the order the OS reports them (window Z-order), ignoring every sort option including command defaults")
         )
         .arg(
             Arg::new("verbose")
@@ -163,14 +464,222 @@ fn build_common_args(command: Command) -> Command {
                 .action(clap::ArgAction::SetTrue)
                 .help("Show detailed information")
         )
+        .arg(
+            Arg::new("match_any")
+                .long("match-any")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Match when any of the --pid, --name, --title, --service, --ex-style, --uia-name and --automation-id filters matches (default: all must match)")
+        )
+        .arg(
+            Arg::new("from_json")
+                .long("from-json")
+                .value_name("FILE")
+                .global(true)
+                .value_parser(WindowTargets::load)
+                .help("Only target the windows saved in FILE by 'windows/get --format json' (re-matched by PID and title)")
+        )
+        .arg(
+            Arg::new("per_process")
+                .long("per-process")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Window operations: act on at most one window per process (the first after sorting), before --index selection")
+        )
+        .arg(
+            Arg::new("exclude_self")
+                .long("exclude-self")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Exclude pscan's own process from process and window results")
+        )
+        .arg(
+            Arg::new("tid")
+                .long("tid")
+                .value_name("THREAD_ID")
+                .global(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Window commands: only enumerate top-level windows created by this thread (Windows only; faster than full enumeration)")
+        )
+        .arg(
+            Arg::new("title_source")
+                .long("title-source")
+                .value_name("SOURCE")
+                .global(true)
+                .value_parser(clap::value_parser!(TitleSource))
+                .default_value("window")
+                .help("Window commands: which title --title/--title-exact tests: the window's own title, the owning process's title (as in the process list), or both")
+        )
+        .arg(
+            Arg::new("exclude_pid")
+                .long("exclude-pid")
+                .value_name("PID")
+                .global(true)
+                .value_parser(PidSet::parse)
+                .help("Drop process ID(s) from process and window results: single, list or range (e.g., 1234, 10,20, 100-200)")
+        )
+        .arg(
+            Arg::new("include_zero_size")
+                .long("include-zero-size")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Keep windows whose width or height is zero or negative (usually being created or destroyed)")
+        )
+        .arg(
+            Arg::new("service")
+                .long("service")
+                .value_name("SERVICE")
+                .global(true)
+                .help("Filter by the Windows service hosted in the owning process (contains, case-insensitive; e.g., svchost instances)")
+        )
+        .arg(
+            Arg::new("ex_style")
+                .long("ex-style")
+                .value_name("STYLES")
+                .global(true)
+                .value_parser(parse_ex_style)
+                .help("Only match windows whose extended style has all the given bits, e.g. TOPMOST|LAYERED or TOOLWINDOW (Windows only)")
+        )
+        .arg(
+            Arg::new("uia_name")
+                .long("uia-name")
+                .value_name("NAME")
+                .global(true)
+                .help("Only match windows whose UI Automation name contains NAME (case-insensitive; Windows only, requires the uia feature)")
+        )
+        .arg(
+            Arg::new("automation_id")
+                .long("automation-id")
+                .value_name("ID")
+                .global(true)
+                .help("Only match windows whose UI Automation AutomationId is ID (exact, case-insensitive; Windows only, requires the uia feature)")
+        )
+        .arg(
+            Arg::new("filter_profile")
+                .long("filter-profile")
+                .value_name("NAME")
+                .global(true)
+                .value_parser(FilterProfile::resolve)
+                .help("Use the named filter preset from the config file; explicit --pid/--name/--title options take precedence")
+        )
+        .arg(
+            Arg::new("at_cursor")
+                .long("at-cursor")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Act on the top-level window under the mouse pointer instead of matching --pid/--name/--title (Windows only)")
+        )
+        .arg(
+            Arg::new("table_width")
+                .long("table-width")
+                .value_name("COLUMNS")
+                .global(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Table width in columns (default: terminal width); the title column takes the extra space")
+        )
+        .arg(
+            Arg::new("no_truncate")
+                .long("no-truncate")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Never shorten long names or titles in table output")
+        )
+        .arg(
+            Arg::new("delay")
+                .long("delay")
+                .value_name("MS")
+                .global(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Wait MS milliseconds between per-window operations (smooths out --all on many windows)")
+        )
+        .arg(
+            Arg::new("skip_hung")
+                .long("skip-hung")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Check each window before operating on it and skip windows that are not responding, so one hung app cannot stall the whole operation")
+        )
+        .arg(
+            Arg::new("tee")
+                .long("tee")
+                .value_name("FILE")
+                .global(true)
+                .help("Also append the formatted results to FILE while printing them")
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Shorthand for --format json on every command, including window operations and features")
+        )
+        .arg(
+            Arg::new("strip_control")
+                .long("strip-control")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Remove control characters (CR, LF, tab, ...) from titles in every output format")
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Print JSON output on a single line")
+        )
+        .arg(
+            Arg::new("pretty")
+                .long("pretty")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("compact")
+                .help("Print JSON output indented (default)")
+        )
+        .arg(
+            Arg::new("memory_unit")
+                .long("memory-unit")
+                .value_name("UNIT")
+                .global(true)
+                .value_parser(clap::value_parser!(MemoryUnit))
+                .default_value("mb")
+                .help("Unit for memory values in table/simple/detailed output: b (raw bytes, no unit), kb, mb, gb or auto (per value); JSON, YAML and CSV are unaffected")
+        )
+        .arg(
+            Arg::new("error_format")
+                .long("error-format")
+                .value_name("FORMAT")
+                .global(true)
+                .value_parser(clap::value_parser!(ErrorFormat))
+                .default_value("text")
+                .help("Format of error messages on stderr: text or json (one object with error kind, message and exit_code); stdout only ever carries results")
+        )
+        .arg(
+            Arg::new("full_title")
+                .long("full-title")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Read window titles of any length (by default titles are cut at 511 UTF-16 units)")
+        )
+        .arg(
+            Arg::new("ascii")
+                .long("ascii")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("Replace non-ASCII characters in human-readable output with '?' (for legacy terminals)")
+        )
 }
 
 // 删除原来的 build_windows_get_command 和 handle_subcommand_matches 函数
 
 pub fn parse_args() -> CliConfig {
     let feature_manager = features::create_default_manager();
-    
-    let matches = build_common_args(
+    let matches = build_cli(&feature_manager).get_matches();
+    parse_matches(&feature_manager, &matches)
+}
+
+/// 构建完整的命令行：公共参数、已注册特性的子命令以及 features/doctor 子命令
+fn build_cli(feature_manager: &features::FeatureManager) -> Command {
+    let command = build_common_args(
         Command::new("Process Filter")
             .version(env!("CARGO_PKG_VERSION"))
             .author(env!("CARGO_PKG_AUTHORS"))
@@ -180,33 +689,149 @@ pub fn parse_args() -> CliConfig {
     );
     
     // 使用特性管理器构建 CLI（现在包含所有窗口操作命令）
-    let matches = feature_manager.build_cli(matches)
-        // 为未来扩展预留
+    feature_manager.build_cli(command)
         .subcommand(
-            Command::new("windows/set")
-                .about("Set window properties (future)")
-                .arg(Arg::new("pid").required(true))
+            Command::new("features")
+                .about("List compiled-in features and whether they are usable in this environment")
         )
         .subcommand(
-            Command::new("windows/move")
-                .about("Move window position (future)")
-                .arg(Arg::new("pid").required(true))
+            Command::new("doctor")
+                .about("Report platform, elevation, window enumeration, features, DPI awareness and monitors (paste this when filing bugs)")
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .value_parser(clap::value_parser!(OutputFormat))
+                        .default_value("auto")
+                        .help("Output format (auto: table on a terminal, json when piped)")
+                )
         )
-        .get_matches();
+}
 
-    // 完全使用特性管理器解析子命令
-    let subcommand = feature_manager.parse_cli(&matches);
+fn parse_matches(feature_manager: &features::FeatureManager, matches: &clap::ArgMatches) -> CliConfig {
+    // features 和 doctor 子命令由主程序处理，其余子命令使用特性管理器解析
+    let subcommand = if matches.subcommand_matches("features").is_some() {
+        Some(SubCommand::Features)
+    } else if let Some(doctor_matches) = matches.subcommand_matches("doctor") {
+        Some(SubCommand::Doctor { format: json_or_format(doctor_matches) })
+    } else {
+        feature_manager.parse_cli(matches)
+    };
 
-    let (pid_filter, name_filter, title_filter) = extract_filter_args(&matches);
-    
     CliConfig {
-        pid_filter,
-        name_filter,
-        title_filter,
+        filter: WindowFilter::from_matches(matches),
         has_window_filter: matches.get_flag("has_window"),
         no_window_filter: matches.get_flag("no_window"),
-        format: matches.get_one::<OutputFormat>("format").unwrap().clone(),
+        min_memory_percent: matches.get_one::<f64>("min_memory_percent").copied(),
+        user: matches.get_one::<String>("user").cloned(),
+        bitness: matches.get_one::<String>("bitness").cloned(),
+        include_parents: matches.get_flag("include_parents"),
+        format: json_or_format(matches),
         verbose: matches.get_flag("verbose"),
+        ascii: matches.get_flag("ascii"),
+        full_title: matches.get_flag("full_title"),
+        strip_control: matches.get_flag("strip_control"),
+        json: matches.get_flag("json"),
+        compact: matches.get_flag("compact"),
+        error_format: matches.get_one::<ErrorFormat>("error_format").copied().unwrap_or_default(),
+        memory_unit: matches.get_one::<MemoryUnit>("memory_unit").copied().unwrap_or_default(),
+        table_width: matches.get_one::<usize>("table_width").copied(),
+        no_truncate: matches.get_flag("no_truncate"),
+        tee: matches.get_one::<String>("tee").cloned(),
+        sort: if matches.get_flag("no_sort") {
+            ProcessSort::default()
+        } else {
+            ProcessSort {
+                pid: parse_sort_order(matches, "sort_pid"),
+                name: parse_sort_order(matches, "sort_name"),
+                memory: parse_sort_order(matches, "sort_memory"),
+                reverse: matches.get_flag("reverse"),
+            }
+        },
+        name_summary: matches
+            .get_flag("summary")
+            .then(|| matches.get_one::<SummarySort>("summary_sort").copied().unwrap_or_default()),
+        watch: matches.get_one::<u64>("watch").copied(),
         subcommand,
     }
-}
\ No newline at end of file
+}
+
+/// 读取 `--format`（`auto` 按标准输出是否为终端解析），全局 `--json` 优先；
+/// 未给出 `--format` 时使用环境变量 `PSCAN_FORMAT`
+pub fn json_or_format(matches: &clap::ArgMatches) -> OutputFormat {
+    if matches.get_flag("json") {
+        return OutputFormat::Json;
+    }
+    let format = match (matches.value_source("format"), &EnvDefaults::get().format) {
+        (Some(ValueSource::DefaultValue) | None, Some(env_format)) => Some(env_format.clone()),
+        _ => matches.get_one::<OutputFormat>("format").cloned(),
+    };
+    format.unwrap_or(OutputFormat::Auto).resolve()
+}
+
+fn parse_sort_order(matches: &clap::ArgMatches, id: &str) -> SortOrder {
+    matches
+        .get_one::<String>(id)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在任意平台上用所有内置特性构建完整的命令行并解析
+    fn parse(args: &[&str]) -> CliConfig {
+        let feature_manager = features::builtin_manager();
+        let matches = build_cli(&feature_manager).try_get_matches_from(args).unwrap();
+        parse_matches(&feature_manager, &matches)
+    }
+
+    #[test]
+    fn test_parse_table_width() {
+        assert_eq!(parse(&["pscan", "--table-width", "80"]).table_width, Some(80));
+        assert_eq!(parse(&["pscan", "windows/get", "--table-width", "80"]).table_width, Some(80));
+    }
+
+    #[test]
+    fn test_parse_resize_width() {
+        let config = parse(&["pscan", "windows/resize", "--all", "--width", "100", "--height", "100"]);
+        assert_eq!(config.table_width, None);
+        assert!(matches!(
+            config.subcommand,
+            Some(SubCommand::WindowsResize { width: Some(ref w), height: Some(ref h), .. }) if w == "100" && h == "100"
+        ));
+    }
+
+    #[test]
+    fn test_parse_set_width() {
+        let config = parse(&["pscan", "windows/set", "--all", "--x", "-10", "--width", "100", "--height", "200"]);
+        assert_eq!(config.table_width, None);
+        assert!(matches!(
+            config.subcommand,
+            Some(SubCommand::WindowsSet { x: Some(-10), width: Some(100), height: Some(200), .. })
+        ));
+    }
+
+    #[test]
+    fn test_process_fields() {
+        assert!(!parse(&["pscan", "--format", "table"]).process_fields().bitness);
+        assert!(parse(&["pscan", "--format", "table", "--bitness", "x64"]).process_fields().bitness);
+        assert!(parse(&["pscan", "--format", "json"]).process_fields().bitness);
+        assert!(!parse(&["pscan", "--format", "table"]).process_fields().services);
+        assert!(parse(&["pscan", "--format", "detailed"]).process_fields().services);
+        assert!(!parse(&["pscan", "--format", "table"]).process_fields().user);
+        assert!(parse(&["pscan", "--format", "table", "--verbose"]).process_fields().user);
+        assert!(parse(&["pscan", "--format", "simple", "--user", "alice"]).process_fields().user);
+    }
+
+    #[test]
+    fn test_parse_operation_options() {
+        let config = parse(&["pscan", "--delay", "50", "windows/minimize", "--all", "--profile", "--skip-hung"]);
+        assert!(matches!(
+            config.subcommand,
+            Some(SubCommand::WindowsMinimize { options: OperationOptions { profile: true, delay: 50, skip_hung: true }, .. })
+        ));
+    }
+}
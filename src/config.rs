@@ -0,0 +1,162 @@
+// src/config.rs
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use clap::ValueEnum;
+use serde::Deserialize;
+use crate::error::{AppError, AppResult};
+use crate::filter::PidSet;
+use crate::output::OutputFormat;
+
+/// 配置文件（YAML）
+///
+/// 默认位于 `<配置目录>/pscan/config.yaml`（Windows 为 `%APPDATA%`，其他平台为
+/// `$XDG_CONFIG_HOME` 或 `~/.config`），可用环境变量 `PSCAN_CONFIG` 指定其他路径。
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// 命名的过滤条件预设，通过 `--filter-profile NAME` 使用
+    #[serde(default)]
+    pub profiles: BTreeMap<String, FilterProfile>,
+}
+
+/// 过滤条件预设；命令行上显式给出的同类条件优先于预设中的值
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilterProfile {
+    pub pid: Option<PidSet>,
+    pub name: Option<String>,
+    pub name_exact: Option<String>,
+    pub title: Option<String>,
+    pub title_exact: Option<String>,
+}
+
+impl Config {
+    /// 配置文件路径；无法确定配置目录时返回 None
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("PSCAN_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let base = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+        base.map(|dir| dir.join("pscan").join("config.yaml"))
+    }
+
+    /// 读取配置文件；文件不存在时返回空配置
+    pub fn load() -> AppResult<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::parse(&std::fs::read_to_string(&path)?)
+                .map_err(|e| AppError::parse(format!("{}: {}", path.display(), e))),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    fn parse(text: &str) -> AppResult<Self> {
+        // 空文件视为空配置
+        if text.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(serde_yaml::from_str(text)?)
+    }
+}
+
+impl FilterProfile {
+    /// 按名称从配置文件中解析预设，可直接用作 clap 的 value_parser
+    pub fn resolve(name: &str) -> AppResult<Self> {
+        Self::lookup(Config::load()?, name)
+    }
+
+    fn lookup(mut config: Config, name: &str) -> AppResult<Self> {
+        config.profiles.remove(name).ok_or_else(|| {
+            let defined: Vec<&str> = config.profiles.keys().map(|k| k.as_str()).collect();
+            AppError::invalid_parameter(if defined.is_empty() {
+                format!("Unknown filter profile '{}': no profiles defined in the config file", name)
+            } else {
+                format!("Unknown filter profile '{}' (defined: {})", name, defined.join(", "))
+            })
+        })
+    }
+}
+
+/// 环境变量 `PSCAN_NAME`、`PSCAN_PID`、`PSCAN_FORMAT` 提供的默认值
+///
+/// 优先级：命令行 > 环境变量 > 配置文件中的预设（`--filter-profile`）。空值视为未设置，无效值警告后忽略。
+#[derive(Debug, Default)]
+pub struct EnvDefaults {
+    /// 进程名（包含匹配），对应 `--name`
+    pub name: Option<String>,
+    pub pid: Option<PidSet>,
+    pub format: Option<OutputFormat>,
+}
+
+impl EnvDefaults {
+    /// 读取一次环境变量，之后复用结果（避免重复输出警告）
+    pub fn get() -> &'static Self {
+        static ENV_DEFAULTS: OnceLock<EnvDefaults> = OnceLock::new();
+        ENV_DEFAULTS.get_or_init(|| Self::from_lookup(|key| std::env::var(key).ok()))
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |key: &str| lookup(key).filter(|value| !value.trim().is_empty());
+        Self {
+            name: var("PSCAN_NAME"),
+            pid: var("PSCAN_PID").and_then(|value| {
+                PidSet::parse(&value)
+                    .map_err(|e| eprintln!("Warning: Ignoring PSCAN_PID: {}", e))
+                    .ok()
+            }),
+            format: var("PSCAN_FORMAT").and_then(|value| {
+                OutputFormat::from_str(value.trim(), true)
+                    .map_err(|_| eprintln!("Warning: Ignoring invalid PSCAN_FORMAT '{}'", value))
+                    .ok()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_profiles_parse() {
+        let config = Config::parse(
+            "profiles:\n  dev:\n    name: code\n    title_exact: Terminal\n  pinned:\n    pid: 42\n  range:\n    pid: \"100-200\"\n",
+        )
+        .unwrap();
+
+        let dev = FilterProfile::lookup(Config::parse("profiles:\n  dev:\n    name: code\n").unwrap(), "dev").unwrap();
+        assert_eq!(dev.name.as_deref(), Some("code"));
+        assert_eq!(config.profiles["dev"].title_exact.as_deref(), Some("Terminal"));
+        assert_eq!(config.profiles["pinned"].pid.as_ref().and_then(PidSet::single), Some(42));
+        assert!(config.profiles["range"].pid.as_ref().is_some_and(|p| p.contains(150)));
+
+        let err = FilterProfile::lookup(config, "missing").unwrap_err().to_string();
+        assert!(err.contains("dev, pinned, range"), "{}", err);
+
+        assert!(Config::parse("").unwrap().profiles.is_empty());
+        assert!(Config::parse("profiles:\n  bad:\n    regex: x\n").is_err());
+        assert!(Config::parse("profiles:\n  bad:\n    pid: \"9-1\"\n").is_err());
+    }
+
+    #[test]
+    fn test_env_defaults() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            EnvDefaults::from_lookup(move |key| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string()))
+        };
+
+        let defaults = env(&[("PSCAN_NAME", "chrome"), ("PSCAN_PID", "10-20"), ("PSCAN_FORMAT", "JSON")]);
+        assert_eq!(defaults.name.as_deref(), Some("chrome"));
+        assert!(defaults.pid.as_ref().is_some_and(|p| p.contains(15)));
+        assert!(matches!(defaults.format, Some(OutputFormat::Json)));
+
+        // 空值和无效值都被忽略
+        let ignored = env(&[("PSCAN_NAME", " "), ("PSCAN_PID", "abc"), ("PSCAN_FORMAT", "xml")]);
+        assert!(ignored.name.is_none() && ignored.pid.is_none() && ignored.format.is_none());
+    }
+}
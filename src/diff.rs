@@ -0,0 +1,186 @@
+// src/diff.rs
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::error::{AppError, AppResult};
+use crate::types::{WindowInfo, WindowRecord};
+use crate::output::{outln, clean_title};
+
+/// 两个窗口快照之间的单项变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum WindowChange {
+    Added { window: WindowInfo },
+    Removed { window: WindowInfo },
+    /// 同一窗口（PID + 标题相同）的位置或尺寸发生变化
+    Changed { before: WindowInfo, after: WindowInfo },
+}
+
+impl WindowChange {
+    /// 变化类型描述：added / removed / moved / resized / moved+resized
+    pub fn kind(&self) -> &'static str {
+        match self {
+            WindowChange::Added { .. } => "added",
+            WindowChange::Removed { .. } => "removed",
+            WindowChange::Changed { before, after } => {
+                let moved = before.rect.x != after.rect.x || before.rect.y != after.rect.y;
+                let resized = before.rect.width != after.rect.width || before.rect.height != after.rect.height;
+                match (moved, resized) {
+                    (true, true) => "moved+resized",
+                    (false, true) => "resized",
+                    _ => "moved",
+                }
+            }
+        }
+    }
+
+    /// 变化后的窗口（已移除的窗口返回移除前的信息）
+    pub fn window(&self) -> &WindowInfo {
+        match self {
+            WindowChange::Added { window } | WindowChange::Removed { window } => window,
+            WindowChange::Changed { after, .. } => after,
+        }
+    }
+}
+
+/// 比较两个窗口快照
+///
+/// 完全相同的窗口被视为未变化；剩余窗口按 PID + 标题依次配对，
+/// 配对成功的记为位置/尺寸变化，其余记为新增或移除。
+pub fn diff_windows(before: &[WindowInfo], after: &[WindowInfo]) -> Vec<WindowChange> {
+    // 先抵消完全相同的窗口（同一窗口可能出现多次，按次数计算）
+    let mut unchanged: HashMap<&WindowInfo, usize> = HashMap::new();
+    for window in before {
+        *unchanged.entry(window).or_default() += 1;
+    }
+
+    let mut added: Vec<&WindowInfo> = Vec::new();
+    for window in after {
+        match unchanged.get_mut(window) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => added.push(window),
+        }
+    }
+
+    // 剩余未抵消的计数即为被移除或发生变化的窗口
+    let mut removed: Vec<&WindowInfo> = Vec::new();
+    for window in before {
+        if let Some(count) = unchanged.get_mut(window) {
+            if *count > 0 {
+                *count -= 1;
+                removed.push(window);
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    let mut removed_left = Vec::new();
+    for old in removed {
+        match added.iter().position(|new| new.pid == old.pid && new.title == old.title) {
+            Some(i) => {
+                let new = added.remove(i);
+                changes.push(WindowChange::Changed { before: old.clone(), after: new.clone() });
+            }
+            None => removed_left.push(old),
+        }
+    }
+
+    changes.extend(removed_left.into_iter().map(|window| WindowChange::Removed { window: window.clone() }));
+    changes.extend(added.into_iter().map(|window| WindowChange::Added { window: window.clone() }));
+    changes
+}
+
+/// 逐行输出窗口变化
+pub fn print_changes(changes: &[WindowChange]) -> AppResult<()> {
+    for change in changes {
+        let window = change.window();
+        let geometry = match change {
+            WindowChange::Changed { before, after } => format!("{} -> {}", before.rect, after.rect),
+            _ => window.rect.to_string(),
+        };
+        outln!("{:<14} {} (PID: {}) {}", change.kind(), clean_title(&window.title), window.pid, geometry);
+    }
+    Ok(())
+}
+
+/// 读取 `windows/get --format json` 保存的窗口快照
+pub fn load_snapshot(path: &str) -> AppResult<Vec<WindowInfo>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::invalid_parameter(format!("Failed to read snapshot '{}': {}", path, e)))?;
+
+    // 扁平和 --nested-rect 两种快照形式都接受
+    let entries: Vec<WindowRecord> = serde_json::from_str(&content)
+        .map_err(|e| AppError::parse(format!("Invalid snapshot '{}': {}", path, e)))?;
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            entry.into_window()
+                .map_err(|pid| AppError::parse(format!("Invalid PID '{}' in snapshot '{}'", pid, path)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WindowRect;
+
+    fn window(pid: u32, title: &str, x: i32, y: i32, width: i32, height: i32) -> WindowInfo {
+        WindowInfo::for_test(pid, title, WindowRect::new(x, y, width, height))
+    }
+
+    #[test]
+    fn test_diff_windows() {
+        let before = vec![
+            window(1, "Editor", 0, 0, 800, 600),
+            window(2, "Browser", 100, 100, 1024, 768),
+            window(3, "Terminal", 50, 50, 640, 480),
+            window(4, "Chat", 0, 0, 300, 500),
+        ];
+        let after = vec![
+            window(1, "Editor", 0, 0, 800, 600),
+            window(2, "Browser", 200, 100, 1024, 768),
+            window(3, "Terminal", 60, 50, 800, 480),
+            window(5, "Player", 10, 10, 400, 300),
+        ];
+
+        let changes = diff_windows(&before, &after);
+        let kinds: Vec<(&str, u32)> = changes.iter().map(|c| (c.kind(), c.window().pid)).collect();
+        assert_eq!(kinds, vec![("moved", 2), ("moved+resized", 3), ("removed", 4), ("added", 5)]);
+
+        assert!(diff_windows(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_diff_windows_duplicates() {
+        let before = vec![window(1, "Untitled", 0, 0, 100, 100)];
+        let after = vec![window(1, "Untitled", 0, 0, 100, 100), window(1, "Untitled", 0, 0, 100, 100)];
+
+        let changes = diff_windows(&before, &after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind(), "added");
+    }
+
+    #[test]
+    fn test_window_record_forms() {
+        let flat = r#"{"pid": "1", "title": "Editor", "x": 10, "y": 20, "width": 800, "height": 600}"#;
+        let nested = r#"{"pid": "1", "title": "Editor", "rect": {"x": 10, "y": 20, "width": 800, "height": 600}}"#;
+
+        for json in [flat, nested] {
+            let record: WindowRecord = serde_json::from_str(json).unwrap();
+            assert_eq!(record.into_window().unwrap(), window(1, "Editor", 10, 20, 800, 600));
+        }
+
+        // 输出的嵌套形式可以被读回
+        let output = serde_json::to_string(&WindowRecord::new(&window(2, "Browser", 0, 0, 1024, 768), "chrome", true)).unwrap();
+        assert!(output.contains(r#""rect":{"x":0,"y":0,"width":1024,"height":768}"#));
+        let record: WindowRecord = serde_json::from_str(&output).unwrap();
+        assert_eq!(record.into_window().unwrap(), window(2, "Browser", 0, 0, 1024, 768));
+
+        // 未响应状态可以被读回；未探测（缺少该字段）时为 None（见上面的 flat/nested）
+        let hung = WindowInfo { responding: Some(false), ..window(3, "Hung", 0, 0, 100, 100) };
+        let output = serde_json::to_string(&WindowRecord::new(&hung, "app", false)).unwrap();
+        let record: WindowRecord = serde_json::from_str(&output).unwrap();
+        assert_eq!(record.into_window().unwrap().responding, Some(false));
+    }
+}
@@ -0,0 +1,116 @@
+// src/doctor.rs
+use serde::Serialize;
+use crate::error::AppResult;
+use crate::features::{feature_statuses, FeatureStatus};
+use crate::output::{delimited_writer, display_text, outln, to_json, OutputFormat};
+use crate::platform;
+use crate::types::RectMode;
+
+/// `doctor` 子命令的诊断报告，便于用户在提交问题时一次性粘贴环境信息
+#[derive(Serialize)]
+struct DoctorReport {
+    version: &'static str,
+    platform: String,
+    /// None 表示无法判断
+    elevated: Option<bool>,
+    /// 窗口枚举可用时为找到的窗口数
+    window_count: Option<usize>,
+    /// 窗口枚举不可用的原因
+    window_error: Option<String>,
+    dpi_awareness: Option<String>,
+    monitor_count: Option<usize>,
+    features: Vec<FeatureStatus>,
+}
+
+impl DoctorReport {
+    /// 收集当前环境的诊断信息
+    fn collect() -> Self {
+        let (window_count, window_error) = match platform::probe_window_support() {
+            Ok(()) => (Some(platform::get_all_windows_with_size(RectMode::Restored, false).len()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            platform: format!("{} ({})", std::env::consts::OS, std::env::consts::ARCH),
+            elevated: platform::is_elevated(),
+            window_count,
+            window_error,
+            dpi_awareness: platform::dpi_awareness(),
+            monitor_count: platform::get_monitor_work_areas().ok().map(|areas| areas.len()),
+            features: feature_statuses(),
+        }
+    }
+
+    /// 表格和 CSV 输出使用的 (检查项, 结果) 行
+    fn rows(&self) -> Vec<(String, String)> {
+        let unknown = || "unknown".to_string();
+        let mut rows = vec![
+            ("version".to_string(), self.version.to_string()),
+            ("platform".to_string(), self.platform.clone()),
+            ("elevated".to_string(), self.elevated.map_or_else(unknown, |e| if e { "yes" } else { "no" }.to_string())),
+            ("windows".to_string(), match (&self.window_count, &self.window_error) {
+                (Some(count), _) => format!("{} found", count),
+                (None, Some(error)) => format!("unavailable: {}", error),
+                (None, None) => unknown(),
+            }),
+            ("dpi_awareness".to_string(), self.dpi_awareness.clone().unwrap_or_else(unknown)),
+            ("monitors".to_string(), self.monitor_count.map_or_else(unknown, |n| n.to_string())),
+        ];
+        for feature in &self.features {
+            let status = match &feature.reason {
+                Some(reason) => format!("{} ({})", feature.status, reason),
+                None => feature.status.to_string(),
+            };
+            rows.push((format!("feature:{}", feature.name), status));
+        }
+        rows
+    }
+}
+
+/// 输出 `doctor` 子命令的诊断报告
+pub fn print_doctor_report(format: OutputFormat) -> AppResult<()> {
+    let report = DoctorReport::collect();
+    match format {
+        OutputFormat::Json => outln!("{}", to_json(&report)?),
+        OutputFormat::Yaml => outln!("{}", serde_yaml::to_string(&report)?),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut wtr = delimited_writer(format.delimiter());
+            wtr.write_record(["Check", "Result"])?;
+            for (check, result) in report.rows() {
+                wtr.write_record([check, result])?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Auto | OutputFormat::Table | OutputFormat::Simple | OutputFormat::Detailed => {
+            for (check, result) in report.rows() {
+                outln!("{:<28} {}", check, display_text(&result));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doctor_rows() {
+        let report = DoctorReport {
+            version: "0.1.0",
+            platform: "linux (x86_64)".to_string(),
+            elevated: None,
+            window_count: None,
+            window_error: Some("no display".to_string()),
+            dpi_awareness: None,
+            monitor_count: Some(2),
+            features: vec![FeatureStatus { name: "capture", status: "unsupported", description: "", reason: Some("not available".to_string()) }],
+        };
+        let rows = report.rows();
+        assert!(rows.contains(&("elevated".to_string(), "unknown".to_string())));
+        assert!(rows.contains(&("windows".to_string(), "unavailable: no display".to_string())));
+        assert!(rows.contains(&("monitors".to_string(), "2".to_string())));
+        assert_eq!(rows.last().unwrap(), &("feature:capture".to_string(), "unsupported (not available)".to_string()));
+    }
+}
@@ -2,6 +2,7 @@
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[allow(dead_code)]
 pub enum AppError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -14,9 +15,16 @@ pub enum AppError {
     
     #[error("No matching windows found")]
     NoMatchingWindows,
+
+    #[error("No process found with PID {0}")]
+    NoSuchProcess(String),
+
+    #[error("Process exists but has no matching window (PID: {0})")]
+    NoWindowForProcess(String),
     
-    #[error("Multiple windows found ({0}). Use --all to modify all matching windows")]
-    MultipleWindows(usize),
+    /// `hint` 说明当前命令如何处理多个匹配，例如 `--all` 只适用于修改窗口的命令
+    #[error("Multiple windows found ({count}). {hint}")]
+    MultipleWindows { count: usize, hint: &'static str },
     
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
@@ -53,6 +61,12 @@ impl From<serde_yaml::Error> for AppError {
     }
 }
 
+impl From<image::ImageError> for AppError {
+    fn from(err: image::ImageError) -> Self {
+        AppError::PlatformError(format!("Image error: {}", err))
+    }
+}
+
 impl From<csv::Error> for AppError {
     fn from(err: csv::Error) -> Self {
         AppError::Parse(format!("CSV error: {}", err))
@@ -60,10 +74,16 @@ impl From<csv::Error> for AppError {
 }
 
 // 便捷构造函数
+#[allow(dead_code)]
 impl AppError {
     pub fn window_operation(msg: impl Into<String>) -> Self {
         AppError::WindowOperation(msg.into())
     }
+
+    /// 附带 Win32 错误码（GetLastError）和系统描述（FormatMessage）的窗口操作错误
+    pub fn win32(msg: impl Into<String>, code: u32, description: impl std::fmt::Display) -> Self {
+        AppError::WindowOperation(format!("{} (Win32 error {}: {})", msg.into(), code, description))
+    }
     
     pub fn parse(msg: impl Into<String>) -> Self {
         AppError::Parse(msg.into())
@@ -81,6 +101,16 @@ impl AppError {
         AppError::FeatureNotSupported(feature.into())
     }
 
+    /// 修改窗口的命令匹配到多个窗口但未指定 `--all`
+    pub fn multiple_windows(count: usize) -> Self {
+        AppError::MultipleWindows { count, hint: "Use --all to modify all matching windows" }
+    }
+
+    /// 要求恰好匹配一个窗口的查询（如 `windows/get --one`）匹配到多个窗口
+    pub fn ambiguous_window(count: usize) -> Self {
+        AppError::MultipleWindows { count, hint: "Refine the filter (e.g. --title-exact or --pid) to match exactly one window" }
+    }
+
     pub fn invalid_window_handle(msg: impl Into<String>) -> Self {
         AppError::InvalidWindowHandle(msg.into())
     }
@@ -88,6 +118,45 @@ impl AppError {
     pub fn permission_denied(operation: impl Into<String>) -> Self {
         AppError::PermissionDenied(format!("{} requires elevated privileges", operation.into()))
     }
+
+    /// 进程退出码：2 没有匹配结果，3 匹配到多个窗口，4 参数无效，5 不支持的特性，6 权限不足，其余为 1
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::NoMatchingWindows | AppError::NoSuchProcess(_) | AppError::NoWindowForProcess(_) => 2,
+            AppError::MultipleWindows { .. } => 3,
+            AppError::InvalidParameter(_) => 4,
+            AppError::FeatureNotSupported(_) => 5,
+            AppError::PermissionDenied(_) => 6,
+            _ => 1,
+        }
+    }
+
+    /// 错误类别的稳定名称，用于 `--error-format json`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "io",
+            AppError::Parse(_) => "parse",
+            AppError::WindowOperation(_) => "window_operation",
+            AppError::NoMatchingWindows => "no_matching_windows",
+            AppError::NoSuchProcess(_) => "no_such_process",
+            AppError::NoWindowForProcess(_) => "no_window_for_process",
+            AppError::MultipleWindows { .. } => "multiple_windows",
+            AppError::InvalidParameter(_) => "invalid_parameter",
+            AppError::FeatureNotSupported(_) => "feature_not_supported",
+            AppError::PlatformError(_) => "platform",
+            AppError::NoWindowsModified => "no_windows_modified",
+            AppError::InvalidWindowHandle(_) => "invalid_window_handle",
+            AppError::PlatformNotSupported => "platform_not_supported",
+            AppError::PermissionDenied(_) => "permission_denied",
+        }
+    }
+
+    /// 目标窗口所属进程以更高权限（管理员）运行
+    pub fn elevation_required(pid: u32) -> Self {
+        AppError::PermissionDenied(format!(
+            "the window belongs to an elevated process (PID: {}); run pscan as administrator to modify it", pid
+        ))
+    }
 }
 
 // 结果类型别名
@@ -1,11 +1,14 @@
 // src/features/always_on_top.rs
 use clap::{Arg, Command};
-use crate::cli::SubCommand;
+use crate::cli::{SubCommand, json_or_format};
 use super::feature_trait::Feature;
-use crate::platform::find_windows;
+use crate::platform::{find_windows, get_all_windows_with_size};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet, EX_STYLE_TOPMOST};
 use crate::error::{AppError, AppResult};
-use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting};
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, apply_window_sorting, DEFAULT_SORT_POSITION};
 use crate::utils::parse_indices;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus, display_windows};
+use crate::types::{RectMode, WindowInfo};
 
 /// 窗口置顶特性
 pub struct AlwaysOnTopFeature;
@@ -24,7 +27,8 @@ impl AlwaysOnTopFeature {
                     .short('p')
                     .long("pid")
                     .value_name("PID")
-                    .help("Filter by process ID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
             )
             .arg(
                 Arg::new("name")
@@ -40,6 +44,7 @@ impl AlwaysOnTopFeature {
                     .value_name("TITLE")
                     .help("Filter by window title (contains)")
             )
+            .args(WindowFilter::exact_match_args())
             .arg(
                 Arg::new("all")
                     .short('a')
@@ -68,37 +73,52 @@ impl AlwaysOnTopFeature {
                     .help("Turn off always on top")
                     .conflicts_with("toggle")
             )
+            .arg(
+                Arg::new("force")
+                    .long("force")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Apply even if the window is already in the requested state")
+                    .conflicts_with("toggle")
+            )
+            .arg(
+                Arg::new("list")
+                    .long("list")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with_all(["toggle", "off", "force", "all", "profile"])
+                    .help("List the matching windows that are currently always on top without changing any window")
+            )
             .arg(
                 Arg::new("sort_position")
                     .long("sort-position")
                     .value_name("X_ORDER|Y_ORDER")
                     .num_args(1)
                     .allow_hyphen_values(true)
-                    .default_value("0|0")
-                    .help("Sort by position: X_ORDER|Y_ORDER, e.g., 1|-1 for X ascending, Y descending")
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
             )
-    }
-    
-    /// 统一的字段提取函数
-    fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-        let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-        let name = matches.get_one::<String>("name").map(|s| s.to_string());
-        let title = matches.get_one::<String>("title").map(|s| s.to_string());
-        (pid, name, title)
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
     }
     
     /// 处理置顶命令
+    #[allow(clippy::too_many_arguments)]
     fn handle_always_on_top(
         &self,
-        pid_filter: Option<String>,
-        name_filter: Option<String>,
-        title_filter: Option<String>,
+        filter: &WindowFilter,
         all: bool,
         index: Option<String>,
         toggle: bool,
         off: bool,
+        force: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+        list: bool,
     ) -> AppResult<()> {
+        if list {
+            return Self::list_topmost(filter, index, sort_position, format);
+        }
+
         // 确定目标状态
         let target_state = if off {
             Some(false)
@@ -109,27 +129,24 @@ impl AlwaysOnTopFeature {
         };
         
         // 获取进程名称用于过滤
-        let processes = crate::process::get_processes();
-        let process_names: Vec<(u32, String)> = processes
-            .iter()
-            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-            .collect();
+        let process_names = ProcessNameIndex::collect(filter);
 
         // 使用平台抽象层查找匹配的窗口
-        let mut windows = find_windows(&pid_filter, &name_filter, &title_filter, &process_names);
+        let mut windows = find_windows(filter, &process_names)?;
         
         // 验证窗口数量
         if windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+            return Err(filter.no_windows_error(&process_names));
         }
 
         // 应用排序
         apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
 
         // 解析索引
         let indices = parse_indices(&index.unwrap_or_default(), windows.len());
 
-        let mut count = 0;
+        let mut report = OperationReport::new("set_always_on_top", format, options);
         for (i, window) in windows.iter().enumerate() {
             // 检查索引过滤
             if !indices.is_empty() && !indices.contains(&(i + 1)) {
@@ -141,17 +158,25 @@ impl AlwaysOnTopFeature {
                 break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
             }
 
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
             let result = match target_state {
                 Some(state) => {
-                    // 直接设置状态
-                    window.set_always_on_top(state).map(|_| state)
+                    // 已处于目标状态时跳过，避免多余的 SetWindowPos 引起重绘闪烁
+                    if !force && window.is_always_on_top().is_ok_and(|current| current == state) {
+                        Ok((state, false))
+                    } else {
+                        window.set_always_on_top(state).map(|_| (state, true))
+                    }
                 }
                 None => {
                     // 切换模式：获取当前状态并取反
                     match window.is_always_on_top() {
                         Ok(current_state) => {
                             let new_state = !current_state;
-                            window.set_always_on_top(new_state).map(|_| new_state)
+                            window.set_always_on_top(new_state).map(|_| (new_state, true))
                         }
                         Err(e) => Err(e),
                     }
@@ -159,12 +184,18 @@ impl AlwaysOnTopFeature {
             };
 
             match result {
-                Ok(new_state) => {
+                Ok((new_state, false)) => {
+                    let state_str = if new_state { "always on top" } else { "normal" };
+                    report.record(window.pid, &window.title, OperationStatus::Unchanged, format!(
+                        "unchanged: {} (PID: {}) - {}", window.title, window.pid, state_str
+                    ))?;
+                }
+                Ok((new_state, true)) => {
                     let state_str = if new_state { "always on top" } else { "normal" };
                     let action_str = if target_state.is_some() { "set" } else { "toggled" };
-                    println!("{}: {} (PID: {}) - {}", 
-                             action_str, window.title, window.pid, state_str);
-                    count += 1;
+                    report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                        "{}: {} (PID: {}) - {}", action_str, window.title, window.pid, state_str
+                    ))?;
                 }
                 Err(e) => {
                     let operation_str = match target_state {
@@ -172,18 +203,47 @@ impl AlwaysOnTopFeature {
                         Some(false) => "unset always on top", 
                         None => "toggle always on top",
                     };
-                    eprintln!("Failed to {} window {} (PID: {}): {}", 
-                             operation_str, window.title, window.pid, e);
+                    report.fail(window.pid, &window.title, &e, format!(
+                        "Failed to {} window {} (PID: {}): {}", operation_str, window.title, window.pid, e
+                    ));
                 }
             }
         }
 
-        if count == 0 {
-            return Err(AppError::NoWindowsModified);
+        let count = report.modified();
+        let unchanged = report.count(OperationStatus::Unchanged);
+        if unchanged > 0 {
+            report.finish(format!("Successfully modified {} window(s), {} unchanged", count, unchanged))
+        } else {
+            report.finish(format!("Successfully modified {} window(s)", count))
+        }
+    }
+
+    /// 列出当前置顶（`WS_EX_TOPMOST`）的匹配窗口，不修改任何窗口
+    fn list_topmost(filter: &WindowFilter, index: Option<String>, sort_position: PositionSort, format: OutputFormat) -> AppResult<()> {
+        let process_names = ProcessNameIndex::collect(filter);
+
+        let topmost = WindowFilter { ex_style: filter.ex_style | EX_STYLE_TOPMOST, ..filter.clone() };
+        let mut windows: Vec<WindowInfo> = get_all_windows_with_size(RectMode::Restored, true)
+            .into_iter()
+            .filter(|window| topmost.matches(window, &process_names))
+            .collect();
+        apply_window_sorting(&mut windows, &SortOrder::None, &sort_position);
+
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+        if !indices.is_empty() {
+            windows = windows
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| indices.contains(&(i + 1)))
+                .map(|(_, window)| window)
+                .collect();
         }
 
-        println!("Successfully modified {} window(s)", count);
-        Ok(())
+        if windows.is_empty() {
+            return Err(AppError::NoMatchingWindows);
+        }
+        display_windows(&windows, &process_names, format, false)
     }
 }
 
@@ -202,34 +262,29 @@ impl Feature for AlwaysOnTopFeature {
     
     fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
         if let Some(matches) = matches.subcommand_matches("windows/always-on-top") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
             let toggle = matches.get_flag("toggle");
             let off = matches.get_flag("off");
+            let force = matches.get_flag("force");
             
-            let sort_position = match matches.get_one::<String>("sort_position").map(|s| s.as_str()) {
-                Some(s) => {
-                    match s.parse() {
-                        Ok(pos) => pos,
-                        Err(_) => {
-                            eprintln!("Warning: Invalid position sort format '{}', using default", s);
-                            PositionSort::default()
-                        }
-                    }
-                }
-                None => PositionSort::default(),
-            };
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
             
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
+            let list = matches.get_flag("list");
             Some(SubCommand::WindowsAlwaysOnTop { 
-                pid, 
-                name, 
-                title, 
+                filter,
                 all,
                 index,
                 toggle,
                 off,
+                force,
                 sort_position,
+                options,
+                format,
+                list,
             })
         } else {
             None
@@ -237,16 +292,18 @@ impl Feature for AlwaysOnTopFeature {
     }
     
     fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
-        if let SubCommand::WindowsAlwaysOnTop { pid, name, title, all, index, toggle, off, sort_position } = subcommand {
+        if let SubCommand::WindowsAlwaysOnTop { filter, all, index, toggle, off, force, sort_position, options, format, list } = subcommand {
             self.handle_always_on_top(
-                pid.clone(),
-                name.clone(), 
-                title.clone(),
+                filter,
                 *all,
                 index.clone(),
                 *toggle,
                 *off,
+                *force,
                 *sort_position,
+                *options,
+                format.clone(),
+                *list,
             )
         } else {
             Ok(()) // 不是本特性处理的命令，忽略
@@ -0,0 +1,488 @@
+// src/features/apply.rs
+use clap::{Arg, Command};
+use serde::Deserialize;
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::{find_windows, WindowHandle};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::{AppError, AppResult};
+#[cfg(feature = "undo")]
+use crate::undo::UndoRecorder;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::utils::parse_indices;
+
+/// 声明式窗口布局特性：按 JSON 规则把位置、尺寸、透明度和置顶状态应用到匹配的窗口
+pub struct ApplyFeature;
+
+/// 单条窗口规则
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WindowDirective {
+    #[serde(rename = "match")]
+    target: MatchSpec,
+    x: Option<i32>,
+    y: Option<i32>,
+    width: Option<i32>,
+    height: Option<i32>,
+    /// 不透明度百分比（0-100）
+    opacity: Option<u8>,
+    topmost: Option<bool>,
+    /// 应用到所有匹配的窗口，默认只应用到第一个
+    #[serde(default)]
+    all: bool,
+}
+
+/// 窗口匹配条件，各条件之间为 AND 关系
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatchSpec {
+    /// 标题包含（不区分大小写）
+    title: Option<String>,
+    pid: Option<u32>,
+    /// 窗口类名（完全相等，不区分大小写）
+    class: Option<String>,
+}
+
+impl WindowDirective {
+    /// 检查规则本身是否有效，`number` 为规则序号（从 1 开始），用于错误信息
+    fn validate(&self, number: usize) -> AppResult<()> {
+        let target = &self.target;
+        if target.title.is_none() && target.pid.is_none() && target.class.is_none() {
+            return Err(AppError::invalid_parameter(format!(
+                "Directive #{}: \"match\" needs at least one of title, pid or class", number
+            )));
+        }
+        self.validate_properties(&format!("Directive #{}", number))
+    }
+
+    /// 检查要应用的属性，`context` 为错误信息前缀
+    fn validate_properties(&self, context: &str) -> AppResult<()> {
+        if self.x.is_none() && self.y.is_none() && self.width.is_none() && self.height.is_none()
+            && self.opacity.is_none() && self.topmost.is_none()
+        {
+            return Err(AppError::invalid_parameter(format!(
+                "{}: nothing to apply (set x, y, width, height, opacity or topmost)", context
+            )));
+        }
+        if self.width.is_some_and(|w| w <= 0) || self.height.is_some_and(|h| h <= 0) {
+            return Err(AppError::invalid_parameter(format!(
+                "{}: width and height must be positive values", context
+            )));
+        }
+        if self.opacity.is_some_and(|o| o > 100) {
+            return Err(AppError::invalid_parameter(format!(
+                "{}: opacity must be between 0 and 100", context
+            )));
+        }
+        Ok(())
+    }
+
+    /// 将规则应用到一个窗口，返回已执行操作的描述
+    fn apply_to(&self, window: &WindowHandle) -> AppResult<Vec<String>> {
+        let mut applied = Vec::new();
+
+        // 只给出部分几何参数时，缺少的部分取窗口当前值
+        let needs_rect = (self.width.is_some() != self.height.is_some()) || (self.x.is_some() != self.y.is_some());
+        let current = if needs_rect { Some(window.get_rect()?) } else { None };
+
+        let size = (self.width.is_some() || self.height.is_some()).then(|| (
+            self.width.or(current.as_ref().map(|r| r.width)).unwrap_or_default(),
+            self.height.or(current.as_ref().map(|r| r.height)).unwrap_or_default(),
+        ));
+        let position = (self.x.is_some() || self.y.is_some()).then(|| (
+            self.x.or(current.as_ref().map(|r| r.x)).unwrap_or_default(),
+            self.y.or(current.as_ref().map(|r| r.y)).unwrap_or_default(),
+        ));
+
+        // 同时给出位置和尺寸时一次设置，避免窗口移动两次
+        match (size, position) {
+            (Some((width, height)), Some((x, y))) => window.set_bounds(x, y, width, height)?,
+            (Some((width, height)), None) => window.resize(width, height, true, false)?,
+            (None, Some((x, y))) => window.set_position(x, y)?,
+            (None, None) => {}
+        }
+        if let Some((width, height)) = size {
+            applied.push(format!("size {}x{}", width, height));
+        }
+        if let Some((x, y)) = position {
+            applied.push(format!("position {},{}", x, y));
+        }
+
+        if let Some(opacity) = self.opacity {
+            window.set_transparency(opacity)?;
+            applied.push(format!("opacity {}%", opacity));
+        }
+
+        if let Some(topmost) = self.topmost {
+            window.set_always_on_top(topmost)?;
+            applied.push(if topmost { "always on top" } else { "normal z-order" }.to_string());
+        }
+
+        Ok(applied)
+    }
+}
+
+/// 解析规则文件内容：JSON 数组，每个元素是一条规则
+fn parse_spec(content: &str) -> AppResult<Vec<WindowDirective>> {
+    let directives: Vec<WindowDirective> = serde_json::from_str(content)?;
+    for (i, directive) in directives.iter().enumerate() {
+        directive.validate(i + 1)?;
+    }
+    Ok(directives)
+}
+
+impl ApplyFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/apply")
+            .about("Apply a JSON spec of window directives (position, size, opacity, topmost)")
+            .arg(
+                Arg::new("file")
+                    .long("file")
+                    .value_name("FILE")
+                    .required(true)
+                    .help("JSON file: [{\"match\": {\"title\"|\"pid\"|\"class\": ...}, \"x\", \"y\", \"width\", \"height\", \"opacity\", \"topmost\", \"all\"}]")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 构建 windows/set 子命令：用命令行参数一次设置多个属性
+    fn build_set_command(&self) -> Command {
+        Command::new("windows/set")
+            .about("Set several window properties at once (position, size, opacity, topmost)")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Apply to all matching windows")
+            )
+            .arg(
+                Arg::new("index")
+                    .long("index")
+                    .value_name("INDICES")
+                    .num_args(1)
+                    .default_value("")
+                    .help("Window indices to set (e.g., \"1,2,3\"), empty means all")
+            )
+            .arg(
+                Arg::new("x")
+                    .long("x")
+                    .value_name("X")
+                    .allow_hyphen_values(true)
+                    .value_parser(clap::value_parser!(i32))
+                    .help("Target X position (Y keeps its current value if --y is omitted)")
+            )
+            .arg(
+                Arg::new("y")
+                    .long("y")
+                    .value_name("Y")
+                    .allow_hyphen_values(true)
+                    .value_parser(clap::value_parser!(i32))
+                    .help("Target Y position (X keeps its current value if --x is omitted)")
+            )
+            .arg(
+                Arg::new("target_width")
+                    .long("width")
+                    .value_name("WIDTH")
+                    .value_parser(clap::value_parser!(i32))
+                    .help("Target width (height keeps its current value if --height is omitted)")
+            )
+            .arg(
+                Arg::new("target_height")
+                    .long("height")
+                    .value_name("HEIGHT")
+                    .value_parser(clap::value_parser!(i32))
+                    .help("Target height (width keeps its current value if --width is omitted)")
+            )
+            .arg(
+                Arg::new("opacity")
+                    .long("opacity")
+                    .value_name("PERCENT")
+                    .value_parser(clap::value_parser!(u8))
+                    .help("Opacity percentage (0-100)")
+            )
+            .arg(
+                Arg::new("topmost")
+                    .long("topmost")
+                    .value_name("on|off")
+                    .value_parser(clap::builder::BoolishValueParser::new())
+                    .help("Set (on) or clear (off) always-on-top")
+            )
+            .arg(
+                Arg::new("sort_position")
+                    .long("sort-position")
+                    .value_name("X_ORDER|Y_ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 处理 windows/set 命令：`properties` 的匹配条件不使用，窗口由 `filter` 选出
+    #[allow(clippy::too_many_arguments)]
+    fn handle_set(
+        &self,
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<String>,
+        properties: &WindowDirective,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    ) -> AppResult<()> {
+        properties.validate_properties("windows/set")?;
+
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        let mut windows = find_windows(filter, &process_names)?;
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+
+        let mut report = OperationReport::new("set", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("set");
+        for (i, window) in windows.iter().enumerate() {
+            // 检查索引过滤
+            if !indices.is_empty() && !indices.contains(&(i + 1)) {
+                continue;
+            }
+
+            // 检查是否应用所有窗口
+            if !all && indices.is_empty() && i > 0 {
+                break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
+            }
+
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            #[cfg(feature = "undo")]
+            undo.capture(window);
+            match properties.apply_to(window) {
+                Ok(applied) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "Set: {} (PID: {}) - {}", window.title, window.pid, applied.join(", ")
+                ))?,
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to set properties of window {} (PID: {}): {}", window.title, window.pid, e
+                )),
+            }
+        }
+
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully set properties of {} window(s)", count))
+    }
+
+    /// 处理 windows/apply 命令
+    fn handle_apply(&self, file: &str, options: OperationOptions, format: OutputFormat) -> AppResult<()> {
+        let content = std::fs::read_to_string(file)?;
+        let directives = parse_spec(&content)?;
+
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(&WindowFilter::default());
+
+        let mut report = OperationReport::new("apply", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("apply");
+        for (i, directive) in directives.iter().enumerate() {
+            let filter = WindowFilter {
+                pid: directive.target.pid.map(PidSet::from),
+                title: directive.target.title.clone(),
+                ..Default::default()
+            };
+
+            let windows: Vec<WindowHandle> = find_windows(&filter, &process_names)?
+                .into_iter()
+                .filter(|window| match &directive.target.class {
+                    Some(class) => window.class_name().is_ok_and(|name| name.eq_ignore_ascii_case(class)),
+                    None => true,
+                })
+                .take(if directive.all { usize::MAX } else { 1 })
+                .collect();
+
+            if windows.is_empty() {
+                eprintln!("Directive #{}: no matching windows", i + 1);
+                continue;
+            }
+
+            for window in &windows {
+                if report.skip_hung(window) {
+                    continue;
+                }
+                report.throttle();
+                #[cfg(feature = "undo")]
+                undo.capture(window);
+                match directive.apply_to(window) {
+                    Ok(applied) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                        "Applied #{}: {} (PID: {}) - {}", i + 1, window.title, window.pid, applied.join(", ")
+                    ))?,
+                    Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                        "Failed to apply directive #{} to window {} (PID: {}): {}", i + 1, window.title, window.pid, e
+                    )),
+                }
+            }
+        }
+
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully applied {} directive(s) to {} window(s)", directives.len(), count))
+    }
+}
+
+impl Feature for ApplyFeature {
+    fn name(&self) -> &'static str {
+        "apply"
+    }
+
+    fn description(&self) -> &'static str {
+        "Declarative window layout from a JSON spec"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command
+            .subcommand(self.build_command())
+            .subcommand(self.build_set_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        if let Some(matches) = matches.subcommand_matches("windows/apply") {
+            let file = matches.get_one::<String>("file").cloned().unwrap_or_default();
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsApply { file, options, format })
+        } else if let Some(matches) = matches.subcommand_matches("windows/set") {
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let index = matches.get_one::<String>("index").map(|s| s.to_string());
+
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+            Some(SubCommand::WindowsSet {
+                filter,
+                all,
+                index,
+                x: matches.get_one::<i32>("x").copied(),
+                y: matches.get_one::<i32>("y").copied(),
+                width: matches.get_one::<i32>("target_width").copied(),
+                height: matches.get_one::<i32>("target_height").copied(),
+                opacity: matches.get_one::<u8>("opacity").copied(),
+                topmost: matches.get_one::<bool>("topmost").copied(),
+                sort_position,
+                options: OperationOptions::from_matches(matches),
+                format: json_or_format(matches),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        match subcommand {
+            SubCommand::WindowsApply { file, options, format } => self.handle_apply(file, *options, format.clone()),
+            SubCommand::WindowsSet { filter, all, index, x, y, width, height, opacity, topmost, sort_position, options, format } => {
+                let properties = WindowDirective {
+                    x: *x,
+                    y: *y,
+                    width: *width,
+                    height: *height,
+                    opacity: *opacity,
+                    topmost: *topmost,
+                    ..Default::default()
+                };
+                self.handle_set(filter, *all, index.clone(), &properties, *sort_position, *options, format.clone())
+            }
+            _ => Ok(()), // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = r#"[
+            {"match": {"title": "Notepad"}, "x": 0, "y": 0, "width": 800, "height": 600},
+            {"match": {"pid": 1234, "class": "Chrome_WidgetWin_1"}, "opacity": 80, "topmost": true, "all": true}
+        ]"#;
+        let directives = parse_spec(spec).unwrap();
+        assert_eq!(directives.len(), 2);
+        assert_eq!(directives[0].target.title.as_deref(), Some("Notepad"));
+        assert_eq!(directives[0].width, Some(800));
+        assert!(!directives[0].all);
+        assert_eq!(directives[1].target.pid, Some(1234));
+        assert_eq!(directives[1].opacity, Some(80));
+        assert!(directives[1].all);
+    }
+
+    #[test]
+    fn test_parse_spec_rejects_invalid_directives() {
+        // 缺少匹配条件
+        assert!(parse_spec(r#"[{"match": {}, "x": 0, "y": 0}]"#).is_err());
+        // 没有要应用的属性
+        assert!(parse_spec(r#"[{"match": {"title": "a"}}]"#).is_err());
+        // 透明度越界
+        assert!(parse_spec(r#"[{"match": {"title": "a"}, "opacity": 150}]"#).is_err());
+        // 未知字段（防止拼写错误被静默忽略）
+        assert!(parse_spec(r#"[{"match": {"title": "a"}, "opactiy": 50}]"#).is_err());
+    }
+
+    #[test]
+    fn test_set_properties_validation() {
+        // windows/set 不需要匹配条件，只检查要设置的属性
+        let properties = |width, opacity| WindowDirective { width, opacity, ..Default::default() };
+        assert!(properties(None, None).validate_properties("windows/set").is_err());
+        assert!(properties(Some(0), None).validate_properties("windows/set").is_err());
+        assert!(properties(None, Some(101)).validate_properties("windows/set").is_err());
+        assert!(properties(Some(800), Some(80)).validate_properties("windows/set").is_ok());
+    }
+}
@@ -0,0 +1,217 @@
+// src/features/capture.rs
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::find_windows;
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::AppResult;
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::utils::{parse_indices, numbered_path};
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 窗口截图特性
+pub struct CaptureFeature;
+
+impl CaptureFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/capture")
+            .about("Capture the client area of matching windows to PNG files")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Capture all matching windows")
+            )
+            .arg(
+                Arg::new("index")
+                    .long("index")
+                    .value_name("INDICES")
+                    .num_args(1)
+                    .default_value("")
+                    .help("Window indices to capture (e.g., \"1,2,3\"), empty means all")
+            )
+            .arg(
+                Arg::new("out")
+                    .short('o')
+                    .long("out")
+                    .value_name("FILE")
+                    .required(true)
+                    .help("Output PNG file; with several windows, files are numbered (shot.png -> shot_1.png, shot_2.png)")
+            )
+            .arg(
+                Arg::new("sort_position")
+                    .long("sort-position")
+                    .value_name("X_ORDER|Y_ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 处理截图命令
+    #[allow(clippy::too_many_arguments)]
+    fn handle_capture(
+        &self,
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<String>,
+        out: &str,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    ) -> AppResult<()> {
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        // 使用平台抽象层查找匹配的窗口
+        let mut windows = find_windows(filter, &process_names)?;
+
+        // 验证窗口数量
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        // 应用排序
+        apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
+
+        // 解析索引
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+
+        // 确定目标窗口：指定索引时只截取这些窗口；否则没有 --all 时只截取第一个窗口
+        let targets: Vec<usize> = (0..windows.len())
+            .filter(|i| indices.is_empty() || indices.contains(&(i + 1)))
+            .take(if all || !indices.is_empty() { usize::MAX } else { 1 })
+            .collect();
+        let numbered = targets.len() > 1;
+
+        let mut report = OperationReport::new("capture", format, options);
+        for i in targets {
+            let window = &windows[i];
+            // 多个窗口时按窗口序号编号，与 --index 对应
+            let path = if numbered { numbered_path(out, i + 1) } else { out.into() };
+
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            let result = window.capture().and_then(|image| {
+                image::save_buffer_with_format(
+                    &path,
+                    &image.rgba,
+                    image.width,
+                    image.height,
+                    image::ColorType::Rgba8,
+                    image::ImageFormat::Png,
+                )?;
+                Ok(image)
+            });
+
+            match result {
+                Ok(image) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "Captured: {} (PID: {}) {}x{} -> {}", window.title, window.pid, image.width, image.height, path.display()
+                ))?,
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to capture window {} (PID: {}): {}", window.title, window.pid, e
+                )),
+            }
+        }
+
+        let count = report.modified();
+        report.finish(format!("Successfully captured {} window(s)", count))
+    }
+}
+
+impl Feature for CaptureFeature {
+    fn name(&self) -> &'static str {
+        "capture"
+    }
+
+    fn description(&self) -> &'static str {
+        "Window screenshot capture"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        if let Some(matches) = matches.subcommand_matches("windows/capture") {
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let index = matches.get_one::<String>("index").map(|s| s.to_string());
+            let out = matches.get_one::<String>("out").cloned().unwrap_or_default();
+
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsCapture {
+                filter,
+                all,
+                index,
+                out,
+                sort_position,
+                options,
+                format,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsCapture { filter, all, index, out, sort_position, options, format } = subcommand {
+            self.handle_capture(
+                filter,
+                *all,
+                index.clone(),
+                out,
+                *sort_position,
+                *options,
+                format.clone(),
+            )
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
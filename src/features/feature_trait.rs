@@ -22,4 +22,9 @@ pub trait Feature: Send + Sync {
     
     /// 检查是否支持当前平台
     fn is_supported(&self) -> bool;
+
+    /// 运行时检查当前环境是否可用（如是否存在可枚举窗口的显示服务），默认可用
+    fn check_runtime(&self) -> AppResult<()> {
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -5,6 +5,19 @@ mod position_set;
 mod window_operations;
 mod windows_get;
 mod resize;  // 新增
+mod windows_diff;
+mod capture;
+mod reset_all;
+mod pin;
+mod apply;
+mod move_to_monitor;
+mod set_bounds;
+mod icon;
+mod state;
+#[cfg(feature = "undo")]
+mod undo;
+#[cfg(feature = "tui")]
+mod tui;
 
 pub use feature_trait::Feature;
 pub use always_on_top::AlwaysOnTopFeature;
@@ -13,9 +26,23 @@ pub use position_set::PositionSetFeature;
 pub use window_operations::WindowOperationsFeature;
 pub use windows_get::WindowsGetFeature;
 pub use resize::ResizeFeature;  // 新增
+pub use windows_diff::WindowsDiffFeature;
+pub use capture::CaptureFeature;
+pub use reset_all::ResetAllFeature;
+pub use pin::PinFeature;
+pub use apply::ApplyFeature;
+pub use move_to_monitor::MoveToMonitorFeature;
+pub use set_bounds::SetBoundsFeature;
+pub use icon::IconFeature;
+pub use state::StateFeature;
+#[cfg(feature = "undo")]
+pub use undo::UndoFeature;
+#[cfg(feature = "tui")]
+pub use tui::TuiFeature;
 
 use std::collections::HashMap;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
+use crate::output::{json_output, outln, to_json};
 
 /// 特性管理器
 pub struct FeatureManager {
@@ -65,26 +92,97 @@ impl FeatureManager {
     /// 执行特性命令
     pub fn execute(&self, subcommand: &crate::cli::SubCommand) -> AppResult<()> {
         for feature in self.features.values() {
-            if let Err(e) = feature.execute(subcommand) {
-                return Err(e);
-            }
+            feature.execute(subcommand)?;
         }
         Ok(())
     }
 }
 
+/// 所有编译进来的内置特性（不论当前平台是否支持）
+#[allow(clippy::vec_init_then_push)]
+fn builtin_features() -> Vec<Box<dyn Feature>> {
+    let mut features: Vec<Box<dyn Feature>> = Vec::new();
+
+    // 窗口获取特性（应该总是支持）
+    #[cfg(feature = "windows_get")]
+    features.push(Box::new(WindowsGetFeature::new()));
+
+    // 窗口操作特性
+    #[cfg(feature = "window_operations")]
+    features.push(Box::new(WindowOperationsFeature::new()));
+
+    // 窗口置顶特性
+    #[cfg(feature = "always_on_top")]
+    features.push(Box::new(AlwaysOnTopFeature::new()));
+
+    // 窗口透明度特性
+    #[cfg(feature = "transparency")]
+    features.push(Box::new(TransparencyFeature::new()));
+
+    // 窗口位置设置特性
+    #[cfg(feature = "position_set")]
+    features.push(Box::new(PositionSetFeature::new()));
+
+    // 窗口调整大小特性
+    #[cfg(feature = "resize")]
+    features.push(Box::new(ResizeFeature::new()));
+
+    // 窗口快照对比特性
+    #[cfg(feature = "windows_diff")]
+    features.push(Box::new(WindowsDiffFeature::new()));
+
+    // 窗口截图特性
+    #[cfg(feature = "capture")]
+    features.push(Box::new(CaptureFeature::new()));
+
+    // 窗口状态恢复特性
+    #[cfg(feature = "reset_all")]
+    features.push(Box::new(ResetAllFeature::new()));
+
+    // 窗口位置锁定特性
+    #[cfg(feature = "pin")]
+    features.push(Box::new(PinFeature::new()));
+
+    // 声明式窗口布局特性
+    #[cfg(feature = "apply")]
+    features.push(Box::new(ApplyFeature::new()));
+
+    // 窗口移动到指定显示器特性
+    #[cfg(feature = "move_to_monitor")]
+    features.push(Box::new(MoveToMonitorFeature::new()));
+
+    // 窗口位置和尺寸一次性设置特性
+    #[cfg(feature = "set_bounds")]
+    features.push(Box::new(SetBoundsFeature::new()));
+
+    // 窗口图标提取特性
+    #[cfg(feature = "icon")]
+    features.push(Box::new(IconFeature::new()));
+
+    // 窗口状态查询特性
+    #[cfg(feature = "state")]
+    features.push(Box::new(StateFeature::new()));
+
+    // 撤销最近一次布局修改特性
+    #[cfg(feature = "undo")]
+    features.push(Box::new(UndoFeature::new()));
+
+    // 交互式窗口浏览特性
+    #[cfg(feature = "tui")]
+    features.push(Box::new(TuiFeature::new()));
+
+    features
+}
+
 /// 创建默认特性管理器（包含所有内置特性）
 pub fn create_default_manager() -> FeatureManager {
     let mut manager = FeatureManager::new();
-    
-    // 统一的特性注册函数
-    fn register_feature_if_supported<F: Feature + 'static>(
-        manager: &mut FeatureManager, 
-        feature: F,
-        feature_name: &str
-    ) {
+
+    // 只注册当前平台支持的特性
+    for feature in builtin_features() {
+        let feature_name = feature.name();
         if feature.is_supported() {
-            manager.register_feature(Box::new(feature));
+            manager.register_feature(feature);
             if std::env::var("PSCAN_DEBUG_FEATURES").is_ok() {
                 println!("Debug: {} feature enabled", feature_name);
             }
@@ -92,34 +190,63 @@ pub fn create_default_manager() -> FeatureManager {
             eprintln!("Warning: {} feature is not supported on this platform", feature_name);
         }
     }
-    
-    // 条件注册窗口获取特性（应该总是支持）
-    #[cfg(feature = "windows_get")]
-    register_feature_if_supported(&mut manager, WindowsGetFeature::new(), "windows_get");
 
-    // 条件注册窗口操作特性
-    #[cfg(feature = "window_operations")]
-    register_feature_if_supported(&mut manager, WindowOperationsFeature::new(), "window_operations");
-
-    // 条件注册窗口置顶特性
-    #[cfg(feature = "always_on_top")]
-    register_feature_if_supported(&mut manager, AlwaysOnTopFeature::new(), "always_on_top");
-    
-    // 条件注册窗口透明度特性
-    #[cfg(feature = "transparency")]
-    register_feature_if_supported(&mut manager, TransparencyFeature::new(), "transparency");
-    
-    // 条件注册窗口位置设置特性
-    #[cfg(feature = "position_set")]
-    register_feature_if_supported(&mut manager, PositionSetFeature::new(), "position_set");
-    
-    // 条件注册窗口调整大小特性
-    #[cfg(feature = "resize")]
-    register_feature_if_supported(&mut manager, ResizeFeature::new(), "resize");
-    
     manager
 }
 
+/// 注册所有内置特性（不检查平台支持），用于在任意平台上测试完整的命令行
+#[cfg(test)]
+pub fn builtin_manager() -> FeatureManager {
+    FeatureManager { features: builtin_features().into_iter().map(|feature| (feature.name(), feature)).collect() }
+}
+
+/// `features --json` 输出中的一项
+#[derive(serde::Serialize)]
+pub struct FeatureStatus {
+    pub name: &'static str,
+    pub status: &'static str,
+    pub description: &'static str,
+    pub reason: Option<String>,
+}
+
+/// 检查每个内置特性在当前环境下的可用性
+pub fn feature_statuses() -> Vec<FeatureStatus> {
+    let mut report = Vec::new();
+    for feature in builtin_features() {
+        let reason = if !feature.is_supported() {
+            Some("not available on this platform".to_string())
+        } else {
+            match feature.check_runtime() {
+                Ok(()) => None,
+                Err(AppError::FeatureNotSupported(msg)) => Some(msg),
+                Err(e) => Some(e.to_string()),
+            }
+        };
+        let status = if reason.is_some() { "unsupported" } else { "available" };
+        report.push(FeatureStatus { name: feature.name(), status, description: feature.description(), reason });
+    }
+    report
+}
+
+/// 输出 `features` 子命令的报告：每个内置特性在当前环境下的可用性
+pub fn print_feature_report() -> AppResult<()> {
+    let report = feature_statuses();
+
+    if json_output() {
+        outln!("{}", to_json(&report)?);
+        return Ok(());
+    }
+
+    outln!("{:<20} {:<12} Description", "Feature", "Status");
+    for entry in &report {
+        outln!("{:<20} {:<12} {}", entry.name, entry.status, entry.description);
+        if let Some(reason) = &entry.reason {
+            outln!("{:<20} {:<12} reason: {}", "", "", reason);
+        }
+    }
+    Ok(())
+}
+
 /// 获取启用的特性列表（用于调试和信息显示）
 pub fn get_enabled_features() -> Vec<&'static str> {
     let mut features = Vec::new();
@@ -153,6 +280,108 @@ pub fn get_enabled_features() -> Vec<&'static str> {
     {
         features.push("resize");
     }
+
+    #[cfg(feature = "windows_diff")]
+    {
+        features.push("windows_diff");
+    }
+
+    #[cfg(feature = "capture")]
+    {
+        features.push("capture");
+    }
+
+    #[cfg(feature = "reset_all")]
+    {
+        features.push("reset_all");
+    }
+
+    #[cfg(feature = "pin")]
+    {
+        features.push("pin");
+    }
+
+    #[cfg(feature = "apply")]
+    {
+        features.push("apply");
+    }
+
+    #[cfg(feature = "move_to_monitor")]
+    {
+        features.push("move_to_monitor");
+    }
+
+    #[cfg(feature = "set_bounds")]
+    {
+        features.push("set_bounds");
+    }
+
+    #[cfg(feature = "icon")]
+    {
+        features.push("icon");
+    }
+
+    #[cfg(feature = "state")]
+    {
+        features.push("state");
+    }
+
+    #[cfg(feature = "undo")]
+    {
+        features.push("undo");
+    }
+
+    #[cfg(feature = "uia")]
+    {
+        features.push("uia");
+    }
+
+    #[cfg(feature = "tui")]
+    {
+        features.push("tui");
+    }
     
     features
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting::{DEFAULT_SORT_POSITION, LAYOUT_SORT_POSITION};
+
+    /// 读取子命令 `--sort-position` 参数的默认值
+    fn sort_position_default(feature: &dyn Feature, subcommand: &str) -> String {
+        let command = feature.build_cli(clap::Command::new("pscan"));
+        let subcommand = command.find_subcommand(subcommand).unwrap();
+        let arg = subcommand
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some("sort-position"))
+            .unwrap();
+        arg.get_default_values()[0].to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_sort_position_defaults() {
+        // 只有 position/set 默认排序（决定窗口分配到哪个布局位置），其余命令保持枚举顺序
+        assert_eq!(sort_position_default(&PositionSetFeature::new(), "windows/position/set"), LAYOUT_SORT_POSITION);
+
+        let unsorted: [(&dyn Feature, &str); 13] = [
+            (&WindowsGetFeature::new(), "windows/get"),
+            (&WindowOperationsFeature::new(), "windows/minimize"),
+            (&AlwaysOnTopFeature::new(), "windows/always-on-top"),
+            (&TransparencyFeature::new(), "windows/transparency"),
+            (&ResizeFeature::new(), "windows/resize"),
+            (&CaptureFeature::new(), "windows/capture"),
+            (&PinFeature::new(), "windows/pin"),
+            (&MoveToMonitorFeature::new(), "windows/move-to-monitor"),
+            (&SetBoundsFeature::new(), "windows/set-bounds"),
+            (&IconFeature::new(), "windows/icon"),
+            (&WindowOperationsFeature::new(), "windows/show"),
+            (&ApplyFeature::new(), "windows/set"),
+            (&StateFeature::new(), "windows/state"),
+        ];
+        for (feature, subcommand) in unsorted {
+            assert_eq!(sort_position_default(feature, subcommand), DEFAULT_SORT_POSITION, "{}", subcommand);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+// src/features/move_to_monitor.rs
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::{find_windows, get_monitor_work_areas};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::{AppError, AppResult};
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::utils::{parse_indices, monitor_of, relocate_to_monitor};
+#[cfg(feature = "undo")]
+use crate::undo::UndoRecorder;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 窗口移动到指定显示器特性
+pub struct MoveToMonitorFeature;
+
+impl MoveToMonitorFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/move-to-monitor")
+            .about("Move windows to another monitor, keeping their relative position in the work area")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Move all matching windows")
+            )
+            .arg(
+                Arg::new("index")
+                    .long("index")
+                    .value_name("INDICES")
+                    .num_args(1)
+                    .default_value("")
+                    .help("Window indices to move (e.g., \"1,2,3\"), empty means all")
+            )
+            .arg(
+                Arg::new("monitor")
+                    .long("monitor")
+                    .value_name("N")
+                    .required(true)
+                    .value_parser(clap::value_parser!(u32).range(1..))
+                    .help("Target monitor (1-based, same numbering as windows/get --monitor-index)")
+            )
+            .arg(
+                Arg::new("center")
+                    .long("center")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Center windows in the target monitor's work area instead of keeping their relative position")
+            )
+            .arg(
+                Arg::new("sort_position")
+                    .long("sort-position")
+                    .value_name("X_ORDER|Y_ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 处理移动到显示器命令
+    #[allow(clippy::too_many_arguments)]
+    fn handle_move_to_monitor(
+        &self,
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<String>,
+        monitor: u32,
+        center: bool,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    ) -> AppResult<()> {
+        let monitors = get_monitor_work_areas()?;
+        let target = monitors.get(monitor as usize - 1).ok_or_else(|| AppError::invalid_parameter(format!(
+            "Monitor index {} is out of range (found {} monitor(s))", monitor, monitors.len()
+        )))?;
+
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        // 使用平台抽象层查找匹配的窗口
+        let mut windows = find_windows(filter, &process_names)?;
+
+        // 验证窗口数量
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        // 应用排序
+        apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
+
+        // 解析索引
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+
+        let mut report = OperationReport::new("move_to_monitor", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("move_to_monitor");
+        for i in (0..windows.len())
+            .filter(|i| indices.is_empty() || indices.contains(&(i + 1)))
+            .take(if all || !indices.is_empty() { usize::MAX } else { 1 })
+        {
+            let window = &windows[i];
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            #[cfg(feature = "undo")]
+            undo.capture(window);
+            let result = window.get_rect().and_then(|rect| {
+                // 以窗口当前所在的显示器为参照计算相对位置
+                let source = monitor_of(&rect, &monitors).map_or(target, |n| &monitors[n]);
+                let (x, y) = relocate_to_monitor(&rect, source, target, center);
+                window.set_position(x, y).map(|()| (x, y))
+            });
+
+            match result {
+                Ok((x, y)) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "Moved: {} (PID: {}) to monitor {} at {},{}", window.title, window.pid, monitor, x, y
+                ))?,
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to move window {} (PID: {}) to monitor {}: {}", window.title, window.pid, monitor, e
+                )),
+            }
+        }
+
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully moved {} window(s) to monitor {}", count, monitor))
+    }
+}
+
+impl Feature for MoveToMonitorFeature {
+    fn name(&self) -> &'static str {
+        "move_to_monitor"
+    }
+
+    fn description(&self) -> &'static str {
+        "Move windows between monitors"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        if let Some(matches) = matches.subcommand_matches("windows/move-to-monitor") {
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let index = matches.get_one::<String>("index").map(|s| s.to_string());
+            let monitor = matches.get_one::<u32>("monitor").copied().unwrap_or(1);
+            let center = matches.get_flag("center");
+
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsMoveToMonitor {
+                filter,
+                all,
+                index,
+                monitor,
+                center,
+                sort_position,
+                options,
+                format,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsMoveToMonitor { filter, all, index, monitor, center, sort_position, options, format } = subcommand {
+            self.handle_move_to_monitor(filter, *all, index.clone(), *monitor, *center, *sort_position, *options, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
@@ -0,0 +1,283 @@
+// src/features/pin.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::{find_windows, WindowHandle};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::{AppError, AppResult};
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::utils::{parse_indices, parse_position, parse_duration};
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 窗口位置锁定特性：在一段时间内持续把窗口移回指定位置
+pub struct PinFeature;
+
+impl PinFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/pin")
+            .about("Keep windows at a position for a while, undoing the app's own repositioning")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Pin all matching windows")
+            )
+            .arg(
+                Arg::new("index")
+                    .long("index")
+                    .value_name("INDICES")
+                    .num_args(1)
+                    .default_value("")
+                    .help("Window indices to pin (e.g., \"1,2,3\"), empty means all")
+            )
+            .arg(
+                Arg::new("position")
+                    .long("position")
+                    .value_name("X,Y")
+                    .num_args(1)
+                    .required(true)
+                    .allow_hyphen_values(true)
+                    .help("Position to keep the windows at (e.g., \"100,100\")")
+            )
+            .arg(
+                Arg::new("duration")
+                    .long("duration")
+                    .value_name("DURATION")
+                    .num_args(1)
+                    .default_value("10s")
+                    .help("How long to keep enforcing the position (e.g., 10s, 2m); Ctrl-C stops early")
+            )
+            .arg(
+                Arg::new("interval")
+                    .long("interval")
+                    .value_name("DURATION")
+                    .num_args(1)
+                    .default_value("500ms")
+                    .help("How often to check and re-apply the position (e.g., 500ms, 1s)")
+            )
+            .arg(
+                Arg::new("sort_position")
+                    .long("sort-position")
+                    .value_name("X_ORDER|Y_ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 窗口不在目标位置时移回去，返回是否移动了窗口
+    fn enforce(window: &WindowHandle, x: i32, y: i32) -> AppResult<bool> {
+        if let Ok(rect) = window.get_rect() {
+            if rect.x == x && rect.y == y {
+                return Ok(false);
+            }
+        }
+        window.set_position(x, y)?;
+        Ok(true)
+    }
+
+    /// 处理位置锁定命令
+    #[allow(clippy::too_many_arguments)]
+    fn handle_pin(
+        &self,
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<String>,
+        position: &str,
+        duration: &str,
+        interval: &str,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    ) -> AppResult<()> {
+        let (x, y) = parse_position(position)?;
+        let duration = parse_duration(duration)?;
+        let interval = parse_duration(interval)?;
+        if interval.is_zero() {
+            return Err(AppError::invalid_parameter("--interval must be greater than zero"));
+        }
+
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        // 使用平台抽象层查找匹配的窗口
+        let mut windows = find_windows(filter, &process_names)?;
+
+        // 验证窗口数量
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        // 应用排序
+        apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
+
+        // 解析索引
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+
+        // 确定目标窗口：指定索引时只锁定这些窗口；否则没有 --all 时只锁定第一个窗口
+        let mut active: Vec<&WindowHandle> = (0..windows.len())
+            .filter(|i| indices.is_empty() || indices.contains(&(i + 1)))
+            .take(if all || !indices.is_empty() { usize::MAX } else { 1 })
+            .map(|i| &windows[i])
+            .collect();
+
+        // Ctrl-C 时结束循环并正常输出汇总，而不是直接终止进程
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let stop = Arc::clone(&stop);
+            ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))
+                .map_err(|e| AppError::platform(format!("Failed to install Ctrl-C handler: {}", e)))?;
+        }
+
+        let started = Instant::now();
+        let deadline = started + duration;
+        let mut first_pass = true;
+        let mut report = OperationReport::new("pin", format, options);
+        let mut reapplied = 0;
+
+        loop {
+            let mut remaining = Vec::with_capacity(active.len());
+            for window in active {
+                // 只在第一轮检查，跳过的窗口不再锁定
+                if first_pass && report.skip_hung(window) {
+                    continue;
+                }
+                report.throttle();
+                match Self::enforce(window, x, y) {
+                    Ok(moved) => {
+                        if first_pass {
+                            report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                                "Pinned: {} (PID: {}) at {},{}", window.title, window.pid, x, y
+                            ))?;
+                        } else if moved {
+                            report.progress(&format!("Re-applied: {} (PID: {}) to {},{}", window.title, window.pid, x, y))?;
+                            reapplied += 1;
+                        }
+                        remaining.push(window);
+                    }
+                    Err(e) => {
+                        // 窗口已关闭或无法移动时不再继续锁定
+                        let message = format!("Stopped pinning window {} (PID: {}): {}", window.title, window.pid, e);
+                        if first_pass {
+                            report.fail(window.pid, &window.title, &e, message);
+                        } else {
+                            eprintln!("{}", message);
+                        }
+                    }
+                }
+            }
+            active = remaining;
+            first_pass = false;
+
+            if active.is_empty() || stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            std::thread::sleep(interval.min(deadline - now));
+        }
+
+        if stop.load(Ordering::SeqCst) && report.modified() > 0 {
+            report.progress("Interrupted")?;
+        }
+        let pinned = report.modified();
+        report.finish(format!("Successfully pinned {} window(s) for {:.1}s, re-applied position {} time(s)",
+                              pinned, started.elapsed().as_secs_f64(), reapplied))
+    }
+}
+
+impl Feature for PinFeature {
+    fn name(&self) -> &'static str {
+        "pin"
+    }
+
+    fn description(&self) -> &'static str {
+        "Keep windows pinned to a position"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        if let Some(matches) = matches.subcommand_matches("windows/pin") {
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let index = matches.get_one::<String>("index").map(|s| s.to_string());
+            let position = matches.get_one::<String>("position").cloned().unwrap_or_default();
+            let duration = matches.get_one::<String>("duration").cloned().unwrap_or_default();
+            let interval = matches.get_one::<String>("interval").cloned().unwrap_or_default();
+
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsPin {
+                filter,
+                all,
+                index,
+                position,
+                duration,
+                interval,
+                sort_position,
+                options,
+                format,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsPin { filter, all, index, position, duration, interval, sort_position, options, format } = subcommand {
+            self.handle_pin(filter, *all, index.clone(), position, duration, interval, *sort_position, *options, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
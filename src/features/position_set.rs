@@ -1,11 +1,16 @@
 // src/features/position_set.rs
 use clap::{Arg, Command};
-use crate::cli::SubCommand;
+use crate::cli::{SubCommand, json_or_format};
 use super::feature_trait::Feature;
 use crate::platform::find_windows;
-use crate::error::{AppError, AppResult};
-use crate::sorting::{SortOrder, PositionSort};
-use crate::utils::{parse_indices, validate_position_parameters, calculate_positions};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::AppResult;
+use crate::sorting::{SortOrder, PositionSort, LAYOUT_SORT_POSITION};
+use crate::platform::get_monitor_work_areas;
+use crate::utils::{parse_indices, validate_position_parameters, calculate_positions, read_layout_file, ensure_onscreen, is_relative_position, parse_relative_position, resolve_relative_position};
+#[cfg(feature = "undo")]
+use crate::undo::UndoRecorder;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
 
 /// 窗口位置设置特性
 pub struct PositionSetFeature;
@@ -24,7 +29,8 @@ impl PositionSetFeature {
                     .short('p')
                     .long("pid")
                     .value_name("PID")
-                    .help("Filter by process ID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
             )
             .arg(
                 Arg::new("name")
@@ -40,6 +46,7 @@ impl PositionSetFeature {
                     .value_name("TITLE")
                     .help("Filter by window title (contains)")
             )
+            .args(WindowFilter::exact_match_args())
             .arg(
                 Arg::new("all")
                     .short('a')
@@ -52,7 +59,7 @@ impl PositionSetFeature {
                     .long("position")
                     .value_name("X,Y")
                     .num_args(1)
-                    .help("Set window position (e.g., \"100,100\")")
+                    .help("Set window position (e.g., \"100,100\"); a percentage places the window center at that fraction of its monitor's work area (e.g., \"50%,25%\")")
             )
             .arg(
                 Arg::new("index")
@@ -70,6 +77,14 @@ impl PositionSetFeature {
                     .default_value("")
                     .help("Multiple positions layout (e.g., \"100,100,150,120,200,140\")")
             )
+            .arg(
+                Arg::new("layout_file")
+                    .long("layout-file")
+                    .value_name("PATH")
+                    .num_args(1)
+                    .help("Read layout positions from a file (one \"X,Y\" per line, or a JSON array)")
+                    .conflicts_with("layout")
+            )
             .arg(
                 Arg::new("x_start")
                     .long("x-start")
@@ -98,58 +113,71 @@ impl PositionSetFeature {
                     .num_args(1)
                     .help("Y step for multiple windows")
             )
+            .arg(
+                Arg::new("validate_onscreen")
+                    .long("validate-onscreen")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Fail if any target position is outside all monitor work areas")
+            )
+            .arg(
+                Arg::new("clamp")
+                    .long("clamp")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Move off-screen target positions onto the nearest monitor (implies --validate-onscreen)")
+            )
             .arg(
                 Arg::new("sort_position")
                     .long("sort-position")
                     .value_name("X_ORDER|Y_ORDER")
                     .num_args(1)
                     .allow_hyphen_values(true)
-                    .default_value("1|1")
-                    .help("Sort by position: X_ORDER|Y_ORDER, e.g., 1|-1 for X ascending, Y descending")
+                    .default_value(LAYOUT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary). Defaults to 1|1 so layout slots are assigned in a stable order; use 0|0 to keep Z-order")
             )
-    }
-    
-    /// 统一的字段提取函数
-    fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-        let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-        let name = matches.get_one::<String>("name").map(|s| s.to_string());
-        let title = matches.get_one::<String>("title").map(|s| s.to_string());
-        (pid, name, title)
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
     }
     
     /// 处理位置设置命令
+    #[allow(clippy::too_many_arguments)]
     fn handle_position_set(
         &self,
-        pid_filter: Option<String>,
-        name_filter: Option<String>,
-        title_filter: Option<String>,
+        filter: &WindowFilter,
         all: bool,
         position: Option<String>,
         index: Option<String>,
         layout: Option<String>,
+        layout_file: Option<String>,
         x_start: Option<String>,
         y_start: Option<String>,
         x_step: Option<String>,
         y_step: Option<String>,
+        validate_onscreen: bool,
+        clamp: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
     ) -> AppResult<()> {
+        // 布局文件等价于 --layout
+        let layout = match layout_file {
+            Some(path) => Some(read_layout_file(&path)?),
+            None => layout,
+        };
+
         // 获取进程名称用于过滤
-        let processes = crate::process::get_processes();
-        let process_names: Vec<(u32, String)> = processes
-            .iter()
-            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-            .collect();
+        let process_names = ProcessNameIndex::collect(filter);
 
         // 使用平台抽象层查找匹配的窗口
-        let mut windows = find_windows(&pid_filter, &name_filter, &title_filter, &process_names);
+        let mut windows = find_windows(filter, &process_names)?;
         
         // 验证窗口数量
         if windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+            return Err(filter.no_windows_error(&process_names));
         }
 
         // 应用排序
         crate::sorting::apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
 
         // 解析索引
         let indices = parse_indices(&index.unwrap_or_default(), windows.len());
@@ -157,49 +185,75 @@ impl PositionSetFeature {
         // 验证参数组合
         validate_position_parameters(&position, &layout, &x_start, &y_start, &x_step, &y_step)?;
 
-        // 获取位置列表
-        let positions = calculate_positions(
-            windows.len(),
-            &position,
-            &layout.unwrap_or_default(),
-            &x_start, &y_start, &x_step, &y_step,
-        )?;
-
-        // 执行位置设置
-        let mut count = 0;
-        for (i, window) in windows.iter().enumerate() {
-            // 检查索引过滤
-            if !indices.is_empty() && !indices.contains(&(i + 1)) {
-                continue;
+        // 获取位置列表：百分比位置按每个窗口所在显示器的工作区解析
+        let mut positions = match position.as_deref().filter(|p| is_relative_position(p)) {
+            Some(spec) => {
+                let spec = parse_relative_position(spec)?;
+                let work_areas = get_monitor_work_areas()?;
+                windows
+                    .iter()
+                    .map(|window| resolve_relative_position(spec, &window.rect, &work_areas))
+                    .collect::<AppResult<Vec<_>>>()?
             }
+            None => calculate_positions(
+                windows.len(),
+                &position,
+                &layout.unwrap_or_default(),
+                &x_start, &y_start, &x_step, &y_step,
+            )?,
+        };
 
-            // 检查是否应用所有窗口
-            if !all && indices.is_empty() && i > 0 {
-                break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
+        // 确定目标窗口：指定索引时只操作这些窗口；否则没有 --all 时只操作第一个窗口
+        let targets: Vec<usize> = (0..windows.len())
+            .filter(|i| indices.is_empty() || indices.contains(&(i + 1)))
+            .take(if all || !indices.is_empty() { usize::MAX } else { 1 })
+            .collect();
+
+        // 应用前检查目标位置是否在屏幕内
+        if validate_onscreen || clamp {
+            let work_areas = get_monitor_work_areas()?;
+            for &i in &targets {
+                if let Some(pos) = positions.get_mut(i) {
+                    let checked = ensure_onscreen(*pos, &work_areas, clamp)?;
+                    if checked != *pos {
+                        eprintln!("Warning: Position {},{} is off screen, clamped to {},{}",
+                                 pos.0, pos.1, checked.0, checked.1);
+                        *pos = checked;
+                    }
+                }
             }
+        }
+
+        // 执行位置设置
+        let mut report = OperationReport::new("set_position", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("set_position");
+        for &i in &targets {
+            let window = &windows[i];
 
             // 获取对应的位置
             if let Some(pos) = positions.get(i) {
+                if report.skip_hung(window) {
+                    continue;
+                }
+                report.throttle();
+                #[cfg(feature = "undo")]
+                undo.capture(window);
                 match window.set_position(pos.0, pos.1) {
-                    Ok(()) => {
-                        println!("{}: {} (PID: {}) to position {},{}", 
-                                 "Position set", window.title, window.pid, pos.0, pos.1);
-                        count += 1;
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to set position for window {} (PID: {}): {}", 
-                                 window.title, window.pid, e);
-                    }
+                    Ok(()) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                        "Position set: {} (PID: {}) to position {},{}", window.title, window.pid, pos.0, pos.1
+                    ))?,
+                    Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                        "Failed to set position for window {} (PID: {}): {}", window.title, window.pid, e
+                    )),
                 }
             }
         }
 
-        if count == 0 {
-            return Err(AppError::NoWindowsModified);
-        }
-
-        println!("Successfully positioned {} window(s)", count);
-        Ok(())
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully positioned {} window(s)", count))
     }
 }
 
@@ -218,42 +272,39 @@ impl Feature for PositionSetFeature {
     
     fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
         if let Some(matches) = matches.subcommand_matches("windows/position/set") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let position = matches.get_one::<String>("position").map(|s| s.to_string());
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
             let layout = matches.get_one::<String>("layout").map(|s| s.to_string());
+            let layout_file = matches.get_one::<String>("layout_file").map(|s| s.to_string());
             let x_start = matches.get_one::<String>("x_start").map(|s| s.to_string());
             let y_start = matches.get_one::<String>("y_start").map(|s| s.to_string());
             let x_step = matches.get_one::<String>("x_step").map(|s| s.to_string());
             let y_step = matches.get_one::<String>("y_step").map(|s| s.to_string());
+            let validate_onscreen = matches.get_flag("validate_onscreen");
+            let clamp = matches.get_flag("clamp");
             
-            let sort_position = match matches.get_one::<String>("sort_position").map(|s| s.as_str()) {
-                Some(s) => {
-                    match s.parse() {
-                        Ok(pos) => pos,
-                        Err(_) => {
-                            eprintln!("Warning: Invalid position sort format '{}', using default", s);
-                            PositionSort::default()
-                        }
-                    }
-                }
-                None => PositionSort::default(),
-            };
+            let sort_position = PositionSort::from_matches(matches, LAYOUT_SORT_POSITION);
             
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
             Some(SubCommand::WindowsPositionSet { 
-                pid, 
-                name, 
-                title, 
+                filter,
                 all,
                 position,
                 index,
                 layout,
+                layout_file,
                 x_start,
                 y_start,
                 x_step,
                 y_step,
+                validate_onscreen,
+                clamp,
                 sort_position,
+                options,
+                format,
             })
         } else {
             None
@@ -262,22 +313,25 @@ impl Feature for PositionSetFeature {
     
     fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
         if let SubCommand::WindowsPositionSet { 
-            pid, name, title, all, position, index, layout, 
-            x_start, y_start, x_step, y_step, sort_position 
+            filter, all, position, index, layout, layout_file,
+            x_start, y_start, x_step, y_step, validate_onscreen, clamp, sort_position, options, format
         } = subcommand {
             self.handle_position_set(
-                pid.clone(),
-                name.clone(), 
-                title.clone(),
+                filter,
                 *all,
                 position.clone(),
                 index.clone(),
                 layout.clone(),
+                layout_file.clone(),
                 x_start.clone(),
                 y_start.clone(),
                 x_step.clone(),
                 y_step.clone(),
+                *validate_onscreen,
+                *clamp,
                 *sort_position,
+                *options,
+                format.clone(),
             )
         } else {
             Ok(()) // 不是本特性处理的命令，忽略
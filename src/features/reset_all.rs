@@ -0,0 +1,176 @@
+// src/features/reset_all.rs
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::{find_windows, WindowHandle};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::{AppError, AppResult};
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 窗口状态恢复特性（"修复桌面"）
+pub struct ResetAllFeature;
+
+impl ResetAllFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/reset-all")
+            .about("Restore matching windows to a normal state: restored, fully opaque, not always on top")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Required when no filter is given: confirms resetting every window")
+            )
+            .arg(
+                Arg::new("dry_run")
+                    .long("dry-run")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("List the windows that would be reset without changing them")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 处理恢复命令
+    fn handle_reset_all(&self, filter: &WindowFilter, all: bool, dry_run: bool, options: OperationOptions, format: OutputFormat) -> AppResult<()> {
+        // 没有过滤条件时会影响所有窗口，必须用 --all 确认
+        if !filter.has_criteria() && !all {
+            return Err(AppError::invalid_parameter(
+                "windows/reset-all without --pid/--name/--title affects every window; pass --all to confirm"
+            ));
+        }
+
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        // 使用平台抽象层查找匹配的窗口
+        let windows = find_windows(filter, &process_names)?;
+
+        // 验证窗口数量
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        let mut report = OperationReport::new("reset", format, options);
+        if dry_run {
+            for window in &windows {
+                report.record(window.pid, &window.title, OperationStatus::DryRun, format!(
+                    "Would reset: {} (PID: {})", window.title, window.pid
+                ))?;
+            }
+            return report.finish(format!("Dry run: {} window(s) would be reset", windows.len()));
+        }
+
+        for window in &windows {
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            let failures = Self::reset_window(window);
+            if failures.is_empty() {
+                report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "Reset: {} (PID: {})", window.title, window.pid
+                ))?;
+            } else {
+                let error = failures.join("; ");
+                report.fail(window.pid, &window.title, &error, format!(
+                    "Failed to fully reset window {} (PID: {}): {}", window.title, window.pid, error
+                ));
+            }
+        }
+
+        let count = report.modified();
+        report.finish(format!("Successfully reset {} window(s)", count))
+    }
+
+    /// 依次执行各项恢复操作，返回失败的步骤（某一步失败不影响其余步骤）
+    fn reset_window(window: &WindowHandle) -> Vec<String> {
+        let steps: [(&str, AppResult<()>); 3] = [
+            ("restore", window.restore()),
+            ("opacity", window.set_transparency(100)),
+            ("always on top", window.set_always_on_top(false)),
+        ];
+
+        steps
+            .into_iter()
+            .filter_map(|(step, result)| result.err().map(|e| format!("{}: {}", step, e)))
+            .collect()
+    }
+}
+
+impl Feature for ResetAllFeature {
+    fn name(&self) -> &'static str {
+        "reset_all"
+    }
+
+    fn description(&self) -> &'static str {
+        "Restore windows to a normal state"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        if let Some(matches) = matches.subcommand_matches("windows/reset-all") {
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let dry_run = matches.get_flag("dry_run");
+
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsResetAll {
+                filter,
+                all,
+                dry_run,
+                options,
+                format,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsResetAll { filter, all, dry_run, options, format } = subcommand {
+            self.handle_reset_all(filter, *all, *dry_run, *options, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
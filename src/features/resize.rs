@@ -1,11 +1,15 @@
 // src/features/resize.rs
 use clap::{Arg, Command};
-use crate::cli::SubCommand;
+use crate::cli::{SubCommand, json_or_format};
 use super::feature_trait::Feature;
 use crate::platform::find_windows;
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
 use crate::error::{AppError, AppResult};
-use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting};
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
 use crate::utils::parse_indices;
+#[cfg(feature = "undo")]
+use crate::undo::UndoRecorder;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
 
 /// 窗口调整大小特性
 pub struct ResizeFeature;
@@ -24,7 +28,8 @@ impl ResizeFeature {
                     .short('p')
                     .long("pid")
                     .value_name("PID")
-                    .help("Filter by process ID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
             )
             .arg(
                 Arg::new("name")
@@ -40,6 +45,7 @@ impl ResizeFeature {
                     .value_name("TITLE")
                     .help("Filter by window title (contains)")
             )
+            .args(WindowFilter::exact_match_args())
             .arg(
                 Arg::new("all")
                     .short('a')
@@ -100,17 +106,11 @@ impl ResizeFeature {
                     .value_name("X_ORDER|Y_ORDER")
                     .num_args(1)
                     .allow_hyphen_values(true)
-                    .default_value("0|0")
-                    .help("Sort by position: X_ORDER|Y_ORDER, e.g., 1|-1 for X ascending, Y descending")
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
             )
-    }
-    
-    /// 统一的字段提取函数
-    fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-        let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-        let name = matches.get_one::<String>("name").map(|s| s.to_string());
-        let title = matches.get_one::<String>("title").map(|s| s.to_string());
-        (pid, name, title)
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
     }
     
     /// 解析尺寸字符串 "WIDTHxHEIGHT" -> (width, height)
@@ -133,11 +133,10 @@ impl ResizeFeature {
     }
     
     /// 处理调整大小命令
+    #[allow(clippy::too_many_arguments)]
     fn handle_resize(
         &self,
-        pid_filter: Option<String>,
-        name_filter: Option<String>,
-        title_filter: Option<String>,
+        filter: &WindowFilter,
         all: bool,
         index: Option<String>,
         width: Option<String>,
@@ -146,6 +145,8 @@ impl ResizeFeature {
         keep_position: bool,
         center: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
     ) -> AppResult<()> {
         // 解析尺寸参数
         let (target_width, target_height) = if let Some(size_str) = size {
@@ -166,27 +167,26 @@ impl ResizeFeature {
         };
         
         // 获取进程名称用于过滤
-        let processes = crate::process::get_processes();
-        let process_names: Vec<(u32, String)> = processes
-            .iter()
-            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-            .collect();
+        let process_names = ProcessNameIndex::collect(filter);
 
         // 使用平台抽象层查找匹配的窗口
-        let mut windows = find_windows(&pid_filter, &name_filter, &title_filter, &process_names);
+        let mut windows = find_windows(filter, &process_names)?;
         
         // 验证窗口数量
         if windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+            return Err(filter.no_windows_error(&process_names));
         }
 
         // 应用排序
         apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
 
         // 解析索引
         let indices = parse_indices(&index.unwrap_or_default(), windows.len());
 
-        let mut count = 0;
+        let mut report = OperationReport::new("resize", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("resize");
         for (i, window) in windows.iter().enumerate() {
             // 检查索引过滤
             if !indices.is_empty() && !indices.contains(&(i + 1)) {
@@ -198,26 +198,37 @@ impl ResizeFeature {
                 break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
             }
 
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            #[cfg(feature = "undo")]
+            undo.capture(window);
+
+            // 记录调整前的尺寸，用于输出
+            let previous = window.get_rect().ok();
+
             // 执行调整大小操作
             match window.resize(target_width, target_height, keep_position, center) {
                 Ok(()) => {
-                    println!("Resized: {} (PID: {}) to {}x{}", 
-                             window.title, window.pid, target_width, target_height);
-                    count += 1;
-                }
-                Err(e) => {
-                    eprintln!("Failed to resize window {} (PID: {}): {}", 
-                             window.title, window.pid, e);
+                    let message = match previous {
+                        Some(rect) => format!("Resized: {} (PID: {}) from {}x{} to {}x{}", 
+                                              window.title, window.pid, rect.width, rect.height, target_width, target_height),
+                        None => format!("Resized: {} (PID: {}) to {}x{}", 
+                                        window.title, window.pid, target_width, target_height),
+                    };
+                    report.record(window.pid, &window.title, OperationStatus::Ok, message)?;
                 }
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to resize window {} (PID: {}): {}", window.title, window.pid, e
+                )),
             }
         }
 
-        if count == 0 {
-            return Err(AppError::NoWindowsModified);
-        }
-
-        println!("Successfully resized {} window(s)", count);
-        Ok(())
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully resized {} window(s)", count))
     }
 }
 
@@ -236,7 +247,7 @@ impl Feature for ResizeFeature {
     
     fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
         if let Some(matches) = matches.subcommand_matches("windows/resize") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
             let width = matches.get_one::<String>("width").map(|s| s.to_string());
@@ -245,23 +256,12 @@ impl Feature for ResizeFeature {
             let keep_position = matches.get_flag("keep-position");
             let center = matches.get_flag("center");
             
-            let sort_position = match matches.get_one::<String>("sort_position").map(|s| s.as_str()) {
-                Some(s) => {
-                    match s.parse() {
-                        Ok(pos) => pos,
-                        Err(_) => {
-                            eprintln!("Warning: Invalid position sort format '{}', using default", s);
-                            PositionSort::default()
-                        }
-                    }
-                }
-                None => PositionSort::default(),
-            };
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
             
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
             Some(SubCommand::WindowsResize { 
-                pid, 
-                name, 
-                title, 
+                filter,
                 all,
                 index,
                 width,
@@ -270,6 +270,8 @@ impl Feature for ResizeFeature {
                 keep_position,
                 center,
                 sort_position,
+                options,
+                format,
             })
         } else {
             None
@@ -278,13 +280,11 @@ impl Feature for ResizeFeature {
     
     fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
         if let SubCommand::WindowsResize { 
-            pid, name, title, all, index, width, height, size, 
-            keep_position, center, sort_position 
+            filter, all, index, width, height, size, 
+            keep_position, center, sort_position, options, format
         } = subcommand {
             self.handle_resize(
-                pid.clone(),
-                name.clone(), 
-                title.clone(),
+                filter,
                 *all,
                 index.clone(),
                 width.clone(),
@@ -293,6 +293,8 @@ impl Feature for ResizeFeature {
                 *keep_position,
                 *center,
                 *sort_position,
+                *options,
+                format.clone(),
             )
         } else {
             Ok(()) // 不是本特性处理的命令，忽略
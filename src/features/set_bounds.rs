@@ -0,0 +1,205 @@
+// src/features/set_bounds.rs
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::find_windows;
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::AppResult;
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::types::WindowRect;
+use crate::utils::{parse_indices, parse_rect, frame_to_window_rect};
+#[cfg(feature = "undo")]
+use crate::undo::UndoRecorder;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 窗口位置和尺寸一次性设置特性
+pub struct SetBoundsFeature;
+
+impl SetBoundsFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/set-bounds")
+            .about("Move and resize windows in a single call (no double-move flicker)")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Apply to all matching windows")
+            )
+            .arg(
+                Arg::new("index")
+                    .long("index")
+                    .value_name("INDICES")
+                    .num_args(1)
+                    .default_value("")
+                    .help("Window indices to set (e.g., \"1,2,3\"), empty means all")
+            )
+            .arg(
+                Arg::new("rect")
+                    .long("rect")
+                    .value_name("X,Y,WIDTH,HEIGHT")
+                    .required(true)
+                    .allow_hyphen_values(true)
+                    .value_parser(parse_rect)
+                    .help("Target position and size (e.g., \"0,0,960,1040\")")
+            )
+            .arg(
+                Arg::new("frame-bounds")
+                    .long("frame-bounds")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Treat --rect as the visible frame, compensating for the invisible border/shadow around the window")
+            )
+            .arg(
+                Arg::new("sort_position")
+                    .long("sort-position")
+                    .value_name("X_ORDER|Y_ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 处理设置位置和尺寸命令
+    #[allow(clippy::too_many_arguments)]
+    fn handle_set_bounds(
+        &self,
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<String>,
+        rect: &WindowRect,
+        frame_bounds: bool,
+        sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
+    ) -> AppResult<()> {
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        // 使用平台抽象层查找匹配的窗口
+        let mut windows = find_windows(filter, &process_names)?;
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+
+        let mut report = OperationReport::new("set_bounds", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("set_bounds");
+        for (i, window) in windows.iter().enumerate() {
+            // 检查索引过滤
+            if !indices.is_empty() && !indices.contains(&(i + 1)) {
+                continue;
+            }
+
+            // 检查是否应用所有窗口
+            if !all && indices.is_empty() && i > 0 {
+                break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
+            }
+
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            #[cfg(feature = "undo")]
+            undo.capture(window);
+
+            // --frame-bounds：按窗口当前的阴影边框宽度扩大目标矩形，使可见边缘落在指定位置
+            let target = match (frame_bounds, window.get_rect(), window.get_frame_rect()) {
+                (true, Ok(current), Ok(frame)) => frame_to_window_rect(rect, &current, &frame),
+                _ => rect.clone(),
+            };
+
+            match window.set_bounds(target.x, target.y, target.width, target.height) {
+                Ok(()) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "Set bounds: {} (PID: {}) from {} to {}", window.title, window.pid, window.rect, rect
+                ))?,
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to set bounds of window {} (PID: {}): {}", window.title, window.pid, e
+                )),
+            }
+        }
+
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully set bounds of {} window(s)", count))
+    }
+}
+
+impl Feature for SetBoundsFeature {
+    fn name(&self) -> &'static str {
+        "set_bounds"
+    }
+
+    fn description(&self) -> &'static str {
+        "Atomic window move and resize"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        let matches = matches.subcommand_matches("windows/set-bounds")?;
+        let filter = WindowFilter::from_matches(matches);
+        let all = matches.get_flag("all");
+        let index = matches.get_one::<String>("index").map(|s| s.to_string());
+        let rect = matches.get_one::<WindowRect>("rect").cloned()?;
+        let frame_bounds = matches.get_flag("frame-bounds");
+
+        let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+        let options = OperationOptions::from_matches(matches);
+        let format = json_or_format(matches);
+        Some(SubCommand::WindowsSetBounds { filter, all, index, rect, frame_bounds, sort_position, options, format })
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsSetBounds { filter, all, index, rect, frame_bounds, sort_position, options, format } = subcommand {
+            self.handle_set_bounds(filter, *all, index.clone(), rect, *frame_bounds, *sort_position, *options, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
@@ -0,0 +1,154 @@
+// src/features/state.rs
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::find_windows;
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::AppResult;
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::types::WindowStateRecord;
+use crate::utils::parse_indices;
+use crate::output::{OutputFormat, display_window_states};
+
+/// 窗口状态查询特性
+pub struct StateFeature;
+
+impl StateFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/state")
+            .about("Show the state, topmost flag, opacity and responsiveness of matching windows (read-only)")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+            .arg(
+                Arg::new("index")
+                    .long("index")
+                    .value_name("INDICES")
+                    .num_args(1)
+                    .default_value("")
+                    .help("Window indices to report (e.g., \"1,2,3\"), empty means all")
+            )
+            .arg(
+                Arg::new("sort_position")
+                    .long("sort-position")
+                    .value_name("X_ORDER|Y_ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(
+                Arg::new("format")
+                    .short('f')
+                    .long("format")
+                    .value_name("FORMAT")
+                    .value_parser(clap::value_parser!(OutputFormat))
+                    .default_value("auto")
+                    .help("Output format (auto: table on a terminal, json when piped)")
+            )
+    }
+
+    /// 处理状态查询命令
+    fn handle_state(
+        &self,
+        filter: &WindowFilter,
+        index: Option<String>,
+        sort_position: PositionSort,
+        format: OutputFormat,
+    ) -> AppResult<()> {
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(filter);
+
+        // 使用平台抽象层查找匹配的窗口
+        let mut windows = find_windows(filter, &process_names)?;
+        if windows.is_empty() {
+            return Err(filter.no_windows_error(&process_names));
+        }
+
+        apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
+        let indices = parse_indices(&index.unwrap_or_default(), windows.len());
+
+        // 逐个查询窗口属性，单项查询失败时该项为空，不影响其他窗口
+        let states: Vec<WindowStateRecord> = windows
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| indices.is_empty() || indices.contains(&(i + 1)))
+            .map(|(_, window)| WindowStateRecord {
+                pid: window.pid,
+                title: window.title.clone(),
+                state: window.show_state().ok(),
+                topmost: window.is_always_on_top().ok(),
+                opacity: window.opacity().ok().flatten(),
+                responding: window.is_responding(),
+            })
+            .collect();
+
+        display_window_states(&states, format)
+    }
+}
+
+impl Feature for StateFeature {
+    fn name(&self) -> &'static str {
+        "state"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read-only window state query"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        let matches = matches.subcommand_matches("windows/state")?;
+        let filter = WindowFilter::from_matches(matches);
+        let index = matches.get_one::<String>("index").map(|s| s.to_string());
+
+        let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+        let format = json_or_format(matches);
+        Some(SubCommand::WindowsState { filter, index, sort_position, format })
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsState { filter, index, sort_position, format } = subcommand {
+            self.handle_state(filter, index.clone(), *sort_position, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
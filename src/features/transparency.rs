@@ -1,11 +1,18 @@
 // src/features/transparency.rs
 use clap::{Arg, Command};
-use crate::cli::SubCommand;
+use crate::cli::{SubCommand, json_or_format};
 use super::feature_trait::Feature;
 use crate::platform::find_windows;
-use crate::error::{AppError, AppResult};
-use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting};
-use crate::utils::parse_indices;
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::AppResult;
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::utils::{parse_indices, parse_gradient, gradient_levels};
+#[cfg(feature = "undo")]
+use crate::undo::UndoRecorder;
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 默认的最低不透明度，避免窗口完全不可见而无法找回
+const DEFAULT_MIN_OPACITY: &str = "10";
 
 /// 窗口透明度特性
 pub struct TransparencyFeature;
@@ -24,7 +31,8 @@ impl TransparencyFeature {
                     .short('p')
                     .long("pid")
                     .value_name("PID")
-                    .help("Filter by process ID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
             )
             .arg(
                 Arg::new("name")
@@ -40,6 +48,7 @@ impl TransparencyFeature {
                     .value_name("TITLE")
                     .help("Filter by window title (contains)")
             )
+            .args(WindowFilter::exact_match_args())
             .arg(
                 Arg::new("all")
                     .short('a')
@@ -72,93 +81,130 @@ impl TransparencyFeature {
                     .help("Reset transparency to fully opaque (100%)")
                     .conflicts_with("level")
             )
+            .arg(
+                Arg::new("gradient")
+                    .long("gradient")
+                    .value_name("START:END")
+                    .num_args(1)
+                    .help("Spread opacity across the sorted windows (e.g., 40:100); front window most opaque, back most transparent")
+                    .conflicts_with_all(["level", "reset"])
+            )
+            .arg(
+                Arg::new("min_opacity")
+                    .long("min-opacity")
+                    .value_name("PERCENT")
+                    .num_args(1)
+                    .value_parser(clap::value_parser!(u8).range(0..=100))
+                    .default_value(DEFAULT_MIN_OPACITY)
+                    .help("Lowest opacity that will be applied; lower levels are raised to it")
+            )
+            .arg(
+                Arg::new("allow_invisible")
+                    .long("allow-invisible")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Allow opacity below --min-opacity (down to 0%, fully invisible)")
+            )
             .arg(
                 Arg::new("sort_position")
                     .long("sort-position")
                     .value_name("X_ORDER|Y_ORDER")
                     .num_args(1)
                     .allow_hyphen_values(true)
-                    .default_value("0|0")
-                    .help("Sort by position: X_ORDER|Y_ORDER, e.g., 1|-1 for X ascending, Y descending")
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
             )
-    }
-    
-    /// 统一的字段提取函数
-    fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-        let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-        let name = matches.get_one::<String>("name").map(|s| s.to_string());
-        let title = matches.get_one::<String>("title").map(|s| s.to_string());
-        (pid, name, title)
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
     }
     
     /// 处理透明度命令
+    #[allow(clippy::too_many_arguments)]
     fn handle_transparency(
         &self,
-        pid_filter: Option<String>,
-        name_filter: Option<String>,
-        title_filter: Option<String>,
+        filter: &WindowFilter,
         all: bool,
         index: Option<String>,
         level: u8,
         reset: bool,
+        gradient: Option<String>,
+        min_opacity: u8,
+        allow_invisible: bool,
         sort_position: PositionSort,
+        options: OperationOptions,
+        format: OutputFormat,
     ) -> AppResult<()> {
         // 确定透明度级别
         let target_level = if reset { 100 } else { level };
+        let gradient = gradient.as_deref().map(parse_gradient).transpose()?;
         
         // 获取进程名称用于过滤
-        let processes = crate::process::get_processes();
-        let process_names: Vec<(u32, String)> = processes
-            .iter()
-            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-            .collect();
+        let process_names = ProcessNameIndex::collect(filter);
 
         // 使用平台抽象层查找匹配的窗口
-        let mut windows = find_windows(&pid_filter, &name_filter, &title_filter, &process_names);
+        let mut windows = find_windows(filter, &process_names)?;
         
         // 验证窗口数量
         if windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+            return Err(filter.no_windows_error(&process_names));
         }
 
         // 应用排序
         apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
 
         // 解析索引
         let indices = parse_indices(&index.unwrap_or_default(), windows.len());
 
-        let mut count = 0;
-        for (i, window) in windows.iter().enumerate() {
-            // 检查索引过滤
-            if !indices.is_empty() && !indices.contains(&(i + 1)) {
-                continue;
-            }
+        // 先确定目标窗口，渐变需要知道窗口总数
+        let targets: Vec<_> = windows
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| indices.is_empty() || indices.contains(&(i + 1)))
+            .take(if all || !indices.is_empty() { usize::MAX } else { 1 }) // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
+            .map(|(_, window)| window)
+            .collect();
+
+        let mut levels = match gradient {
+            Some((min, max)) => gradient_levels(min, max, targets.len()),
+            None => vec![target_level; targets.len()],
+        };
 
-            // 检查是否应用所有窗口
-            if !all && indices.is_empty() && i > 0 {
-                break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
+        // 低于下限的透明度会被提高到下限，除非指定 --allow-invisible
+        if !allow_invisible && levels.iter().any(|&level| level < min_opacity) {
+            eprintln!("Note: Opacity below {}% raised to {}% (use --allow-invisible to go lower)",
+                     min_opacity, min_opacity);
+            for level in levels.iter_mut() {
+                *level = (*level).max(min_opacity);
             }
+        }
 
+        let mut report = OperationReport::new("set_transparency", format, options);
+        #[cfg(feature = "undo")]
+        let mut undo = UndoRecorder::new("set_transparency");
+        for (window, &target_level) in targets.iter().zip(levels.iter()) {
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            #[cfg(feature = "undo")]
+            undo.capture(window);
             match window.set_transparency(target_level) {
                 Ok(()) => {
                     let action_str = if reset { "reset" } else { "set" };
-                    println!("{}: {} (PID: {}) to {}% opacity", 
-                             action_str, window.title, window.pid, target_level);
-                    count += 1;
-                }
-                Err(e) => {
-                    eprintln!("Failed to set transparency for window {} (PID: {}): {}", 
-                             window.title, window.pid, e);
+                    report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                        "{}: {} (PID: {}) to {}% opacity", action_str, window.title, window.pid, target_level
+                    ))?;
                 }
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to set transparency for window {} (PID: {}): {}", window.title, window.pid, e
+                )),
             }
         }
 
-        if count == 0 {
-            return Err(AppError::NoWindowsModified);
-        }
-
-        println!("Successfully modified {} window(s)", count);
-        Ok(())
+        #[cfg(feature = "undo")]
+        undo.save();
+        let count = report.modified();
+        report.finish(format!("Successfully modified {} window(s)", count))
     }
 }
 
@@ -177,34 +223,31 @@ impl Feature for TransparencyFeature {
     
     fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
         if let Some(matches) = matches.subcommand_matches("windows/transparency") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
             let level = *matches.get_one::<u8>("level").unwrap_or(&100);
             let reset = matches.get_flag("reset");
+            let gradient = matches.get_one::<String>("gradient").cloned();
+            let min_opacity = *matches.get_one::<u8>("min_opacity").unwrap_or(&10);
+            let allow_invisible = matches.get_flag("allow_invisible");
             
-            let sort_position = match matches.get_one::<String>("sort_position").map(|s| s.as_str()) {
-                Some(s) => {
-                    match s.parse() {
-                        Ok(pos) => pos,
-                        Err(_) => {
-                            eprintln!("Warning: Invalid position sort format '{}', using default", s);
-                            PositionSort::default()
-                        }
-                    }
-                }
-                None => PositionSort::default(),
-            };
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
             
+            let options = OperationOptions::from_matches(matches);
+            let format = json_or_format(matches);
             Some(SubCommand::WindowsTransparency { 
-                pid, 
-                name, 
-                title, 
+                filter,
                 all,
                 index,
                 level,
                 reset,
+                gradient,
+                min_opacity,
+                allow_invisible,
                 sort_position,
+                options,
+                format,
             })
         } else {
             None
@@ -212,16 +255,19 @@ impl Feature for TransparencyFeature {
     }
     
     fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
-        if let SubCommand::WindowsTransparency { pid, name, title, all, index, level, reset, sort_position } = subcommand {
+        if let SubCommand::WindowsTransparency { filter, all, index, level, reset, gradient, min_opacity, allow_invisible, sort_position, options, format } = subcommand {
             self.handle_transparency(
-                pid.clone(),
-                name.clone(), 
-                title.clone(),
+                filter,
                 *all,
                 index.clone(),
                 *level,
                 *reset,
+                gradient.clone(),
+                *min_opacity,
+                *allow_invisible,
                 *sort_position,
+                *options,
+                format.clone(),
             )
         } else {
             Ok(()) // 不是本特性处理的命令，忽略
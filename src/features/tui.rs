@@ -0,0 +1,296 @@
+// src/features/tui.rs
+use clap::{Arg, Command};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use crate::cli::SubCommand;
+use super::feature_trait::Feature;
+use crate::platform::{find_windows, WindowHandle};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::error::AppResult;
+use crate::sorting::Sortable;
+
+/// 交互式窗口浏览特性
+pub struct TuiFeature;
+
+impl TuiFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("tui")
+            .about("Interactive window browser: filter the list and minimize/maximize/restore/close/set-topmost the selected window")
+            .arg(
+                Arg::new("pid")
+                    .short('p')
+                    .long("pid")
+                    .value_name("PID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
+            )
+            .arg(
+                Arg::new("name")
+                    .short('n')
+                    .long("name")
+                    .value_name("NAME")
+                    .help("Filter by process name (contains)")
+            )
+            .arg(
+                Arg::new("title")
+                    .short('t')
+                    .long("title")
+                    .value_name("TITLE")
+                    .help("Filter by window title (contains)")
+            )
+            .args(WindowFilter::exact_match_args())
+    }
+
+    /// 处理 tui 命令：进入备用屏幕，退出（包括出错）时恢复终端
+    fn handle_tui(&self, filter: &WindowFilter) -> AppResult<()> {
+        let mut app = TuiApp::new(filter.clone());
+        app.refresh();
+
+        let mut terminal = ratatui::try_init()?;
+        let result = app.run(&mut terminal);
+        ratatui::try_restore()?;
+        result
+    }
+}
+
+/// 快捷键说明，显示在底部
+const HELP: &str = "↑/↓ select  / filter  m minimize  x maximize  r restore  t topmost  c close  F5 refresh  q quit";
+
+/// 交互界面的状态
+struct TuiApp {
+    filter: WindowFilter,
+    windows: Vec<WindowHandle>,
+    process_names: ProcessNameIndex,
+    /// 界面内输入的过滤文本（匹配标题或进程名）
+    query: String,
+    /// 是否正在输入过滤文本
+    editing: bool,
+    list_state: ListState,
+    status: String,
+    quit: bool,
+}
+
+impl TuiApp {
+    fn new(filter: WindowFilter) -> Self {
+        Self {
+            filter,
+            windows: Vec::new(),
+            process_names: ProcessNameIndex::default(),
+            query: String::new(),
+            editing: false,
+            list_state: ListState::default(),
+            status: String::new(),
+            quit: false,
+        }
+    }
+
+    /// 重新枚举窗口，保持当前选中的序号（超出范围时选中最后一个）
+    fn refresh(&mut self) {
+        self.process_names = ProcessNameIndex::collect(&self.filter);
+        match find_windows(&self.filter, &self.process_names) {
+            Ok(windows) => self.windows = windows,
+            Err(e) => {
+                self.windows.clear();
+                self.status = e.to_string();
+            }
+        }
+        self.clamp_selection();
+    }
+
+    /// 当前过滤文本下可见窗口在 `windows` 中的下标
+    fn visible(&self) -> Vec<usize> {
+        visible_indices(&self.windows, &self.process_names, &self.query)
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state.select((len > 0).then(|| selected.min(len - 1)));
+    }
+
+    fn selected_window(&self) -> Option<&WindowHandle> {
+        let visible = self.visible();
+        self.list_state.selected().and_then(|i| visible.get(i)).map(|&i| &self.windows[i])
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> AppResult<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [list_area, filter_area, status_area] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let visible = self.visible();
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|&i| {
+                let window = &self.windows[i];
+                let name = self.process_names.get(window.pid).unwrap_or("Unknown");
+                ListItem::new(format!("{:>7}  {:<20}  {:<22}  {}", window.pid, name, window.rect, window.title))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" Windows ({}/{}) ", visible.len(), self.windows.len())))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        let cursor = if self.editing { "_" } else { "" };
+        frame.render_widget(Paragraph::new(format!("Filter: {}{}", self.query, cursor)), filter_area);
+        let status = if self.status.is_empty() { HELP } else { self.status.as_str() };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        if self.editing {
+            match code {
+                KeyCode::Char(c) => self.query.push(c),
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Enter | KeyCode::Esc => self.editing = false,
+                _ => {}
+            }
+            self.clamp_selection();
+            return;
+        }
+
+        self.status.clear();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Char('/') => self.editing = true,
+            KeyCode::F(5) => self.refresh(),
+            KeyCode::Char('m') => self.act("Minimized", WindowHandle::minimize),
+            KeyCode::Char('x') => self.act("Maximized", WindowHandle::maximize),
+            KeyCode::Char('r') => self.act("Restored", WindowHandle::restore),
+            KeyCode::Char('t') => self.act("Toggled topmost", |window| {
+                window.set_always_on_top(!window.is_always_on_top()?)
+            }),
+            KeyCode::Char('c') => {
+                self.act("Closed", WindowHandle::close);
+                self.refresh();
+            }
+            _ => {}
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.visible().len();
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(step_selection(selected, delta, len));
+    }
+
+    /// 对选中的窗口执行操作，结果显示在状态栏
+    fn act(&mut self, done: &str, operation: impl Fn(&WindowHandle) -> AppResult<()>) {
+        let Some(window) = self.selected_window() else {
+            return;
+        };
+        self.status = match operation(window) {
+            Ok(()) => format!("{}: {} (PID: {})", done, window.title, window.pid),
+            Err(e) => format!("Failed: {} (PID: {}): {}", window.title, window.pid, e),
+        };
+    }
+}
+
+/// 标题或进程名包含 `query`（不区分大小写）的窗口下标；`query` 为空时全部可见
+fn visible_indices<T: Sortable>(windows: &[T], process_names: &ProcessNameIndex, query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    windows
+        .iter()
+        .enumerate()
+        .filter(|(_, window)| {
+            query.is_empty()
+                || window.get_title().to_lowercase().contains(&query)
+                || process_names.get(window.get_pid()).is_some_and(|name| name.to_lowercase().contains(&query))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// 在 `len` 项中移动选中位置，停在首尾而不回绕；列表为空时没有选中项
+fn step_selection(selected: usize, delta: isize, len: usize) -> Option<usize> {
+    (len > 0).then(|| selected.saturating_add_signed(delta).min(len - 1))
+}
+
+impl Feature for TuiFeature {
+    fn name(&self) -> &'static str {
+        "tui"
+    }
+
+    fn description(&self) -> &'static str {
+        "Interactive window browser"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        let matches = matches.subcommand_matches("tui")?;
+        Some(SubCommand::Tui { filter: WindowFilter::from_matches(matches) })
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::Tui { filter } = subcommand {
+            self.handle_tui(filter)
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProcessInfo, WindowInfo, WindowRect};
+
+    #[test]
+    fn test_visible_indices() {
+        let window = |pid, title: &str| WindowInfo::for_test(pid, title, WindowRect::new(0, 0, 800, 600));
+        let process = |pid: &str, name: &str| ProcessInfo { has_window: true, ..ProcessInfo::for_test(pid, name) };
+        let names = ProcessNameIndex::from_processes(&[process("1", "Code.exe"), process("2", "chrome.exe")]);
+        let windows = [window(1, "main.rs"), window(2, "Docs"), window(3, "Chrome Settings")];
+
+        assert_eq!(visible_indices(&windows, &names, ""), vec![0, 1, 2]);
+        assert_eq!(visible_indices(&windows, &names, "CHROME"), vec![1, 2]);
+        assert_eq!(visible_indices(&windows, &names, "code"), vec![0]);
+    }
+
+    #[test]
+    fn test_step_selection() {
+        assert_eq!(step_selection(0, -1, 3), Some(0));
+        assert_eq!(step_selection(1, 1, 3), Some(2));
+        assert_eq!(step_selection(2, 1, 3), Some(2));
+        assert_eq!(step_selection(5, 0, 3), Some(2));
+        assert_eq!(step_selection(0, 1, 0), None);
+    }
+}
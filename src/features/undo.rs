@@ -0,0 +1,121 @@
+// src/features/undo.rs
+use clap::Command;
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::platform::{find_windows, WindowHandle};
+use crate::filter::{WindowFilter, ProcessNameIndex};
+use crate::error::{AppError, AppResult};
+use crate::undo::{snapshot_path, UndoSnapshot, WindowSnapshot};
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
+
+/// 撤销最近一次布局修改特性
+pub struct UndoFeature;
+
+impl UndoFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/undo")
+            .about("Restore the position, size and opacity of the windows changed by the last layout command in this session")
+            .arg(OperationReport::profile_arg())
+            .arg(OperationReport::format_arg())
+    }
+
+    /// 处理撤销命令
+    fn handle_undo(&self, options: OperationOptions, format: OutputFormat) -> AppResult<()> {
+        let path = snapshot_path();
+        let snapshot = UndoSnapshot::load(&path)?.ok_or_else(|| {
+            AppError::invalid_parameter("Nothing to undo: no layout change has been recorded in this session")
+        })?;
+
+        // 获取进程名称用于过滤
+        let process_names = ProcessNameIndex::collect(&WindowFilter::default());
+        let windows = find_windows(&WindowFilter::default(), &process_names)?;
+
+        // 按保存时的窗口句柄重新匹配窗口，找不到时按 PID 和标题匹配
+        let candidates: Vec<(isize, u32, &str)> = windows.iter().map(|w| (w.raw_handle(), w.pid, w.title.as_str())).collect();
+        let matched = snapshot.match_windows(&candidates);
+
+        let mut report = OperationReport::new("undo", format, options);
+        for (saved, index) in snapshot.windows.iter().zip(matched) {
+            let Some(window) = index.map(|i| &windows[i]) else {
+                report.fail(saved.pid, &saved.title, AppError::NoMatchingWindows, format!(
+                    "Window no longer exists: {} (PID: {})", saved.title, saved.pid
+                ));
+                continue;
+            };
+
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
+            match Self::restore(window, saved) {
+                Ok(()) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "Restored: {} (PID: {}) to {}", window.title, window.pid, saved.rect
+                ))?,
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to restore window {} (PID: {}): {}", window.title, window.pid, e
+                )),
+            }
+        }
+
+        // 快照只能撤销一次，避免重复执行时覆盖之后的手动调整
+        std::fs::remove_file(&path)?;
+
+        let count = report.modified();
+        report.finish(format!("Undid {} for {} window(s)", snapshot.operation, count))
+    }
+
+    /// 恢复窗口的位置、尺寸、显示状态和不透明度（不透明度未变化时不修改）
+    fn restore(window: &WindowHandle, saved: &WindowSnapshot) -> AppResult<()> {
+        let rect = &saved.rect;
+        match saved.state {
+            // 还原位置与 GetWindowPlacement 使用相同的坐标，最小化的窗口保持最小化
+            Some(state) => window.set_placement(rect, state)?,
+            None => window.set_bounds(rect.x, rect.y, rect.width, rect.height)?,
+        }
+        if let Some(opacity) = saved.opacity {
+            if window.opacity().ok().flatten() != Some(opacity) {
+                window.set_transparency(opacity)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Feature for UndoFeature {
+    fn name(&self) -> &'static str {
+        "undo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Undo the last layout change"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        let matches = matches.subcommand_matches("windows/undo")?;
+        Some(SubCommand::WindowsUndo { options: OperationOptions::from_matches(matches), format: json_or_format(matches) })
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsUndo { options, format } = subcommand {
+            self.handle_undo(*options, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(windows)]
+        { true }
+        #[cfg(not(windows))]
+        { false }
+    }
+}
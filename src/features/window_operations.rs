@@ -1,13 +1,15 @@
 // src/features/window_operations.rs
 use clap::{Arg, Command};
-use crate::cli::SubCommand;
+use crate::cli::{SubCommand, json_or_format};
 use super::feature_trait::Feature;
-use crate::platform::find_windows;
+use crate::window::wait_for_windows;
+use crate::filter::{WindowFilter, PidSet};
 use crate::error::{AppError, AppResult};
-use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting};
-use crate::utils::parse_indices;
+use crate::sorting::{SortOrder, PositionSort, apply_window_handle_sorting, DEFAULT_SORT_POSITION};
+use crate::utils::{parse_indices, parse_duration, confirm_arg, confirm_mass_action};
+use crate::output::{OutputFormat, OperationReport, OperationOptions, OperationStatus};
 
-/// 窗口操作特性（最大化、最小化、还原）
+/// 窗口操作特性（最大化、最小化、还原、隐藏、显示）
 pub struct WindowOperationsFeature;
 
 impl WindowOperationsFeature {
@@ -30,6 +32,17 @@ impl WindowOperationsFeature {
         self.build_window_operation_command("windows/restore", "Restore windows to normal state")
     }
     
+    /// 构建隐藏子命令
+    fn build_hide_command(&self) -> Command {
+        self.build_window_operation_command("windows/hide", "Hide windows entirely (no taskbar entry, unlike minimize)")
+            .arg(confirm_arg())
+    }
+    
+    /// 构建显示子命令
+    fn build_show_command(&self) -> Command {
+        self.build_window_operation_command("windows/show", "Show windows hidden by windows/hide (requires --pid, --name or --title)")
+    }
+    
     /// 构建窗口操作子命令的通用函数
     fn build_window_operation_command(&self, name: &'static str, about: &'static str) -> Command {
         Command::new(name)
@@ -39,7 +52,8 @@ impl WindowOperationsFeature {
                     .short('p')
                     .long("pid")
                     .value_name("PID")
-                    .help("Filter by process ID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
             )
             .arg(
                 Arg::new("name")
@@ -55,6 +69,7 @@ impl WindowOperationsFeature {
                     .value_name("TITLE")
                     .help("Filter by window title (contains)")
             )
+            .args(WindowFilter::exact_match_args())
             .arg(
                 Arg::new("all")
                     .short('a')
@@ -76,68 +91,66 @@ impl WindowOperationsFeature {
                     .value_name("X_ORDER|Y_ORDER")
                     .num_args(1)
                     .allow_hyphen_values(true)
-                    .default_value("0|0")
-                    .help("Sort by position: X_ORDER|Y_ORDER, e.g., 1|-1 for X ascending, Y descending")
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
             )
-    }
-    
-    /// 统一的字段提取函数
-    fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-        let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-        let name = matches.get_one::<String>("name").map(|s| s.to_string());
-        let title = matches.get_one::<String>("title").map(|s| s.to_string());
-        (pid, name, title)
-    }
-    
-    /// 解析排序位置参数
-    fn parse_sort_position(matches: &clap::ArgMatches) -> PositionSort {
-        match matches.get_one::<String>("sort_position").map(|s| s.as_str()) {
-            Some(s) => {
-                match s.parse() {
-                    Ok(pos) => pos,
-                    Err(_) => {
-                        eprintln!("Warning: Invalid position sort format '{}', using default", s);
-                        PositionSort::default()
-                    }
-                }
-            }
-            None => PositionSort::default(),
-        }
+            .arg(OperationReport::profile_arg())
+            .arg(
+                Arg::new("wait-for")
+                    .long("wait-for")
+                    .value_name("DURATION")
+                    .num_args(1)
+                    .help("Wait up to DURATION (e.g., 5s, 500ms) for a matching window to appear before acting")
+            )
+            .arg(OperationReport::format_arg())
     }
     
     /// 处理窗口操作命令
+    #[allow(clippy::too_many_arguments)]
     fn handle_window_operation(
         &self,
-        pid_filter: Option<String>,
-        name_filter: Option<String>,
-        title_filter: Option<String>,
+        filter: &WindowFilter,
         all: bool,
         index: Option<String>,
         operation: WindowOperation,
         sort_position: PositionSort,
+        options: OperationOptions,
+        wait_for: Option<&str>,
+        format: OutputFormat,
+        yes: bool,
     ) -> AppResult<()> {
-        // 获取进程名称用于过滤
-        let processes = crate::process::get_processes();
-        let process_names: Vec<(u32, String)> = processes
-            .iter()
-            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-            .collect();
+        let wait_for = wait_for.map(parse_duration).transpose()?;
 
-        // 使用平台抽象层查找匹配的窗口
-        let mut windows = find_windows(&pid_filter, &name_filter, &title_filter, &process_names);
-        
-        // 验证窗口数量
-        if windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+        // 显示隐藏窗口时需要枚举不可见窗口；系统中有大量隐藏窗口，因此必须提供过滤条件
+        let mut filter = filter.clone();
+        if matches!(operation, WindowOperation::Show) {
+            if !filter.has_criteria() {
+                return Err(AppError::invalid_parameter(
+                    "windows/show requires --pid, --name or --title to select hidden windows"
+                ));
+            }
+            filter.include_hidden = true;
         }
 
+        // 查找匹配的窗口；指定 --wait-for 时轮询等待窗口出现，超时后才报告没有匹配的窗口
+        let mut windows = wait_for_windows(&filter, wait_for)?;
+
         // 应用排序
         apply_window_handle_sorting(&mut windows, &SortOrder::None, &sort_position);
+        filter.dedup_per_process(&mut windows);
 
         // 解析索引
         let indices = parse_indices(&index.unwrap_or_default(), windows.len());
 
-        let mut count = 0;
+        // 批量隐藏前确认，避免误操作大量窗口；按实际命中的窗口数判断，--index 选中多个窗口时同样需要确认
+        if operation.is_destructive() {
+            let targets = (0..windows.len())
+                .filter(|i| if indices.is_empty() { all || *i == 0 } else { indices.contains(&(i + 1)) })
+                .count();
+            confirm_mass_action(operation.as_str(), targets, yes)?;
+        }
+
+        let mut report = OperationReport::new(operation.as_str(), format, options);
         for (i, window) in windows.iter().enumerate() {
             // 检查索引过滤
             if !indices.is_empty() && !indices.contains(&(i + 1)) {
@@ -149,29 +162,36 @@ impl WindowOperationsFeature {
                 break; // 如果没有指定 --all 且没有指定索引，只操作第一个窗口
             }
 
+            if report.skip_hung(window) {
+                continue;
+            }
+            report.throttle();
             let result = match operation {
                 WindowOperation::Minimize => window.minimize(),
                 WindowOperation::Maximize => window.maximize(),
                 WindowOperation::Restore => window.restore(),
+                WindowOperation::Hide => window.hide(),
+                WindowOperation::Show => window.show(),
             };
 
             match result {
-                Ok(()) => {
-                    println!("{}: {} (PID: {})", operation.capitalized(), window.title, window.pid);
-                    count += 1;
-                }
-                Err(e) => {
-                    eprintln!("Failed to {} window {} (PID: {}): {}", 
-                             operation.as_str(), window.title, window.pid, e);
-                }
+                Ok(()) => report.record(window.pid, &window.title, OperationStatus::Ok, format!(
+                    "{}: {} (PID: {})", operation.capitalized(), window.title, window.pid
+                ))?,
+                Err(e) => report.fail(window.pid, &window.title, &e, format!(
+                    "Failed to {} window {} (PID: {}): {}", operation.as_str(), window.title, window.pid, e
+                )),
             }
         }
 
-        if count == 0 {
-            return Err(AppError::NoWindowsModified);
+        let count = report.modified();
+        report.finish(format!("Successfully {} {} window(s)", operation.past_tense(), count))?;
+
+        if matches!(operation, WindowOperation::Hide) {
+            eprintln!("Warning: hidden windows have no taskbar entry and may be hard to recover. \
+                       Use 'pscan windows/show --all' with the same filters to bring them back");
         }
 
-        println!("Successfully {} {} window(s)", operation.past_tense(), count);
         Ok(())
     }
 }
@@ -182,6 +202,8 @@ enum WindowOperation {
     Minimize,
     Maximize,
     Restore,
+    Hide,
+    Show,
 }
 
 impl WindowOperation {
@@ -190,6 +212,8 @@ impl WindowOperation {
             WindowOperation::Minimize => "minimize",
             WindowOperation::Maximize => "maximize",
             WindowOperation::Restore => "restore",
+            WindowOperation::Hide => "hide",
+            WindowOperation::Show => "show",
         }
     }
     
@@ -198,6 +222,8 @@ impl WindowOperation {
             WindowOperation::Minimize => "minimized",
             WindowOperation::Maximize => "maximized",
             WindowOperation::Restore => "restored",
+            WindowOperation::Hide => "hid",
+            WindowOperation::Show => "showed",
         }
     }
     
@@ -206,8 +232,15 @@ impl WindowOperation {
             WindowOperation::Minimize => "Minimized",
             WindowOperation::Maximize => "Maximized",
             WindowOperation::Restore => "Restored",
+            WindowOperation::Hide => "Hidden",
+            WindowOperation::Show => "Shown",
         }
     }
+
+    /// 难以撤销的操作（隐藏的窗口没有任务栏入口），批量执行前需要确认
+    fn is_destructive(&self) -> bool {
+        matches!(self, WindowOperation::Hide)
+    }
 }
 
 impl Feature for WindowOperationsFeature {
@@ -216,7 +249,7 @@ impl Feature for WindowOperationsFeature {
     }
     
     fn description(&self) -> &'static str {
-        "Window operations (minimize, maximize, restore)"
+        "Window operations (minimize, maximize, restore, hide, show)"
     }
     
     fn build_cli(&self, command: Command) -> Command {
@@ -224,27 +257,57 @@ impl Feature for WindowOperationsFeature {
             .subcommand(self.build_minimize_command())
             .subcommand(self.build_maximize_command())
             .subcommand(self.build_restore_command())
+            .subcommand(self.build_hide_command())
+            .subcommand(self.build_show_command())
     }
     
     fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
         if let Some(matches) = matches.subcommand_matches("windows/minimize") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
-            let sort_position = Self::parse_sort_position(matches);
-            Some(SubCommand::WindowsMinimize { pid, name, title, all, index, sort_position })
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+            let options = OperationOptions::from_matches(matches);
+            let wait_for = matches.get_one::<String>("wait-for").cloned();
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsMinimize { filter, all, index, sort_position, options, wait_for, format })
         } else if let Some(matches) = matches.subcommand_matches("windows/maximize") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
-            let sort_position = Self::parse_sort_position(matches);
-            Some(SubCommand::WindowsMaximize { pid, name, title, all, index, sort_position })
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+            let options = OperationOptions::from_matches(matches);
+            let wait_for = matches.get_one::<String>("wait-for").cloned();
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsMaximize { filter, all, index, sort_position, options, wait_for, format })
         } else if let Some(matches) = matches.subcommand_matches("windows/restore") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let index = matches.get_one::<String>("index").map(|s| s.to_string());
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+            let options = OperationOptions::from_matches(matches);
+            let wait_for = matches.get_one::<String>("wait-for").cloned();
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsRestore { filter, all, index, sort_position, options, wait_for, format })
+        } else if let Some(matches) = matches.subcommand_matches("windows/hide") {
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
-            let sort_position = Self::parse_sort_position(matches);
-            Some(SubCommand::WindowsRestore { pid, name, title, all, index, sort_position })
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+            let options = OperationOptions::from_matches(matches);
+            let wait_for = matches.get_one::<String>("wait-for").cloned();
+            let format = json_or_format(matches);
+            let yes = matches.get_flag("yes");
+            Some(SubCommand::WindowsHide { filter, all, index, sort_position, options, wait_for, format, yes })
+        } else if let Some(matches) = matches.subcommand_matches("windows/show") {
+            let filter = WindowFilter::from_matches(matches);
+            let all = matches.get_flag("all");
+            let index = matches.get_one::<String>("index").map(|s| s.to_string());
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+            let options = OperationOptions::from_matches(matches);
+            let wait_for = matches.get_one::<String>("wait-for").cloned();
+            let format = json_or_format(matches);
+            Some(SubCommand::WindowsShow { filter, all, index, sort_position, options, wait_for, format })
         } else {
             None
         }
@@ -252,37 +315,69 @@ impl Feature for WindowOperationsFeature {
     
     fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
         match subcommand {
-            SubCommand::WindowsMinimize { pid, name, title, all, index, sort_position } => {
+            SubCommand::WindowsMinimize { filter, all, index, sort_position, options, wait_for, format } => {
                 self.handle_window_operation(
-                    pid.clone(),
-                    name.clone(), 
-                    title.clone(),
+                    filter,
                     *all,
                     index.clone(),
                     WindowOperation::Minimize,
                     *sort_position,
+                    *options,
+                    wait_for.as_deref(),
+                    format.clone(),
+                    false,
                 )
             }
-            SubCommand::WindowsMaximize { pid, name, title, all, index, sort_position } => {
+            SubCommand::WindowsMaximize { filter, all, index, sort_position, options, wait_for, format } => {
                 self.handle_window_operation(
-                    pid.clone(),
-                    name.clone(), 
-                    title.clone(),
+                    filter,
                     *all,
                     index.clone(),
                     WindowOperation::Maximize,
                     *sort_position,
+                    *options,
+                    wait_for.as_deref(),
+                    format.clone(),
+                    false,
                 )
             }
-            SubCommand::WindowsRestore { pid, name, title, all, index, sort_position } => {
+            SubCommand::WindowsRestore { filter, all, index, sort_position, options, wait_for, format } => {
                 self.handle_window_operation(
-                    pid.clone(),
-                    name.clone(), 
-                    title.clone(),
+                    filter,
                     *all,
                     index.clone(),
                     WindowOperation::Restore,
                     *sort_position,
+                    *options,
+                    wait_for.as_deref(),
+                    format.clone(),
+                    false,
+                )
+            }
+            SubCommand::WindowsHide { filter, all, index, sort_position, options, wait_for, format, yes } => {
+                self.handle_window_operation(
+                    filter,
+                    *all,
+                    index.clone(),
+                    WindowOperation::Hide,
+                    *sort_position,
+                    *options,
+                    wait_for.as_deref(),
+                    format.clone(),
+                    *yes,
+                )
+            }
+            SubCommand::WindowsShow { filter, all, index, sort_position, options, wait_for, format } => {
+                self.handle_window_operation(
+                    filter,
+                    *all,
+                    index.clone(),
+                    WindowOperation::Show,
+                    *sort_position,
+                    *options,
+                    wait_for.as_deref(),
+                    format.clone(),
+                    false,
                 )
             }
             _ => Ok(()) // 不是本特性处理的命令，忽略
@@ -0,0 +1,109 @@
+// src/features/windows_diff.rs
+use clap::{Arg, Command};
+use crate::cli::{SubCommand, json_or_format};
+use super::feature_trait::Feature;
+use crate::diff::{diff_windows, load_snapshot, print_changes};
+use crate::output::{OutputFormat, outln, to_json};
+use crate::error::AppResult;
+
+/// 窗口快照对比特性
+pub struct WindowsDiffFeature;
+
+impl WindowsDiffFeature {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 构建子命令
+    fn build_command(&self) -> Command {
+        Command::new("windows/diff")
+            .about("Compare two window snapshots saved with `windows/get --format json`")
+            .arg(
+                Arg::new("before")
+                    .long("before")
+                    .value_name("FILE")
+                    .required(true)
+                    .help("Snapshot taken before the change")
+            )
+            .arg(
+                Arg::new("after")
+                    .long("after")
+                    .value_name("FILE")
+                    .required(true)
+                    .help("Snapshot taken after the change")
+            )
+            .arg(
+                Arg::new("format")
+                    .short('f')
+                    .long("format")
+                    .value_name("FORMAT")
+                    .value_parser(clap::value_parser!(OutputFormat))
+                    .default_value("auto")
+                    .help("Output format (auto: table on a terminal, json when piped)")
+            )
+    }
+
+    /// 处理 windows/diff 命令
+    fn handle_windows_diff(&self, before: &str, after: &str, format: OutputFormat) -> AppResult<()> {
+        let before = load_snapshot(before)?;
+        let after = load_snapshot(after)?;
+        let changes = diff_windows(&before, &after);
+
+        match format {
+            OutputFormat::Json => outln!("{}", to_json(&changes)?),
+            OutputFormat::Yaml => outln!("{}", serde_yaml::to_string(&changes)?),
+            _ => {
+                if changes.is_empty() {
+                    outln!("No window changes");
+                } else {
+                    outln!("Found {} window change(s):", changes.len());
+                    print_changes(&changes)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Feature for WindowsDiffFeature {
+    fn name(&self) -> &'static str {
+        "windows_diff"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compare saved window snapshots"
+    }
+
+    fn build_cli(&self, command: Command) -> Command {
+        command.subcommand(self.build_command())
+    }
+
+    fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
+        if let Some(matches) = matches.subcommand_matches("windows/diff") {
+            let before = matches.get_one::<String>("before").cloned().unwrap_or_default();
+            let after = matches.get_one::<String>("after").cloned().unwrap_or_default();
+            let format = json_or_format(matches);
+
+            Some(SubCommand::WindowsDiff {
+                before,
+                after,
+                format,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
+        if let SubCommand::WindowsDiff { before, after, format } = subcommand {
+            self.handle_windows_diff(before, after, format.clone())
+        } else {
+            Ok(()) // 不是本特性处理的命令，忽略
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        // 只比较快照文件，不依赖平台窗口 API
+        true
+    }
+}
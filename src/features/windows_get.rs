@@ -1,17 +1,35 @@
 // src/features/windows_get.rs
 use clap::{Arg, Command};
-use crate::cli::SubCommand;
+use crate::cli::{SubCommand, json_or_format};
 use super::feature_trait::Feature;
-use crate::platform::get_all_windows_with_size;
-use crate::process::get_processes;
-use crate::output::{OutputFormat, display_windows};
-use crate::sorting::{SortOrder, PositionSort, apply_window_sorting};
+use crate::platform::{get_all_windows_with_size, get_windows_by_pid, get_windows_by_thread, get_window_at_cursor};
+use crate::output::{OutputFormat, EmptyAs, to_json, display_windows, display_window_summary, display_monitor_counts, outln, json_errors, print_error};
+use crate::sorting::{SortOrder, PositionSort, SortKey, apply_window_sorting, apply_key_sorting, apply_count_sorting, parse_sort_keys, DEFAULT_SORT_POSITION};
 use crate::error::{AppError, AppResult};
-use crate::utils::parse_indices;
+use crate::utils::{parse_indices, monitor_of, near_monitor_center, aspect_in_range, count_windows_per_monitor, retain_duplicates};
+use crate::filter::{WindowFilter, ProcessNameIndex, PidSet};
+use crate::process::{get_processes, ProcessFields};
+use crate::diff::{diff_windows, print_changes};
+use crate::types::{WindowInfo, WindowRect, WindowCount, RectMode, DuplicateKey};
+use crate::platform::{get_monitor_work_areas, probe_window_support, current_virtual_desktop, set_probe_responding};
 
 /// 窗口信息获取特性
 pub struct WindowsGetFeature;
 
+/// windows/get 的输出形式
+#[derive(Clone, Copy)]
+enum ResultView<'a> {
+    /// 逐个列出窗口；带显示器列表时按显示器分组，`nested_rect` 控制 JSON/YAML 中矩形的形式
+    Windows {
+        monitors: Option<&'a [WindowRect]>,
+        nested_rect: bool,
+    },
+    /// 按进程名汇总窗口数量，按给定方向排序
+    Summary(SortOrder),
+    /// 按中心点统计每个显示器上的窗口数量
+    MonitorCounts(&'a [WindowRect]),
+}
+
 impl WindowsGetFeature {
     pub fn new() -> Self {
         Self
@@ -26,7 +44,8 @@ impl WindowsGetFeature {
                     .short('p')
                     .long("pid")
                     .value_name("PID")
-                    .help("Filter by process ID")
+                    .value_parser(PidSet::parse)
+                    .help("Filter by process ID(s): single, list or range (e.g., 1234, 10,20, 100-200)")
             )
             .arg(
                 Arg::new("name")
@@ -42,6 +61,7 @@ impl WindowsGetFeature {
                     .value_name("TITLE")
                     .help("Filter by window title (contains)")
             )
+            .args(WindowFilter::exact_match_args())
             .arg(
                 Arg::new("all")
                     .short('a')
@@ -49,6 +69,13 @@ impl WindowsGetFeature {
                     .action(clap::ArgAction::SetTrue)
                     .help("Show all matching windows (ignore index filtering)")
             )
+            .arg(
+                Arg::new("one")
+                    .long("one")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with_all(["all", "index", "summary-only", "count-windows-per-monitor", "watch", "allow-empty", "empty-as"])
+                    .help("Expect exactly one matching window: exit 2 if none match, exit 3 if several match")
+            )
             .arg(
                 Arg::new("index")
                     .long("index")
@@ -63,8 +90,8 @@ impl WindowsGetFeature {
                     .long("format")
                     .value_name("FORMAT")
                     .value_parser(clap::value_parser!(OutputFormat))
-                    .default_value("table")
-                    .help("Output format")
+                    .default_value("auto")
+                    .help("Output format (auto: table on a terminal, json when piped)")
             )
             .arg(
                 Arg::new("sort-pid")
@@ -77,91 +104,452 @@ impl WindowsGetFeature {
                     .help("Sort by PID: 1 (ascending), -1 (descending), 0 (none)")
             )
             .arg(
-                Arg::new("sort-position")
+                Arg::new("sort_position")
                     .long("sort-position")
                     .value_name("X_ORDER|Y_ORDER")
                     .num_args(1)
                     .allow_hyphen_values(true)
-                    .default_value("0|0")
-                    .help("Sort by position: X_ORDER|Y_ORDER, e.g., 1|-1 for X ascending, Y descending")
+                    .default_value(DEFAULT_SORT_POSITION)
+                    .help("Sort by position: X_ORDER|Y_ORDER (e.g., 1|-1) or AXIS:ORDER pairs (e.g., y:asc,x:asc; first axis is primary)")
+            )
+            .arg(
+                Arg::new("sort")
+                    .long("sort")
+                    .value_name("KEY:ORDER,...")
+                    .value_parser(parse_sort_keys)
+                    .help("Sort by keys, first key is primary: pid, title, titlelen (e.g., titlelen:desc); applied after --sort-pid/--sort-position")
+            )
+            .arg(
+                Arg::new("allow-empty")
+                    .long("allow-empty")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Print an empty result and exit 0 instead of failing when no windows match (same as --empty-as array)")
+            )
+            .arg(
+                Arg::new("empty-as")
+                    .long("empty-as")
+                    .value_name("MODE")
+                    .value_parser(clap::value_parser!(EmptyAs))
+                    .default_value("error")
+                    .conflicts_with("allow-empty")
+                    .help("What to do when no windows match: error (exit 2), array (empty result) or null (JSON/YAML print null)")
+            )
+            .arg(
+                Arg::new("raw-rect")
+                    .long("raw-rect")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Report the literal GetWindowRect values (minimized windows show off-screen coordinates)")
+            )
+            .arg(
+                Arg::new("frame-bounds")
+                    .long("frame-bounds")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with("raw-rect")
+                    .help("Report the visible frame from DWM, excluding the invisible resize border/shadow that GetWindowRect includes")
+            )
+            .arg(
+                Arg::new("monitor-index")
+                    .long("monitor-index")
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(u32).range(1..))
+                    .help("Only show windows whose center lies on monitor N (1-based)")
+            )
+            .arg(
+                Arg::new("group-by")
+                    .long("group-by")
+                    .value_name("KEY")
+                    .value_parser(["monitor"])
+                    .help("Group the output under a header per monitor (table, simple and detailed formats)")
+            )
+            .arg(
+                Arg::new("desktop")
+                    .long("desktop")
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(u32).range(1..))
+                    .help("Only show windows on virtual desktop N (1-based); windows from all desktops are shown by default")
+            )
+            .arg(
+                Arg::new("current-desktop")
+                    .long("current-desktop")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with("desktop")
+                    .help("Only show windows on the current virtual desktop")
+            )
+            .arg(
+                Arg::new("min-aspect")
+                    .long("min-aspect")
+                    .value_name("RATIO")
+                    .value_parser(clap::value_parser!(f64))
+                    .help("Only show windows whose width/height ratio is at least RATIO (e.g., 1.5 for wide windows)")
+            )
+            .arg(
+                Arg::new("max-aspect")
+                    .long("max-aspect")
+                    .value_name("RATIO")
+                    .value_parser(clap::value_parser!(f64))
+                    .help("Only show windows whose width/height ratio is at most RATIO (e.g., 1 for portrait windows)")
+            )
+            .arg(
+                Arg::new("near-center")
+                    .long("near-center")
+                    .visible_alias("center-only")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Only show windows whose center is within --tolerance pixels of a monitor's center (e.g., centered dialogs)")
+            )
+            .arg(
+                Arg::new("tolerance")
+                    .long("tolerance")
+                    .value_name("PIXELS")
+                    .value_parser(clap::value_parser!(u32))
+                    .default_value("100")
+                    .requires("near-center")
+                    .help("Maximum distance in pixels from the monitor center for --near-center")
+            )
+            .arg(
+                Arg::new("only-responding")
+                    .long("only-responding")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Only show windows that respond to messages")
+            )
+            .arg(
+                Arg::new("only-hung")
+                    .long("only-hung")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with("only-responding")
+                    .help("Only show windows that are not responding (hung applications)")
+            )
+            .arg(
+                Arg::new("duplicates-only")
+                    .long("duplicates-only")
+                    .value_name("KEY")
+                    .num_args(0..=1)
+                    .default_missing_value("title")
+                    .value_parser(clap::value_parser!(DuplicateKey))
+                    .help("Only show windows whose title (title) or title and class (title-class) is shared by another matching window")
+            )
+            .arg(
+                Arg::new("nested-rect")
+                    .long("nested-rect")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with("summary-only")
+                    .help("In json/yaml output, nest position and size in a \"rect\" object instead of flat x/y/width/height fields")
+            )
+            .arg(
+                Arg::new("flatten")
+                    .long("flatten")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Guarantee one flat object per window (scalar fields only) for single-level consumers; overrides --nested-rect")
+            )
+            .arg(
+                Arg::new("summary-only")
+                    .long("summary-only")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with_all(["group-by", "diff"])
+                    .help("Print the number of matching windows per process name instead of each window")
+            )
+            .arg(
+                Arg::new("count-windows-per-monitor")
+                    .long("count-windows-per-monitor")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with_all(["summary-only", "group-by", "diff", "nested-rect"])
+                    .help("Print each monitor's bounds and the number of matching windows whose center lies on it")
+            )
+            .arg(
+                Arg::new("sort-count")
+                    .long("sort-count")
+                    .value_name("ORDER")
+                    .num_args(1)
+                    .allow_hyphen_values(true)
+                    .value_parser(["1", "-1", "0"])
+                    .default_value("-1")
+                    .help("With --summary-only, sort by window count: 1 (ascending), -1 (descending), 0 (first seen)")
+            )
+            .arg(
+                Arg::new("watch")
+                    .long("watch")
+                    .value_name("SECONDS")
+                    .num_args(0..=1)
+                    .default_missing_value("2")
+                    .value_parser(clap::value_parser!(u64).range(1..))
+                    .help("Refresh the window list every SECONDS (default 2) until interrupted")
+            )
+            .arg(
+                Arg::new("diff")
+                    .long("diff")
+                    .action(clap::ArgAction::SetTrue)
+                    .requires("watch")
+                    .help("With --watch, print only windows that appeared, disappeared, moved or resized")
             )
-    }
-    
-    /// 统一的字段提取函数
-    fn extract_filter_args(matches: &clap::ArgMatches) -> (Option<String>, Option<String>, Option<String>) {
-        let pid = matches.get_one::<String>("pid").map(|s| s.to_string());
-        let name = matches.get_one::<String>("name").map(|s| s.to_string());
-        let title = matches.get_one::<String>("title").map(|s| s.to_string());
-        (pid, name, title)
     }
     
     /// 处理 windows/get 命令
+    #[allow(clippy::too_many_arguments)]
     fn handle_windows_get(
         &self,
-        pid_filter: Option<String>,
-        name_filter: Option<String>,
-        title_filter: Option<String>,
+        filter: &WindowFilter,
         all: bool,
         index: Option<String>,
         format: OutputFormat,
         sort_pid: SortOrder,
         sort_position: PositionSort,
+        sort_keys: &[SortKey],
+        empty_as: EmptyAs,
+        watch: Option<u64>,
+        diff: bool,
+        rect_mode: RectMode,
+        monitor_index: Option<u32>,
+        group_by_monitor: bool,
+        summary: Option<SortOrder>,
+        count_per_monitor: bool,
+        desktop: Option<u32>,
+        current_desktop: bool,
+        nested_rect: bool,
+        aspect_range: (Option<f64>, Option<f64>),
+        responding: Option<bool>,
+        near_center: Option<u32>,
+        duplicates: Option<DuplicateKey>,
+        one: bool,
     ) -> AppResult<()> {
-        // 使用平台抽象层获取所有窗口及其尺寸信息
-        let windows = get_all_windows_with_size();
-        
-        // 获取进程名称用于显示
-        let processes = get_processes();
-        let process_names: Vec<(u32, String)> = processes
-            .iter()
-            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-            .collect();
-        
-        // 过滤窗口
-        let mut filtered_windows: Vec<crate::types::WindowInfo> = windows
-            .iter()
-            .filter(|window| {
-                // PID filter
-                if let Some(pid) = &pid_filter {
-                    if window.pid.to_string() != *pid {
-                        return false;
-                    }
-                }
+        // 没有可用的窗口系统时直接报告不支持，而不是输出空列表
+        probe_window_support()?;
 
-                // Name filter
-                if let Some(name) = &name_filter {
-                    let process_name = process_names
-                        .iter()
-                        .find(|(process_pid, _)| *process_pid == window.pid)
-                        .map(|(_, name)| name.to_lowercase())
-                        .unwrap_or_default();
-                    
-                    if !process_name.contains(&name.to_lowercase()) {
-                        return false;
-                    }
+        // --current-desktop 在开始时解析为桌面序号（监视模式下不跟随桌面切换）
+        let desktop_filter = if current_desktop { Some(current_virtual_desktop()?) } else { desktop };
+
+        if group_by_monitor && matches!(format, OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv | OutputFormat::Tsv) {
+            return Err(AppError::invalid_parameter(
+                "--group-by monitor is only supported with table, simple or detailed output"
+            ));
+        }
+
+        if let (Some(min), Some(max)) = aspect_range {
+            if min > max {
+                return Err(AppError::invalid_parameter(format!(
+                    "--min-aspect ({}) must not be greater than --max-aspect ({})", min, max
+                )));
+            }
+        }
+
+        if nested_rect && !matches!(format, OutputFormat::Json | OutputFormat::Yaml) {
+            return Err(AppError::invalid_parameter("--nested-rect is only supported with json or yaml output"));
+        }
+
+        // 仅在需要按显示器过滤、分组或统计时枚举显示器
+        let monitors = if monitor_index.is_some() || group_by_monitor || count_per_monitor || near_center.is_some() {
+            let monitors = get_monitor_work_areas()?;
+            if let Some(n) = monitor_index {
+                if n as usize > monitors.len() {
+                    return Err(AppError::invalid_parameter(format!(
+                        "Monitor index {} is out of range (found {} monitor(s))", n, monitors.len()
+                    )));
                 }
+            }
+            monitors
+        } else {
+            Vec::new()
+        };
+        let monitor_filter = monitor_index.map(|n| (monitors.as_slice(), n as usize - 1));
+        let center_filter = near_center.map(|tolerance| (monitors.as_slice(), tolerance));
+        let group_monitors = if group_by_monitor { Some(monitors.as_slice()) } else { None };
+        let view = match summary {
+            Some(order) => ResultView::Summary(order),
+            None if count_per_monitor => ResultView::MonitorCounts(&monitors),
+            None => ResultView::Windows { monitors: group_monitors, nested_rect },
+        };
+
+        // 响应探测是逐个窗口的跨进程调用，只在按响应状态过滤或输出中包含该字段时进行；
+        // 只作用于过滤用的窗口枚举，构建进程索引时的窗口枚举从不探测（见 `get_window_titles`）
+        set_probe_responding(responding.is_some() || Self::shows_responding(&view, &format));
+
+        if let Some(interval) = watch {
+            return Self::watch_windows(filter, all, index, format, sort_pid, sort_position, sort_keys, rect_mode, monitor_filter, center_filter, desktop_filter, aspect_range, responding, duplicates, view, interval, diff);
+        }
+
+        // 获取进程名称用于过滤和显示
+        let process_names = ProcessNameIndex::from_processes(&get_processes(Self::process_fields(filter, &view, &format)));
+
+        let filtered_windows = Self::collect_windows(filter, all, index.as_deref(), sort_pid, sort_position, sort_keys, rect_mode, monitor_filter, center_filter, desktop_filter, aspect_range, responding, duplicates, &process_names)?;
+
+        // --one：断言恰好匹配一个窗口
+        if one {
+            ensure_single(&filtered_windows)?;
+        }
+
+        // 如果没有匹配的窗口（显示器统计仍然输出每个显示器的 0）
+        if filtered_windows.is_empty() && !count_per_monitor {
+            return Self::handle_empty_result(&process_names, format, empty_as);
+        }
 
-                // Title filter
-                if let Some(title) = &title_filter {
-                    if !window.title.to_lowercase().contains(&title.to_lowercase()) {
-                        return false;
+        // 显示结果
+        Self::display_result(&filtered_windows, &process_names, format, view)
+    }
+
+    /// 按进程名汇总窗口数量，按首次出现的顺序排列
+    fn summarize(windows: &[WindowInfo], process_names: &ProcessNameIndex) -> Vec<WindowCount> {
+        let mut summary: Vec<WindowCount> = Vec::new();
+        for window in windows {
+            let name = process_names.get(window.pid).unwrap_or("Unknown");
+            match summary.iter_mut().find(|entry| entry.name == name) {
+                Some(entry) => entry.count += 1,
+                None => summary.push(WindowCount { name: name.to_string(), count: 1 }),
+            }
+        }
+        summary
+    }
+
+    /// 输出结果：窗口列表（可按显示器分组，每组前输出标题行）或按进程汇总的数量
+    fn display_result(
+        windows: &[WindowInfo],
+        process_names: &ProcessNameIndex,
+        format: OutputFormat,
+        view: ResultView,
+    ) -> AppResult<()> {
+        let monitors = match view {
+            ResultView::Summary(order) => {
+                let mut summary = Self::summarize(windows, process_names);
+                apply_count_sorting(&mut summary, order);
+                return display_window_summary(&summary, format);
+            }
+            ResultView::MonitorCounts(monitors) => {
+                return display_monitor_counts(&count_windows_per_monitor(windows, monitors), format);
+            }
+            ResultView::Windows { monitors: None, nested_rect } => {
+                return display_windows(windows, process_names, format, nested_rect);
+            }
+            ResultView::Windows { monitors: Some(monitors), .. } => monitors,
+        };
+
+        for (i, monitor) in monitors.iter().enumerate() {
+            let group: Vec<WindowInfo> = windows
+                .iter()
+                .filter(|w| monitor_of(&w.rect, monitors) == Some(i))
+                .cloned()
+                .collect();
+            if group.is_empty() {
+                continue;
+            }
+            outln!("== Monitor {} ({}) ==", i + 1, monitor);
+            display_windows(&group, process_names, format.clone(), false)?;
+            outln!();
+        }
+        Ok(())
+    }
+
+    /// 监视模式：每隔 `interval` 秒刷新一次窗口列表，直到被中断
+    ///
+    /// 指定 `diff` 时，首轮输出完整列表，之后每轮只输出与上一轮相比发生变化的窗口。
+    #[allow(clippy::too_many_arguments)]
+    fn watch_windows(
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<String>,
+        format: OutputFormat,
+        sort_pid: SortOrder,
+        sort_position: PositionSort,
+        sort_keys: &[SortKey],
+        rect_mode: RectMode,
+        monitor_filter: Option<(&[WindowRect], usize)>,
+        center_filter: Option<(&[WindowRect], u32)>,
+        desktop_filter: Option<u32>,
+        aspect_range: (Option<f64>, Option<f64>),
+        responding: Option<bool>,
+        duplicates: Option<DuplicateKey>,
+        view: ResultView,
+        interval: u64,
+        diff: bool,
+    ) -> AppResult<()> {
+        let mut previous: Option<Vec<WindowInfo>> = None;
+
+        loop {
+            let process_names = ProcessNameIndex::from_processes(&get_processes(Self::process_fields(filter, &view, &format)));
+            let windows = Self::collect_windows(filter, all, index.as_deref(), sort_pid, sort_position, sort_keys, rect_mode, monitor_filter, center_filter, desktop_filter, aspect_range, responding, duplicates, &process_names)?;
+
+            match (&previous, diff) {
+                (Some(previous), true) => {
+                    let changes = diff_windows(previous, &windows);
+                    if !changes.is_empty() {
+                        print_changes(&changes)?;
                     }
                 }
+                _ => Self::display_result(&windows, &process_names, format.clone(), view)?,
+            }
 
-                true
+            previous = Some(windows);
+            std::thread::sleep(std::time::Duration::from_secs(interval));
+        }
+    }
+
+    /// 进程索引需要的字段：逐个列出窗口的 detailed 输出还会显示所属进程承载的服务
+    fn process_fields(filter: &WindowFilter, view: &ResultView, format: &OutputFormat) -> ProcessFields {
+        let mut fields = filter.process_fields();
+        fields.services |= matches!(view, ResultView::Windows { .. }) && matches!(format, OutputFormat::Detailed);
+        fields
+    }
+
+    /// 输出中是否包含窗口的响应状态（逐个列出窗口的 json/yaml/csv/tsv/detailed 输出）
+    fn shows_responding(view: &ResultView, format: &OutputFormat) -> bool {
+        matches!(view, ResultView::Windows { .. }) && format.shows_all_fields()
+    }
+
+    /// 获取、过滤、排序并按索引选择窗口
+    #[allow(clippy::too_many_arguments)]
+    fn collect_windows(
+        filter: &WindowFilter,
+        all: bool,
+        index: Option<&str>,
+        sort_pid: SortOrder,
+        sort_position: PositionSort,
+        sort_keys: &[SortKey],
+        rect_mode: RectMode,
+        monitor_filter: Option<(&[WindowRect], usize)>,
+        center_filter: Option<(&[WindowRect], u32)>,
+        desktop_filter: Option<u32>,
+        aspect_range: (Option<f64>, Option<f64>),
+        responding: Option<bool>,
+        duplicates: Option<DuplicateKey>,
+        process_names: &ProcessNameIndex,
+    ) -> AppResult<Vec<WindowInfo>> {
+        // 使用平台抽象层获取窗口及其尺寸、所在虚拟桌面信息（单一 PID 时只枚举该进程的窗口）
+        let windows = if filter.at_cursor {
+            vec![get_window_at_cursor(rect_mode, true)?]
+        } else if let Some(tid) = filter.tid {
+            get_windows_by_thread(tid, rect_mode, true)?
+        } else {
+            match filter.single_pid() {
+                Some(pid) => get_windows_by_pid(pid, rect_mode, true),
+                None => get_all_windows_with_size(rect_mode, true),
+            }
+        };
+
+        // 过滤窗口（--at-cursor 选中的窗口不再匹配 PID、名称和标题）
+        let mut filtered_windows: Vec<WindowInfo> = windows
+            .into_iter()
+            .filter(|window| filter.at_cursor || filter.matches(window, process_names))
+            .filter(|window| match monitor_filter {
+                Some((monitors, n)) => monitor_of(&window.rect, monitors) == Some(n),
+                None => true,
             })
-            .cloned()
+            .filter(|window| center_filter.is_none_or(|(monitors, tolerance)| near_monitor_center(&window.rect, monitors, tolerance)))
+            .filter(|window| desktop_filter.is_none_or(|n| window.desktop == Some(n)))
+            .filter(|window| aspect_in_range(&window.rect, aspect_range.0, aspect_range.1))
+            .filter(|window| responding.is_none_or(|r| window.responding == Some(r)))
             .collect();
 
+        // --duplicates-only 在其他过滤之后分组，只保留重复的窗口
+        if let Some(key) = duplicates {
+            retain_duplicates(&mut filtered_windows, key);
+        }
+
         if filtered_windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+            return Ok(filtered_windows);
         }
 
         // 应用排序
         apply_window_sorting(&mut filtered_windows, &sort_pid, &sort_position);
+        apply_key_sorting(&mut filtered_windows, sort_keys, sort_position.reverse);
 
         // 解析索引
-        let indices = parse_indices(&index.unwrap_or_default(), filtered_windows.len());
+        let indices = parse_indices(index.unwrap_or_default(), filtered_windows.len());
 
         // 应用索引过滤（除非指定了 --all）
         if !all && !indices.is_empty() {
@@ -174,13 +562,44 @@ impl WindowsGetFeature {
             filtered_windows = indexed_windows;
         }
 
-        // 如果没有匹配的窗口
-        if filtered_windows.is_empty() {
-            return Err(AppError::NoMatchingWindows);
+        Ok(filtered_windows)
+    }
+
+    /// 处理无匹配窗口的情况
+    ///
+    /// 按 `--empty-as` 处理：`array`（或 `--allow-empty`）输出空结果（JSON/YAML 为 `[]`，表格/CSV 只有表头），
+    /// `null` 在 JSON/YAML 中输出 `null`，两者都正常退出；默认 `error` 返回 `NoMatchingWindows`（退出码 2）。
+    /// 空结果写到 stdout；`--error-format json` 时另外向 stderr 写入 `no_matching_windows` 对象（exit_code 为 0），
+    /// 脚本可以始终解析 stdout，并从 stderr 区分“没有结果”。
+    fn handle_empty_result(
+        process_names: &ProcessNameIndex,
+        format: OutputFormat,
+        empty_as: EmptyAs,
+    ) -> AppResult<()> {
+        if empty_as != EmptyAs::Error && json_errors() {
+            print_error(&AppError::NoMatchingWindows, 0);
         }
+        match (empty_as, &format) {
+            (EmptyAs::Error, _) => Err(AppError::NoMatchingWindows),
+            (EmptyAs::Null, OutputFormat::Json) => {
+                outln!("{}", to_json(&())?);
+                Ok(())
+            }
+            (EmptyAs::Null, OutputFormat::Yaml) => {
+                outln!("{}", serde_yaml::to_string(&())?);
+                Ok(())
+            }
+            _ => display_windows(&[], process_names, format, false),
+        }
+    }
+}
 
-        // 显示结果
-        display_windows(&filtered_windows, &process_names, format)
+/// `--one` 的约定：没有匹配时返回 `NoMatchingWindows`（退出码 2），多于一个时返回 `MultipleWindows`（退出码 3）
+fn ensure_single(windows: &[WindowInfo]) -> AppResult<()> {
+    match windows.len() {
+        0 => Err(AppError::NoMatchingWindows),
+        1 => Ok(()),
+        n => Err(AppError::ambiguous_window(n)),
     }
 }
 
@@ -199,10 +618,10 @@ impl Feature for WindowsGetFeature {
     
     fn parse_cli(&self, matches: &clap::ArgMatches) -> Option<SubCommand> {
         if let Some(matches) = matches.subcommand_matches("windows/get") {
-            let (pid, name, title) = Self::extract_filter_args(matches);
+            let filter = WindowFilter::from_matches(matches);
             let all = matches.get_flag("all");
             let index = matches.get_one::<String>("index").map(|s| s.to_string());
-            let format = matches.get_one::<OutputFormat>("format").unwrap().clone();
+            let format = json_or_format(matches);
             
             // 手动解析排序参数
             let sort_pid = match matches.get_one::<String>("sort-pid").map(|s| s.as_str()) {
@@ -212,28 +631,86 @@ impl Feature for WindowsGetFeature {
                 Some(_) => SortOrder::None, // 不应该发生，因为有 value_parser
             };
             
-            let sort_position = match matches.get_one::<String>("sort-position").map(|s| s.as_str()) {
-                Some(s) => {
-                    match s.parse() {
-                        Ok(pos) => pos,
-                        Err(_) => {
-                            eprintln!("Warning: Invalid position sort format '{}', using default", s);
-                            PositionSort::default()
-                        }
-                    }
-                }
-                None => PositionSort::default(),
+            let sort_position = PositionSort::from_matches(matches, DEFAULT_SORT_POSITION);
+
+            // --no-sort 保持窗口的枚举顺序（Z 序），忽略所有排序参数
+            let no_sort = matches.get_flag("no_sort");
+            let sort_pid = if no_sort { SortOrder::None } else { sort_pid };
+            let sort_keys = match matches.get_one::<Vec<SortKey>>("sort") {
+                Some(keys) if !no_sort => keys.clone(),
+                _ => Vec::new(),
+            };
+            
+            let empty_as = if matches.get_flag("allow-empty") {
+                EmptyAs::Array
+            } else {
+                matches.get_one::<EmptyAs>("empty-as").copied().unwrap_or_default()
+            };
+            let watch = matches.get_one::<u64>("watch").copied();
+            let diff = matches.get_flag("diff");
+            let rect_mode = if matches.get_flag("raw-rect") {
+                RectMode::Raw
+            } else if matches.get_flag("frame-bounds") {
+                RectMode::Frame
+            } else {
+                RectMode::Restored
+            };
+            let monitor_index = matches.get_one::<u32>("monitor-index").copied();
+            let group_by_monitor = matches.get_one::<String>("group-by").is_some_and(|key| key == "monitor");
+            let summary_only = matches.get_flag("summary-only");
+            let count_per_monitor = matches.get_flag("count-windows-per-monitor");
+            let desktop = matches.get_one::<u32>("desktop").copied();
+            let current_desktop = matches.get_flag("current-desktop");
+            // --flatten 始终输出扁平记录，撤销 --nested-rect（例如来自别名或脚本中的默认参数）
+            let nested_rect = matches.get_flag("nested-rect") && !matches.get_flag("flatten");
+            let min_aspect = matches.get_one::<f64>("min-aspect").copied();
+            let max_aspect = matches.get_one::<f64>("max-aspect").copied();
+            let near_center = matches
+                .get_flag("near-center")
+                .then(|| matches.get_one::<u32>("tolerance").copied().unwrap_or(100));
+            let duplicates = matches.get_one::<DuplicateKey>("duplicates-only").copied();
+            let one = matches.get_flag("one");
+            let responding = if matches.get_flag("only-responding") {
+                Some(true)
+            } else if matches.get_flag("only-hung") {
+                Some(false)
+            } else {
+                None
+            };
+            // --reverse 对汇总结果同样生效
+            let sort_count = match (matches.get_one::<String>("sort-count").map(|s| s.as_str()), sort_position.reverse) {
+                _ if no_sort => SortOrder::None,
+                (Some("1"), false) | (Some("-1"), true) => SortOrder::Ascending,
+                (Some("-1"), false) | (Some("1"), true) => SortOrder::Descending,
+                _ => SortOrder::None,
             };
             
             Some(SubCommand::WindowsGet { 
-                pid, 
-                name, 
-                title, 
+                filter,
                 all,
                 index,
                 format,
                 sort_pid,
                 sort_position,
+                sort_keys,
+                empty_as,
+                watch,
+                diff,
+                rect_mode,
+                monitor_index,
+                group_by_monitor,
+                summary_only,
+                sort_count,
+                count_per_monitor,
+                desktop,
+                current_desktop,
+                nested_rect,
+                min_aspect,
+                max_aspect,
+                responding,
+                near_center,
+                duplicates,
+                one,
             })
         } else {
             None
@@ -241,16 +718,31 @@ impl Feature for WindowsGetFeature {
     }
     
     fn execute(&self, subcommand: &SubCommand) -> AppResult<()> {
-        if let SubCommand::WindowsGet { pid, name, title, all, index, format, sort_pid, sort_position } = subcommand {
+        if let SubCommand::WindowsGet { filter, all, index, format, sort_pid, sort_position, sort_keys, empty_as, watch, diff, rect_mode, monitor_index, group_by_monitor, summary_only, sort_count, count_per_monitor, desktop, current_desktop, nested_rect, min_aspect, max_aspect, responding, near_center, duplicates, one } = subcommand {
             self.handle_windows_get(
-                pid.clone(),
-                name.clone(), 
-                title.clone(),
+                filter,
                 *all,
                 index.clone(),
                 format.clone(),
                 *sort_pid,
                 *sort_position,
+                sort_keys,
+                *empty_as,
+                *watch,
+                *diff,
+                *rect_mode,
+                *monitor_index,
+                *group_by_monitor,
+                summary_only.then_some(*sort_count),
+                *count_per_monitor,
+                *desktop,
+                *current_desktop,
+                *nested_rect,
+                (*min_aspect, *max_aspect),
+                *responding,
+                *near_center,
+                *duplicates,
+                *one,
             )
         } else {
             Ok(()) // 不是本特性处理的命令，忽略
@@ -258,7 +750,37 @@ impl Feature for WindowsGetFeature {
     }
     
     fn is_supported(&self) -> bool {
-        // windows/get 功能在所有平台都支持，因为即使没有窗口操作功能，也能获取基本信息
+        // windows/get 在所有平台都注册，运行时能力由 check_runtime 判断
         true
     }
-}
\ No newline at end of file
+
+    fn check_runtime(&self) -> AppResult<()> {
+        probe_window_support()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shows_responding() {
+        let windows = ResultView::Windows { monitors: None, nested_rect: false };
+        assert!(WindowsGetFeature::shows_responding(&windows, &OutputFormat::Json));
+        assert!(WindowsGetFeature::shows_responding(&windows, &OutputFormat::Detailed));
+        assert!(!WindowsGetFeature::shows_responding(&windows, &OutputFormat::Table));
+        assert!(!WindowsGetFeature::shows_responding(&ResultView::Summary(SortOrder::None), &OutputFormat::Json));
+    }
+
+    #[test]
+    fn test_ensure_single() {
+        let window = WindowInfo::for_test(1, "Editor", WindowRect::new(0, 0, 800, 600));
+
+        assert!(matches!(ensure_single(&[]), Err(AppError::NoMatchingWindows)));
+        assert!(ensure_single(std::slice::from_ref(&window)).is_ok());
+        let err = ensure_single(&[window.clone(), window]).unwrap_err();
+        assert!(matches!(err, AppError::MultipleWindows { count: 2, .. }));
+        assert_eq!(err.exit_code(), 3);
+        assert!(!err.to_string().contains("--all"));
+    }
+}
@@ -0,0 +1,792 @@
+// src/filter.rs
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Deserializer};
+use crate::types::{ProcessInfo, WindowInfo};
+use crate::error::{AppError, AppResult};
+use crate::config::{EnvDefaults, FilterProfile};
+use crate::process::ProcessFields;
+
+/// 进程名索引：PID -> 进程名（及承载的服务名、进程标题），用于 O(1) 查找窗口所属进程的名称
+#[derive(Debug, Clone, Default)]
+pub struct ProcessNameIndex {
+    names: HashMap<u32, String>,
+    services: HashMap<u32, String>,
+    titles: HashMap<u32, String>,
+}
+
+impl ProcessNameIndex {
+    /// 从进程列表构建索引
+    pub fn from_processes(processes: &[ProcessInfo]) -> Self {
+        let names = processes
+            .iter()
+            .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
+            .collect();
+        let services = processes
+            .iter()
+            .filter_map(|p| Some((p.pid.parse().unwrap_or(0), p.service.clone()?)))
+            .collect();
+        let titles = processes
+            .iter()
+            .map(|p| (p.pid.parse().unwrap_or(0), p.title.clone()))
+            .collect();
+        Self { names, services, titles }
+    }
+
+    /// 为窗口过滤获取进程信息，只查询 `filter` 用到的字段
+    pub fn collect(filter: &WindowFilter) -> Self {
+        Self::from_processes(&crate::process::get_processes(filter.process_fields()))
+    }
+
+    /// 获取 PID 对应的进程名
+    pub fn get(&self, pid: u32) -> Option<&str> {
+        self.names.get(&pid).map(|name| name.as_str())
+    }
+
+    /// 所有进程的 PID 和进程名
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names.iter().map(|(pid, name)| (*pid, name.as_str()))
+    }
+
+    /// 获取 PID 对应进程承载的服务名
+    pub fn service(&self, pid: u32) -> Option<&str> {
+        self.services.get(&pid).map(|service| service.as_str())
+    }
+
+    /// 获取 PID 对应进程的标题（进程列表中的 Title：窗口标题或命令行）
+    pub fn title(&self, pid: u32) -> Option<&str> {
+        self.titles.get(&pid).map(|title| title.as_str())
+    }
+}
+
+/// 窗口命令中 `--title` 匹配的标题（`--title-source`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TitleSource {
+    /// 窗口自身的标题
+    #[default]
+    Window,
+    /// 所属进程的标题（与进程列表相同：窗口标题或命令行）
+    Process,
+    /// 任一标题匹配即可
+    Both,
+}
+
+/// 文本匹配方式（均不区分大小写）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// 包含匹配
+    #[default]
+    Contains,
+    /// 完全相等
+    Exact,
+}
+
+impl MatchMode {
+    fn matches(self, text: &str, pattern: &str) -> bool {
+        match self {
+            MatchMode::Contains => text.to_lowercase().contains(&pattern.to_lowercase()),
+            MatchMode::Exact => text.to_lowercase() == pattern.to_lowercase(),
+        }
+    }
+}
+
+/// PID 过滤条件：逗号分隔的 PID 和闭区间，如 "10,20,100-200"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PidSet {
+    ranges: Vec<(u32, u32)>,
+}
+
+impl PidSet {
+    /// 解析 `--pid` 参数值，可直接用作 clap 的 value_parser
+    pub fn parse(pid_str: &str) -> AppResult<Self> {
+        let ranges = pid_str
+            .split(',')
+            .map(|part| {
+                let part = part.trim();
+                let parse = |s: &str| {
+                    s.trim().parse::<u32>()
+                        .map_err(|_| AppError::parse(format!("Invalid PID: '{}'", s.trim())))
+                };
+                match part.split_once('-') {
+                    Some((lo, hi)) => {
+                        let (lo, hi) = (parse(lo)?, parse(hi)?);
+                        if lo > hi {
+                            return Err(AppError::invalid_parameter(format!(
+                                "Invalid PID range {}: start must not exceed end", part
+                            )));
+                        }
+                        Ok((lo, hi))
+                    }
+                    None => parse(part).map(|pid| (pid, pid)),
+                }
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+        Ok(Self { ranges })
+    }
+
+    /// 只包含单个 PID 时返回该 PID（用于只枚举该进程的窗口）
+    pub fn single(&self) -> Option<u32> {
+        match self.ranges.as_slice() {
+            [(lo, hi)] if lo == hi => Some(*lo),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, pid: u32) -> bool {
+        self.ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&pid))
+    }
+}
+
+impl std::fmt::Display for PidSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|&(lo, hi)| if lo == hi { lo.to_string() } else { format!("{}-{}", lo, hi) })
+            .collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+impl From<u32> for PidSet {
+    fn from(pid: u32) -> Self {
+        Self { ranges: vec![(pid, pid)] }
+    }
+}
+
+/// 配置文件中既可写单个数字，也可写与 `--pid` 相同格式的字符串
+impl<'de> Deserialize<'de> for PidSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Pid(u32),
+            Spec(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Pid(pid) => Ok(pid.into()),
+            Raw::Spec(spec) => PidSet::parse(&spec).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// 置顶窗口的扩展样式位（`WS_EX_TOPMOST`）
+pub const EX_STYLE_TOPMOST: u32 = 0x0000_0008;
+
+/// 扩展窗口样式名称及对应的 `WS_EX_*` 值（`--ex-style`）
+const EX_STYLE_NAMES: &[(&str, u32)] = &[
+    ("DLGMODALFRAME", 0x0000_0001),
+    ("NOPARENTNOTIFY", 0x0000_0004),
+    ("TOPMOST", EX_STYLE_TOPMOST),
+    ("ACCEPTFILES", 0x0000_0010),
+    ("TRANSPARENT", 0x0000_0020),
+    ("MDICHILD", 0x0000_0040),
+    ("TOOLWINDOW", 0x0000_0080),
+    ("WINDOWEDGE", 0x0000_0100),
+    ("CLIENTEDGE", 0x0000_0200),
+    ("CONTEXTHELP", 0x0000_0400),
+    ("RIGHT", 0x0000_1000),
+    ("RTLREADING", 0x0000_2000),
+    ("LEFTSCROLLBAR", 0x0000_4000),
+    ("CONTROLPARENT", 0x0001_0000),
+    ("STATICEDGE", 0x0002_0000),
+    ("APPWINDOW", 0x0004_0000),
+    ("LAYERED", 0x0008_0000),
+    ("NOINHERITLAYOUT", 0x0010_0000),
+    ("NOREDIRECTIONBITMAP", 0x0020_0000),
+    ("LAYOUTRTL", 0x0040_0000),
+    ("COMPOSITED", 0x0200_0000),
+    ("NOACTIVATE", 0x0800_0000),
+];
+
+/// 解析 `--ex-style` 参数：以 `|` 或 `,` 分隔的样式名（不区分大小写，`WS_EX_` 前缀可省略），返回合并后的位掩码
+pub fn parse_ex_style(value: &str) -> AppResult<u32> {
+    value
+        .split(['|', ','])
+        .map(|name| {
+            let name = name.trim().to_uppercase();
+            let name = name.strip_prefix("WS_EX_").unwrap_or(&name);
+            EX_STYLE_NAMES
+                .iter()
+                .find(|(known, _)| *known == name)
+                .map(|&(_, bit)| bit)
+                .ok_or_else(|| AppError::invalid_parameter(format!(
+                    "Unknown extended style '{}' (expected one of: {})",
+                    name,
+                    EX_STYLE_NAMES.iter().map(|(known, _)| *known).collect::<Vec<_>>().join(", ")
+                )))
+        })
+        .try_fold(0, |mask, bit| bit.map(|bit| mask | bit))
+}
+
+/// `--from-json` 指定的目标窗口
+///
+/// HWND 不会保存到 `windows/get --format json` 的输出中，因此按保存时的 PID 和标题重新匹配。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowTargets(HashSet<(u32, String)>);
+
+impl WindowTargets {
+    /// 读取 `windows/get --format json` 保存的窗口列表，可直接用作 clap 的 value_parser
+    pub fn load(path: &str) -> AppResult<Self> {
+        Ok(Self::from_windows(&crate::diff::load_snapshot(path)?))
+    }
+
+    fn from_windows(windows: &[WindowInfo]) -> Self {
+        Self(windows.iter().map(|window| (window.pid, window.title.clone())).collect())
+    }
+
+    /// 窗口的 PID 和标题是否与保存的某个窗口完全一致
+    pub fn contains(&self, window: &WindowInfo) -> bool {
+        self.0.contains(&(window.pid, window.title.clone()))
+    }
+}
+
+/// 窗口/进程过滤条件，由所有特性共享
+///
+/// PID、名称、标题、服务和扩展样式条件之间默认为 AND 关系，`--match-any` 时为 OR 关系；
+/// 名称和标题默认为不区分大小写的包含匹配。
+#[derive(Debug, Clone, Default)]
+pub struct WindowFilter {
+    pub pid: Option<PidSet>,
+    pub name: Option<String>,
+    pub name_mode: MatchMode,
+    pub title: Option<String>,
+    pub title_mode: MatchMode,
+    /// 按进程承载的服务名过滤（不区分大小写的包含匹配，仅 Windows 上可用）
+    pub service: Option<String>,
+    /// 窗口的扩展样式必须包含的所有位（`--ex-style`），0 表示不过滤
+    pub ex_style: u32,
+    /// 枚举时包含不可见（隐藏）的窗口
+    pub include_hidden: bool,
+    /// 排除 pscan 自身的进程（--exclude-self）
+    pub exclude_self: bool,
+    /// 排除的进程 ID（--exclude-pid），在其他条件之后生效，不受 --match-any 影响
+    pub exclude_pid: Option<PidSet>,
+    /// 保留宽或高不为正的窗口（--include-zero-size）
+    pub include_zero_size: bool,
+    /// 只选择鼠标指针下的窗口，忽略其他条件（--at-cursor）
+    pub at_cursor: bool,
+    /// 满足任一条件即匹配（--match-any）；--exclude-self、--exclude-pid 和零尺寸检查不受影响
+    pub match_any: bool,
+    /// 窗口操作中每个进程只保留排序后的第一个窗口（--per-process）
+    pub per_process: bool,
+    /// 只枚举该线程创建的窗口（--tid）；作为枚举范围，不参与 --match-any
+    pub tid: Option<u32>,
+    /// 窗口命令中 `--title` 匹配窗口标题、进程标题或两者（--title-source）
+    pub title_source: TitleSource,
+    /// 只匹配 `--from-json` 文件中保存的窗口（不影响进程列表）；与 --match-any 无关，始终作为附加条件
+    pub targets: Option<WindowTargets>,
+    /// 按 UI Automation 名称过滤窗口（--uia-name，不区分大小写的包含匹配，不影响进程列表）
+    pub uia_name: Option<String>,
+    /// 按 UI Automation 的 AutomationId 过滤窗口（--automation-id，不区分大小写的精确匹配，不影响进程列表）
+    pub automation_id: Option<String>,
+}
+
+impl WindowFilter {
+    /// 精确匹配参数 `--name-exact`/`--title-exact`，分别与 `--name`/`--title` 互斥
+    pub fn exact_match_args() -> [clap::Arg; 2] {
+        [
+            clap::Arg::new("name_exact")
+                .long("name-exact")
+                .value_name("NAME")
+                .help("Filter by process name (exact, case-insensitive)")
+                .conflicts_with("name"),
+            clap::Arg::new("title_exact")
+                .long("title-exact")
+                .value_name("TITLE")
+                .help("Filter by window title (exact, case-insensitive)")
+                .conflicts_with("title"),
+        ]
+    }
+
+    /// 从命令行参数中解析过滤条件（需要 `pid`/`name`/`title` 及 `exact_match_args` 参数）
+    ///
+    /// 命令行未给出的 PID 和名称条件依次取自环境变量 `PSCAN_PID`/`PSCAN_NAME` 和 `--filter-profile` 指定的预设，
+    /// 标题条件只取自预设。
+    pub fn from_matches(matches: &clap::ArgMatches) -> Self {
+        let profile = matches.get_one::<FilterProfile>("filter_profile").cloned().unwrap_or_default();
+        let env = EnvDefaults::get();
+        let (name, name_mode) = Self::text_criterion(matches, "name", "name_exact")
+            .or_else(|| Self::criterion(env.name.clone(), None))
+            .or_else(|| Self::criterion(profile.name, profile.name_exact))
+            .unwrap_or_default();
+        let (title, title_mode) = Self::text_criterion(matches, "title", "title_exact")
+            .or_else(|| Self::criterion(profile.title, profile.title_exact))
+            .unwrap_or_default();
+
+        Self {
+            pid: matches.get_one::<PidSet>("pid").cloned().or_else(|| env.pid.clone()).or(profile.pid),
+            name,
+            name_mode,
+            title,
+            title_mode,
+            service: matches.get_one::<String>("service").cloned(),
+            ex_style: matches.get_one::<u32>("ex_style").copied().unwrap_or(0),
+            include_hidden: false,
+            exclude_self: matches.get_flag("exclude_self"),
+            exclude_pid: matches.get_one::<PidSet>("exclude_pid").cloned(),
+            include_zero_size: matches.get_flag("include_zero_size"),
+            at_cursor: matches.get_flag("at_cursor"),
+            match_any: matches.get_flag("match_any"),
+            per_process: matches.get_flag("per_process"),
+            tid: matches.get_one::<u32>("tid").copied(),
+            title_source: matches.get_one::<TitleSource>("title_source").copied().unwrap_or_default(),
+            targets: matches.get_one::<WindowTargets>("from_json").cloned(),
+            uia_name: matches.get_one::<String>("uia_name").cloned(),
+            automation_id: matches.get_one::<String>("automation_id").cloned(),
+        }
+    }
+
+    fn text_criterion(matches: &clap::ArgMatches, contains_id: &str, exact_id: &str) -> Option<(Option<String>, MatchMode)> {
+        Self::criterion(
+            matches.get_one::<String>(contains_id).cloned(),
+            matches.get_one::<String>(exact_id).cloned(),
+        )
+    }
+
+    /// 精确匹配优先；两者都没有时返回 None
+    fn criterion(contains: Option<String>, exact: Option<String>) -> Option<(Option<String>, MatchMode)> {
+        match (exact, contains) {
+            (Some(exact), _) => Some((Some(exact), MatchMode::Exact)),
+            (None, Some(contains)) => Some((Some(contains), MatchMode::Contains)),
+            (None, None) => None,
+        }
+    }
+
+    /// 是否指定了任何过滤条件
+    pub fn has_criteria(&self) -> bool {
+        self.at_cursor || self.tid.is_some() || self.pid.is_some() || self.name.is_some() || self.title.is_some() || self.service.is_some()
+            || self.ex_style != 0 || self.has_uia_criteria()
+    }
+
+    /// 是否指定了 UI Automation 条件（需要在枚举窗口时查询 UIA 属性）
+    pub fn has_uia_criteria(&self) -> bool {
+        self.uia_name.is_some() || self.automation_id.is_some()
+    }
+
+    /// 只按单个 PID 过滤时返回该 PID，用于只枚举该进程的窗口（--match-any 时不能缩小枚举范围）
+    pub fn single_pid(&self) -> Option<u32> {
+        if self.match_any {
+            None
+        } else {
+            self.pid.as_ref().and_then(PidSet::single)
+        }
+    }
+
+    /// 指定 --per-process 时按 PID 去重，保留每个进程排序后的第一个窗口；应在排序之后、按索引选择之前调用
+    pub fn dedup_per_process<T: crate::sorting::Sortable>(&self, windows: &mut Vec<T>) {
+        if self.per_process {
+            let mut seen = HashSet::new();
+            windows.retain(|window| seen.insert(window.get_pid()));
+        }
+    }
+
+    /// 检查窗口是否匹配过滤条件
+    pub fn matches(&self, window: &WindowInfo, process_names: &ProcessNameIndex) -> bool {
+        (self.include_zero_size || !window.rect.is_empty())
+            && !self.is_excluded(window.pid)
+            && self.targets.as_ref().is_none_or(|targets| targets.contains(window))
+            && self.combine([
+                self.pid_criterion(window.pid),
+                self.name_criterion(process_names.get(window.pid).unwrap_or_default()),
+                self.window_title_criterion(window, process_names),
+                self.service_criterion(process_names.service(window.pid)),
+                (self.ex_style != 0).then_some(window.ex_style & self.ex_style == self.ex_style),
+                Self::uia_criterion(self.uia_name.as_ref(), window.uia.as_ref().map(|uia| uia.name.as_str()), MatchMode::Contains),
+                Self::uia_criterion(
+                    self.automation_id.as_ref(),
+                    window.uia.as_ref().map(|uia| uia.automation_id.as_str()),
+                    MatchMode::Exact,
+                ),
+            ])
+    }
+
+    /// 过滤窗口时需要的进程字段：只有按进程标题匹配时才需要枚举所有窗口，只有 --service 需要查询服务
+    pub fn process_fields(&self) -> ProcessFields {
+        ProcessFields {
+            windows: self.title.is_some() && self.title_source != TitleSource::Window,
+            services: self.service.is_some(),
+            ..Default::default()
+        }
+    }
+
+    /// 没有找到匹配窗口时的错误
+    ///
+    /// 指定 `--pid` 时区分“没有这个进程”和“进程存在但没有匹配的窗口”，其余情况为 `NoMatchingWindows`。
+    pub fn no_windows_error(&self, process_names: &ProcessNameIndex) -> AppError {
+        let Some(pids) = &self.pid else {
+            return AppError::NoMatchingWindows;
+        };
+
+        let mut existing: Vec<u32> = process_names
+            .iter()
+            .filter(|&(pid, name)| {
+                !self.is_self(pid) && self.pid_criterion(pid) == Some(true) && self.name_criterion(name) != Some(false)
+            })
+            .map(|(pid, _)| pid)
+            .collect();
+        if existing.is_empty() {
+            return AppError::NoSuchProcess(pids.to_string());
+        }
+
+        existing.sort_unstable();
+        let existing: Vec<String> = existing.iter().map(|pid| pid.to_string()).collect();
+        AppError::NoWindowForProcess(existing.join(", "))
+    }
+
+    /// 检查进程是否匹配过滤条件
+    pub fn matches_process(&self, process: &ProcessInfo) -> bool {
+        let pid = process.pid.parse().unwrap_or(0);
+        !self.is_excluded(pid)
+            && self.combine([
+                self.pid_criterion(pid),
+                self.name_criterion(&process.name),
+                self.title_criterion(&process.title),
+                self.service_criterion(process.service.as_deref()),
+            ])
+    }
+
+    /// 按 AND（默认）或 OR（--match-any）组合已给出条件的结果；没有给出任何条件时匹配
+    fn combine<const N: usize>(&self, results: [Option<bool>; N]) -> bool {
+        let mut given = results.into_iter().flatten().peekable();
+        if given.peek().is_none() {
+            true
+        } else if self.match_any {
+            given.any(|matched| matched)
+        } else {
+            given.all(|matched| matched)
+        }
+    }
+
+    fn is_self(&self, pid: u32) -> bool {
+        self.exclude_self && pid == std::process::id()
+    }
+
+    /// --exclude-self 或 --exclude-pid 排除的进程
+    fn is_excluded(&self, pid: u32) -> bool {
+        self.is_self(pid) || self.exclude_pid.as_ref().is_some_and(|pids| pids.contains(pid))
+    }
+
+    fn pid_criterion(&self, pid: u32) -> Option<bool> {
+        self.pid.as_ref().map(|pids| pids.contains(pid))
+    }
+
+    fn name_criterion(&self, name: &str) -> Option<bool> {
+        self.name.as_ref().map(|filter| self.name_mode.matches(name, filter))
+    }
+
+    /// 服务名可能包含多个服务（", " 分隔），任一服务匹配即可；不承载服务的进程不匹配
+    fn service_criterion(&self, service: Option<&str>) -> Option<bool> {
+        self.service.as_ref().map(|filter| {
+            service.is_some_and(|service| service.split(", ").any(|s| MatchMode::Contains.matches(s, filter)))
+        })
+    }
+
+    /// 没有 UIA 属性（查询失败）的窗口不匹配
+    fn uia_criterion(filter: Option<&String>, value: Option<&str>, mode: MatchMode) -> Option<bool> {
+        filter.map(|filter| value.is_some_and(|value| mode.matches(value, filter)))
+    }
+
+    fn title_criterion(&self, title: &str) -> Option<bool> {
+        self.title.as_ref().map(|filter| self.title_mode.matches(title, filter))
+    }
+
+    /// 按 `title_source` 选择窗口标题和/或所属进程的标题进行匹配
+    fn window_title_criterion(&self, window: &WindowInfo, process_names: &ProcessNameIndex) -> Option<bool> {
+        let process_title = || self.title_criterion(process_names.title(window.pid).unwrap_or_default());
+        match self.title_source {
+            TitleSource::Window => self.title_criterion(&window.title),
+            TitleSource::Process => process_title(),
+            TitleSource::Both => self.title_criterion(&window.title).map(|matched| matched || process_title() == Some(true)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WindowRect;
+
+    fn process(pid: &str, name: &str, title: &str) -> ProcessInfo {
+        ProcessInfo { title: title.to_string(), has_window: true, ..ProcessInfo::for_test(pid, name) }
+    }
+
+    fn window(pid: u32, title: &str) -> WindowInfo {
+        WindowInfo::for_test(pid, title, WindowRect::new(0, 0, 800, 600))
+    }
+
+    #[test]
+    fn test_process_name_index() {
+        let index = ProcessNameIndex::from_processes(&[
+            process("100", "notepad.exe", "Untitled"),
+            process("200", "chrome.exe", "Google"),
+        ]);
+
+        assert_eq!(index.get(100), Some("notepad.exe"));
+        assert_eq!(index.get(200), Some("chrome.exe"));
+        assert_eq!(index.get(300), None);
+    }
+
+    #[test]
+    fn test_window_filter_matches() {
+        let index = ProcessNameIndex::from_processes(&[process("100", "Notepad.exe", "Untitled")]);
+        let notepad = window(100, "Untitled - Notepad");
+        let other = window(200, "Other Window");
+
+        assert!(WindowFilter::default().matches(&notepad, &index));
+        assert!(!WindowFilter::default().has_criteria());
+
+        let by_pid = WindowFilter { pid: Some(100.into()), ..Default::default() };
+        assert!(by_pid.matches(&notepad, &index));
+        assert!(!by_pid.matches(&other, &index));
+
+        let by_name = WindowFilter { name: Some("notepad".to_string()), ..Default::default() };
+        assert!(by_name.matches(&notepad, &index));
+        assert!(!by_name.matches(&other, &index)); // 未知进程名不匹配
+
+        let combined = WindowFilter {
+            pid: Some(100.into()),
+            title: Some("OTHER".to_string()),
+            ..Default::default()
+        };
+        assert!(!combined.matches(&notepad, &index));
+    }
+
+    #[test]
+    fn test_ex_style_filter() {
+        assert_eq!(parse_ex_style("TOPMOST").unwrap(), 0x8);
+        assert_eq!(parse_ex_style("topmost|WS_EX_LAYERED").unwrap(), 0x8 | 0x80000);
+        assert_eq!(parse_ex_style("toolwindow, layered").unwrap(), 0x80 | 0x80000);
+        assert!(parse_ex_style("TOPMOST|BOGUS").is_err());
+        assert!(parse_ex_style("").is_err());
+
+        let index = ProcessNameIndex::default();
+        let mut layered = window(100, "Overlay");
+        layered.ex_style = 0x8 | 0x80000;
+        let topmost_layered = WindowFilter { ex_style: 0x8 | 0x80000, ..Default::default() };
+        let tool = WindowFilter { ex_style: 0x80, ..Default::default() };
+
+        assert!(topmost_layered.matches(&layered, &index));
+        assert!(!tool.matches(&layered, &index));
+        assert!(!topmost_layered.matches(&window(100, "Plain"), &index));
+    }
+
+    #[test]
+    fn test_uia_filter() {
+        let index = ProcessNameIndex::default();
+        let mut dialog = window(100, "");
+        dialog.uia = Some(crate::types::UiaProperties {
+            name: "Save As".to_string(),
+            automation_id: "SaveDialog".to_string(),
+        });
+
+        let by_name = WindowFilter { uia_name: Some("save".to_string()), ..Default::default() };
+        assert!(by_name.has_uia_criteria());
+        assert!(by_name.matches(&dialog, &index));
+        // 没有查询到 UIA 属性的窗口不匹配
+        assert!(!by_name.matches(&window(100, "Save As"), &index));
+
+        let by_id = |id: &str| WindowFilter { automation_id: Some(id.to_string()), ..Default::default() };
+        assert!(by_id("savedialog").matches(&dialog, &index));
+        assert!(!by_id("Save").matches(&dialog, &index));
+    }
+
+    #[test]
+    fn test_window_filter_service() {
+        let mut host = process("100", "svchost.exe", "");
+        host.service = Some("Dnscache, LanmanWorkstation".to_string());
+        let index = ProcessNameIndex::from_processes(&[host, process("200", "svchost.exe", "")]);
+        let filter = WindowFilter { service: Some("lanman".to_string()), ..Default::default() };
+
+        assert_eq!(index.service(100), Some("Dnscache, LanmanWorkstation"));
+        assert!(filter.has_criteria());
+        assert!(filter.matches(&window(100, ""), &index));
+        assert!(!filter.matches(&window(200, ""), &index));
+    }
+
+    #[test]
+    fn test_no_windows_error() {
+        let index = ProcessNameIndex::from_processes(&[process("100", "notepad.exe", ""), process("150", "tray.exe", "")]);
+        let error = |filter: WindowFilter| filter.no_windows_error(&index);
+
+        assert!(matches!(error(WindowFilter::default()), AppError::NoMatchingWindows));
+        assert!(matches!(error(WindowFilter { pid: Some(100.into()), ..Default::default() }),
+            AppError::NoWindowForProcess(pids) if pids == "100"));
+        assert!(matches!(error(WindowFilter { pid: Some(PidSet::parse("90-200").unwrap()), ..Default::default() }),
+            AppError::NoWindowForProcess(pids) if pids == "100, 150"));
+        assert!(matches!(error(WindowFilter { pid: Some(PidSet::parse("300,400-500").unwrap()), ..Default::default() }),
+            AppError::NoSuchProcess(pids) if pids == "300,400-500"));
+        // PID 存在但进程名不匹配，视为没有这个进程
+        assert!(matches!(error(WindowFilter { pid: Some(100.into()), name: Some("chrome".to_string()), ..Default::default() }),
+            AppError::NoSuchProcess(_)));
+    }
+
+    #[test]
+    fn test_match_any() {
+        let index = ProcessNameIndex::from_processes(&[process("100", "code.exe", ""), process("200", "chrome.exe", "")]);
+        let editor = window(100, "main.rs - Visual Studio Code");
+        let browser = window(200, "Docs - Chrome");
+        let terminal = window(300, "Terminal");
+
+        let all = WindowFilter { name: Some("chrome".to_string()), title: Some("code".to_string()), ..Default::default() };
+        assert!(!all.matches(&editor, &index));
+        assert!(!all.matches(&browser, &index));
+
+        let any = WindowFilter { match_any: true, ..all };
+        assert!(any.matches(&editor, &index));
+        assert!(any.matches(&browser, &index));
+        assert!(!any.matches(&terminal, &index));
+        assert!(WindowFilter { match_any: true, ..Default::default() }.matches(&terminal, &index));
+
+        // --match-any 时不能只枚举单个进程的窗口
+        let by_pid = WindowFilter { pid: Some(100.into()), ..Default::default() };
+        assert_eq!(by_pid.single_pid(), Some(100));
+        assert_eq!(WindowFilter { match_any: true, ..by_pid }.single_pid(), None);
+        assert!(any.matches_process(&process("300", "chrome.exe", "")));
+    }
+
+    #[test]
+    fn test_from_json_targets() {
+        let index = ProcessNameIndex::default();
+        let saved = [window(100, "main.rs - Visual Studio Code"), window(200, "Docs - Chrome")];
+        let filter = WindowFilter { targets: Some(WindowTargets::from_windows(&saved)), ..Default::default() };
+
+        assert!(filter.matches(&window(100, "main.rs - Visual Studio Code"), &index));
+        // 标题变化或 PID 被复用后不再匹配
+        assert!(!filter.matches(&window(100, "lib.rs - Visual Studio Code"), &index));
+        assert!(!filter.matches(&window(300, "Docs - Chrome"), &index));
+        // --match-any 不会放宽保存的目标
+        let any = WindowFilter { match_any: true, title: Some("chrome".to_string()), ..filter };
+        assert!(!any.matches(&window(300, "Docs - Chrome"), &index));
+    }
+
+    #[test]
+    fn test_dedup_per_process() {
+        let mut windows = vec![window(100, "a"), window(200, "b"), window(100, "c"), window(300, "d"), window(200, "e")];
+
+        WindowFilter::default().dedup_per_process(&mut windows);
+        assert_eq!(windows.len(), 5);
+
+        WindowFilter { per_process: true, ..Default::default() }.dedup_per_process(&mut windows);
+        let titles: Vec<&str> = windows.iter().map(|w| w.title.as_str()).collect();
+        assert_eq!(titles, ["a", "b", "d"]);
+    }
+
+    #[test]
+    fn test_window_filter_matches_process() {
+        let p = process("100", "notepad.exe", "Untitled - Notepad");
+
+        assert!(WindowFilter { pid: Some(100.into()), ..Default::default() }.matches_process(&p));
+        assert!(!WindowFilter { pid: Some(101.into()), ..Default::default() }.matches_process(&p));
+        assert!(WindowFilter { title: Some("untitled".to_string()), ..Default::default() }.matches_process(&p));
+    }
+
+    #[test]
+    fn test_window_filter_zero_size() {
+        let index = ProcessNameIndex::default();
+        let mut empty = window(100, "Closing");
+        empty.rect = WindowRect::new(10, 10, 0, 600);
+
+        assert!(!WindowFilter::default().matches(&empty, &index));
+        assert!(WindowFilter { include_zero_size: true, ..Default::default() }.matches(&empty, &index));
+        assert!(WindowFilter::default().matches(&window(100, "Normal"), &index));
+    }
+
+    #[test]
+    fn test_pid_set_parse() {
+        let pids = PidSet::parse("10, 20,100-200").unwrap();
+        assert!(pids.contains(10) && pids.contains(20));
+        assert!(pids.contains(100) && pids.contains(150) && pids.contains(200));
+        assert!(!pids.contains(15) && !pids.contains(201));
+        assert_eq!(pids.single(), None);
+
+        assert_eq!(PidSet::parse("42").unwrap().single(), Some(42));
+        assert_eq!(PidSet::parse("42-42").unwrap().single(), Some(42));
+        assert!(PidSet::parse("200-100").is_err());
+        assert!(PidSet::parse("abc").is_err());
+        assert!(PidSet::parse("1,,2").is_err());
+    }
+
+    #[test]
+    fn test_window_filter_exclude_self() {
+        let own_pid = std::process::id();
+        let p = process(&own_pid.to_string(), "pscan", "pscan");
+
+        assert!(WindowFilter::default().matches_process(&p));
+        assert!(!WindowFilter { exclude_self: true, ..Default::default() }.matches_process(&p));
+        // 显式指定自身 PID 时同样被排除
+        assert!(!WindowFilter { pid: Some(own_pid.into()), exclude_self: true, ..Default::default() }.matches_process(&p));
+        assert!(WindowFilter { exclude_self: true, ..Default::default() }.matches_process(&process("1", "init", "")));
+    }
+
+    #[test]
+    fn test_window_filter_title_source() {
+        let names = ProcessNameIndex::from_processes(&[process("1", "python", "python server.py")]);
+        let w = window(1, "Untitled - Notepad");
+        let filter = |title: &str, title_source| WindowFilter { title: Some(title.to_string()), title_source, ..Default::default() };
+
+        assert!(filter("notepad", TitleSource::Window).matches(&w, &names));
+        assert!(!filter("server.py", TitleSource::Window).matches(&w, &names));
+        assert!(filter("server.py", TitleSource::Process).matches(&w, &names));
+        assert!(!filter("notepad", TitleSource::Process).matches(&w, &names));
+        assert!(filter("notepad", TitleSource::Both).matches(&w, &names));
+        assert!(filter("server.py", TitleSource::Both).matches(&w, &names));
+        assert!(!filter("missing", TitleSource::Both).matches(&w, &names));
+    }
+
+    #[test]
+    fn test_window_filter_exclude_pid() {
+        let names = ProcessNameIndex::default();
+        let filter = WindowFilter {
+            name: Some("chrome".to_string()),
+            exclude_pid: Some(PidSet::parse("10,20-30").unwrap()),
+            ..Default::default()
+        };
+        assert!(filter.matches_process(&process("5", "chrome", "")));
+        assert!(!filter.matches_process(&process("10", "chrome", "")));
+        assert!(!filter.matches_process(&process("25", "chrome", "")));
+
+        // --match-any 时排除条件仍然生效
+        let filter = WindowFilter { title: Some("Docs".to_string()), match_any: true, ..filter };
+        assert!(filter.matches(&window(40, "Docs"), &names));
+        assert!(!filter.matches(&window(20, "Docs"), &names));
+    }
+
+    #[test]
+    fn test_window_filter_exact_mode() {
+        let index = ProcessNameIndex::from_processes(&[
+            process("100", "notepad.exe", "Notepad"),
+            process("200", "notepad++.exe", "Notepad++"),
+        ]);
+
+        let exact = WindowFilter {
+            title: Some("notepad".to_string()),
+            title_mode: MatchMode::Exact,
+            ..Default::default()
+        };
+        assert!(exact.matches(&window(100, "Notepad"), &index));
+        assert!(!exact.matches(&window(200, "Notepad++"), &index));
+
+        let exact_name = WindowFilter {
+            name: Some("NOTEPAD.EXE".to_string()),
+            name_mode: MatchMode::Exact,
+            ..Default::default()
+        };
+        assert!(exact_name.matches(&window(100, "Notepad"), &index));
+        assert!(!exact_name.matches(&window(200, "Notepad++"), &index));
+    }
+
+    #[test]
+    fn test_process_fields() {
+        // 只按窗口标题过滤时不需要枚举所有窗口
+        let window_title = WindowFilter { title: Some("Notepad".to_string()), ..Default::default() };
+        assert_eq!(window_title.process_fields(), ProcessFields::default());
+
+        let process_title = WindowFilter { title_source: TitleSource::Process, ..window_title };
+        assert!(process_title.process_fields().windows);
+        assert!(!process_title.process_fields().services);
+
+        let service = WindowFilter { service: Some("Dnscache".to_string()), ..Default::default() };
+        assert_eq!(service.process_fields(), ProcessFields { services: true, ..Default::default() });
+    }
+}
@@ -9,35 +9,52 @@ mod sorting;
 mod utils;
 mod features;  // 新增特性模块
 mod error;     // 新增错误处理模块
+mod filter;
+mod diff;
+mod config;
+mod doctor;
+#[cfg(feature = "undo")]
+mod undo;
 
+use std::collections::HashMap;
 use std::process::exit;
 // use output::{OutputFormat, display_processes};
 // use cli::{parse_args, SubCommand};
-use output::display_processes;  // 移除 OutputFormat
+use output::{display_processes, display_process_name_summary};  // 移除 OutputFormat
 use cli::parse_args;            // 移除 SubCommand
-use process::{get_processes, filter_processes};
-use features::{create_default_manager, get_enabled_features};  // 新增
+use process::{get_processes, filter_processes, collect_ancestors, summarize_by_name, memory_deltas};
+use types::ProcessInfo;
+use features::{create_default_manager, get_enabled_features, print_feature_report};  // 新增
 use error::{AppError, AppResult};  // 新增
+use filter::{WindowFilter, ProcessNameIndex};
+use sorting::{apply_process_sorting, apply_name_summary_sorting};
 
 fn main() {
     if let Err(e) = run() {
-        eprintln!("Error: {}", e);
-        
-        // 根据错误类型决定退出码
-        let exit_code = match e {
-            AppError::NoMatchingWindows => 2,
-            AppError::MultipleWindows(_) => 3,
-            AppError::InvalidParameter(_) => 4,
-            AppError::FeatureNotSupported(_) => 5,
-            _ => 1,
-        };
-        
+        // 根据错误类型决定退出码；错误只写到 stderr，stdout 只包含已输出的结果
+        let exit_code = e.exit_code();
+        output::print_error(&e, exit_code);
         exit(exit_code);
     }
 }
 
 fn run() -> AppResult<()> {
     let config = parse_args();
+
+    // 在输出任何内容前设置控制台编码
+    platform::enable_utf8_console();
+    platform::set_full_title(config.full_title);
+    platform::set_uia_query(config.filter.has_uia_criteria())?;
+    output::set_ascii_output(config.ascii);
+    output::set_strip_control(config.strip_control);
+    output::set_json_output(config.json);
+    output::set_compact_json(config.compact);
+    output::set_table_width(config.table_width, config.no_truncate);
+    output::set_error_format(config.error_format);
+    output::set_memory_unit(config.memory_unit);
+    if let Some(path) = &config.tee {
+        output::set_tee_file(path)?;
+    }
     let feature_manager = create_default_manager();  // 创建特性管理器
 
     // 显示启用的特性（调试信息）
@@ -57,7 +74,13 @@ fn run() -> AppResult<()> {
     }
 
     match config.subcommand {
-        // 所有子命令现在都由特性管理器处理
+        Some(cli::SubCommand::Features) => {
+            print_feature_report()?;
+        }
+        Some(cli::SubCommand::Doctor { format }) => {
+            doctor::print_doctor_report(format)?;
+        }
+        // 其余子命令都由特性管理器处理
         Some(subcommand) => {
             feature_manager.execute(&subcommand)?;
         }
@@ -72,12 +95,14 @@ fn run() -> AppResult<()> {
 
 // 窗口操作类型枚举 - 提供类型安全
 #[derive(Debug, Clone, Copy)]
+#[allow(dead_code)]
 enum WindowOperation {
     Minimize,
     Maximize,
     Restore,
 }
 
+#[allow(dead_code)]
 impl WindowOperation {
     // 获取操作名称（动词形式）
     fn as_str(&self) -> &'static str {
@@ -108,26 +133,19 @@ impl WindowOperation {
 }
 
 // 统一的窗口操作处理函数
+#[allow(dead_code)]
 fn handle_window_operation_command(
-    pid_filter: Option<String>,
-    name_filter: Option<String>,
-    title_filter: Option<String>,
+    filter: &WindowFilter,
     all: bool,
     operation: WindowOperation,
 ) -> AppResult<()> {
     // Get process names for filtering
-    let processes = get_processes();
-    let process_names: Vec<(u32, String)> = processes
-        .iter()
-        .map(|p| (p.pid.parse().unwrap_or(0), p.name.clone()))
-        .collect();
+    let process_names = ProcessNameIndex::collect(filter);
 
     // 使用统一的执行器
     let count = execute_window_operation(
         operation,
-        &pid_filter,
-        &name_filter,
-        &title_filter,
+        filter,
         &process_names,
         all
     )?;
@@ -137,24 +155,23 @@ fn handle_window_operation_command(
 }
 
 // 统一的窗口操作执行器 - 消除重复逻辑
+#[allow(dead_code)]
 fn execute_window_operation(
     operation: WindowOperation,
-    pid_filter: &Option<String>,
-    name_filter: &Option<String>,
-    title_filter: &Option<String>,
-    process_names: &[(u32, String)],
+    filter: &WindowFilter,
+    process_names: &ProcessNameIndex,
     all: bool,
 ) -> AppResult<usize> {
     // 使用平台抽象层查找匹配的窗口
-    let windows = crate::platform::find_windows(pid_filter, name_filter, title_filter, process_names);
+    let windows = crate::platform::find_windows(filter, process_names)?;
     
     // 验证窗口数量
     if windows.is_empty() {
-        return Err(AppError::NoMatchingWindows);
+        return Err(filter.no_windows_error(process_names));
     }
 
     if !all && windows.len() > 1 {
-        return Err(AppError::MultipleWindows(windows.len()));
+        return Err(AppError::multiple_windows(windows.len()));
     }
 
     // 执行操作
@@ -183,33 +200,80 @@ fn execute_window_operation(
 
 // 进程列表处理函数（保持独立）
 fn handle_process_command(config: cli::CliConfig) -> AppResult<()> {
+    if let Some(interval) = config.watch {
+        return watch_processes(&config, interval);
+    }
+
     // Get process list
-    let processes = get_processes();
+    let processes = get_processes(config.process_fields());
+    let selected = select_processes(&processes, &config);
+    let filtered_processes: Vec<&ProcessInfo> = selected.iter().collect();
+
+    // Display results
+    if filtered_processes.is_empty() {
+        return Err(AppError::NoMatchingWindows);
+    }
 
+    // --group-by name --summary：按进程名汇总数量和内存
+    if let Some(summary_sort) = config.name_summary {
+        let mut summary = summarize_by_name(&filtered_processes);
+        apply_name_summary_sorting(&mut summary, summary_sort, config.sort.reverse);
+        return display_process_name_summary(&summary, config.format);
+    }
+
+    display_processes(&filtered_processes, config.format, config.verbose, None)
+}
+
+/// --watch：定时刷新进程列表，并显示每个进程相对上一轮的内存变化，便于发现内存泄漏
+fn watch_processes(config: &cli::CliConfig, interval: u64) -> AppResult<()> {
+    let mut previous: HashMap<String, u64> = HashMap::new();
+
+    loop {
+        let processes = get_processes(config.process_fields());
+        let selected = select_processes(&processes, config);
+        let filtered_processes: Vec<&ProcessInfo> = selected.iter().collect();
+        let deltas = memory_deltas(&previous, &filtered_processes);
+        display_processes(&filtered_processes, config.format.clone(), config.verbose, Some(&deltas))?;
+
+        // 只保留本轮的进程，已退出的进程不再跟踪
+        previous = filtered_processes
+            .iter()
+            .map(|process| (process.pid.clone(), process.memory_usage))
+            .collect();
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+    }
+}
+
+/// 按命令行条件过滤、补充祖先并排序进程
+fn select_processes(processes: &[ProcessInfo], config: &cli::CliConfig) -> Vec<ProcessInfo> {
     // Filter processes
-    let filtered_processes = filter_processes(
-        &processes,
-        &config.pid_filter,
-        &config.name_filter,
-        &config.title_filter,
+    let mut filtered_processes = filter_processes(
+        processes,
+        &config.filter,
         config.has_window_filter,
         config.no_window_filter,
+        config.min_memory_percent,
+        config.user.as_deref(),
+        config.bitness.as_deref(),
     );
 
-    // Display results
-    if filtered_processes.is_empty() {
-        return Err(AppError::NoMatchingWindows);
-    }
+    // 加入匹配进程的父进程链（祖先不受其他过滤条件限制）
+    let ancestors = if config.include_parents {
+        collect_ancestors(processes, &filtered_processes)
+    } else {
+        Vec::new()
+    };
+    filtered_processes.extend(ancestors.iter());
 
-    display_processes(&filtered_processes, config.format, config.verbose)
+    // Sort processes
+    apply_process_sorting(&mut filtered_processes, &config.sort);
+    filtered_processes.into_iter().cloned().collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use output::truncate_string;
-    use sorting::{SortOrder, PositionSort};
-    use types::WindowRect;
 
     #[test]
     fn test_truncate_string() {
@@ -239,6 +303,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_window_operation_clone() {
         // Test that the enum can be cloned (needed for function parameters)
         let op1 = WindowOperation::Minimize;
@@ -257,9 +322,9 @@ mod tests {
         let restore = WindowOperation::Restore;
 
         // This should compile and run without panicking
-        format!("{:?}", minimize);
-        format!("{:?}", maximize);
-        format!("{:?}", restore);
+        let _ = format!("{:?}", minimize);
+        let _ = format!("{:?}", maximize);
+        let _ = format!("{:?}", restore);
     }
 
     #[test]
@@ -1,68 +1,450 @@
 // src/output.rs
-use crate::types::{ProcessInfo, ProcessOutput, WindowInfo, WindowOutput};
-use crate::error::AppResult;
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use serde::Serialize;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::types::{ProcessInfo, ProcessOutput, ProcessNameCount, WindowInfo, WindowCount, MonitorWindowCount, WindowRecord, WindowStateRecord};
+use crate::error::{AppError, AppResult};
+use crate::filter::ProcessNameIndex;
+use crate::platform::WindowHandle;
 
 #[derive(Debug, Clone, clap::ValueEnum)]
 pub enum OutputFormat {
+    /// 标准输出是终端时为 table，被管道或重定向时为 json
+    Auto,
     Table,
     Json,
     Yaml,
     Csv,
+    /// 制表符分隔，与 CSV 使用相同的列
+    Tsv,
     Simple,
     Detailed,
 }
 
+impl OutputFormat {
+    /// 把 `auto` 解析为具体格式，其他格式原样返回
+    pub fn resolve(self) -> Self {
+        use std::io::IsTerminal;
+        match self {
+            OutputFormat::Auto if std::io::stdout().is_terminal() => OutputFormat::Table,
+            OutputFormat::Auto => OutputFormat::Json,
+            format => format,
+        }
+    }
+
+    /// 是否逐条输出记录的全部字段（json/yaml/csv/tsv/detailed）；table 和 simple 只显示部分列
+    pub fn shows_all_fields(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Detailed)
+    }
+
+    /// CSV/TSV 的字段分隔符
+    pub fn delimiter(&self) -> u8 {
+        match self {
+            OutputFormat::Tsv => b'\t',
+            _ => b',',
+        }
+    }
+}
+
+/// 没有匹配结果时的处理方式（`windows/get --empty-as`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EmptyAs {
+    /// 报告 `NoMatchingWindows`（退出码 2）
+    #[default]
+    Error,
+    /// 输出空结果：JSON/YAML 为 `[]`，表格/CSV 只有表头
+    Array,
+    /// JSON/YAML 输出 `null`，其他格式与 `array` 相同
+    Null,
+}
+
+/// 错误信息的输出格式（`--error-format`）
+///
+/// 约定：stdout 只输出结果，错误无论哪种格式都只写到 stderr。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// `Error: <message>`
+    #[default]
+    Text,
+    /// 单行 JSON 对象：`{"error": <kind>, "message": <message>, "exit_code": <code>}`
+    Json,
+}
+
+/// 错误信息是否输出为 JSON（--error-format json）
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// 设置错误信息的输出格式，启动时调用一次
+pub fn set_error_format(format: ErrorFormat) {
+    JSON_ERRORS.store(format == ErrorFormat::Json, Ordering::Relaxed);
+}
+
+/// 是否以 JSON 输出错误信息
+pub fn json_errors() -> bool {
+    JSON_ERRORS.load(Ordering::Relaxed)
+}
+
+/// `--error-format json` 时写到 stderr 的错误对象
+#[derive(Serialize)]
+struct ErrorRecord {
+    error: &'static str,
+    message: String,
+    exit_code: i32,
+}
+
+/// 把错误写到 stderr；`exit_code` 为进程实际的退出码
+pub fn print_error(error: &AppError, exit_code: i32) {
+    if json_errors() {
+        let record = ErrorRecord { error: error.kind(), message: error.to_string(), exit_code };
+        // 单行输出，便于脚本按行读取 stderr
+        if let Ok(json) = serde_json::to_string(&record) {
+            eprintln!("{}", json);
+            return;
+        }
+    }
+    eprintln!("Error: {}", error);
+}
+
+/// CSV/TSV 写入器：只在字段包含分隔符、引号或换行时加引号
+pub fn delimited_writer(delimiter: u8) -> csv::Writer<Tee<std::io::Stdout, &'static File>> {
+    csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .quote_style(csv::QuoteStyle::Necessary)
+        .from_writer(output_writer())
+}
+
+/// 是否将人类可读输出中的非 ASCII 字符替换为 '?'（--ascii）
+static ASCII_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// 设置 ASCII 输出模式，启动时调用一次
+pub fn set_ascii_output(enabled: bool) {
+    ASCII_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否以 JSON 输出结果（全局 --json）
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// 设置 JSON 输出模式，启动时调用一次
+pub fn set_json_output(enabled: bool) {
+    JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否处于 JSON 输出模式
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// 是否输出单行 JSON（--compact），默认为缩进格式（--pretty）
+static COMPACT_JSON: AtomicBool = AtomicBool::new(false);
+
+/// 设置 JSON 是否紧凑输出，启动时调用一次
+pub fn set_compact_json(enabled: bool) {
+    COMPACT_JSON.store(enabled, Ordering::Relaxed);
+}
+
+/// 按 `--compact`/`--pretty` 将结果序列化为 JSON
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    if COMPACT_JSON.load(Ordering::Relaxed) {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+/// 按当前输出模式处理文本：ASCII 模式下将非 ASCII 字符替换为 '?'
+pub fn display_text(s: &str) -> Cow<'_, str> {
+    if ASCII_OUTPUT.load(Ordering::Relaxed) {
+        to_ascii(s)
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// 将非 ASCII 字符替换为 '?'
+fn to_ascii(s: &str) -> Cow<'_, str> {
+    if s.is_ascii() {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.chars().map(|c| if c.is_ascii() { c } else { '?' }).collect())
+    }
+}
+
+/// 是否去掉所有格式输出中标题里的控制字符（--strip-control）
+static STRIP_CONTROL: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否去掉标题中的控制字符，启动时调用一次
+pub fn set_strip_control(enabled: bool) {
+    STRIP_CONTROL.store(enabled, Ordering::Relaxed);
+}
+
+/// 按 `--strip-control` 处理标题：开启时去掉换行、制表符等控制字符
+pub fn clean_title(s: &str) -> Cow<'_, str> {
+    if STRIP_CONTROL.load(Ordering::Relaxed) {
+        strip_control(s)
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// 去掉控制字符（`\r`、`\n`、`\t` 等）
+fn strip_control(s: &str) -> Cow<'_, str> {
+    if s.chars().any(char::is_control) {
+        Cow::Owned(s.chars().filter(|c| !c.is_control()).collect())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// 表格总宽度（--table-width 或检测到的终端宽度），0 表示未知，使用默认列宽
+static TABLE_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// 人类可读输出中内存值的单位（`--memory-unit`）；JSON/YAML/CSV 不受影响
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MemoryUnit {
+    /// 字节数，不带单位，便于脚本处理
+    B,
+    Kb,
+    #[default]
+    Mb,
+    Gb,
+    /// 按每个数值的大小选择 B、KB、MB 或 GB
+    Auto,
+}
+
+impl MemoryUnit {
+    /// `auto` 时按数值大小选择具体单位
+    fn resolve(self, bytes: f64) -> Self {
+        match self {
+            MemoryUnit::Auto => match bytes.abs() {
+                b if b >= 1024.0 * 1024.0 * 1024.0 => MemoryUnit::Gb,
+                b if b >= 1024.0 * 1024.0 => MemoryUnit::Mb,
+                b if b >= 1024.0 => MemoryUnit::Kb,
+                _ => MemoryUnit::B,
+            },
+            unit => unit,
+        }
+    }
+
+    /// 格式化内存值；`signed` 为 true 时总是带正负号（用于变化量）
+    fn format(self, bytes: f64, decimals: usize, signed: bool) -> String {
+        let unit = self.resolve(bytes);
+        if unit == MemoryUnit::B {
+            let count = if signed { format!("{:+}", bytes as i64) } else { (bytes as i64).to_string() };
+            // 显式指定 b 时输出裸字节数；auto 选出的字节带单位
+            return if self == MemoryUnit::B { count } else { format!("{} B", count) };
+        }
+        let (value, label) = match unit {
+            MemoryUnit::Kb => (bytes / 1024.0, "KB"),
+            MemoryUnit::Mb => (bytes / 1024.0 / 1024.0, "MB"),
+            _ => (bytes / 1024.0 / 1024.0 / 1024.0, "GB"),
+        };
+        if signed {
+            format!("{:+.decimals$} {}", value, label)
+        } else {
+            format!("{:.decimals$} {}", value, label)
+        }
+    }
+}
+
+/// 内存值的显示单位
+static MEMORY_UNIT: OnceLock<MemoryUnit> = OnceLock::new();
+
+/// 设置内存值的显示单位，启动时调用一次
+pub fn set_memory_unit(unit: MemoryUnit) {
+    let _ = MEMORY_UNIT.set(unit);
+}
+
+/// 按 `--memory-unit` 格式化字节数，`decimals` 为小数位数（字节不带小数）
+fn format_memory(bytes: f64, decimals: usize) -> String {
+    MEMORY_UNIT.get().copied().unwrap_or_default().format(bytes, decimals, false)
+}
+
+/// 与 `format_memory` 相同，但总是带正负号
+fn format_memory_delta(bytes: f64) -> String {
+    MEMORY_UNIT.get().copied().unwrap_or_default().format(bytes, 2, true)
+}
+
+/// 不截断表格中的长文本（--no-truncate）
+static NO_TRUNCATE: AtomicBool = AtomicBool::new(false);
+
+/// 设置表格宽度；`width` 为 None 时使用终端宽度，输出不是终端时保持默认列宽
+pub fn set_table_width(width: Option<usize>, no_truncate: bool) {
+    let width = width
+        .or_else(|| terminal_size::terminal_size().map(|(w, _)| w.0 as usize))
+        .unwrap_or(0);
+    TABLE_WIDTH.store(width, Ordering::Relaxed);
+    NO_TRUNCATE.store(no_truncate, Ordering::Relaxed);
+}
+
+/// 可伸缩列（标题等）的宽度：表格宽度已知时占满其余列（共 `fixed` 个字符）之外的空间，但不小于默认宽度
+fn flex_width(default: usize, fixed: usize) -> usize {
+    match TABLE_WIDTH.load(Ordering::Relaxed) {
+        0 => default,
+        width => width.saturating_sub(fixed).max(default),
+    }
+}
+
+/// 生成左对齐的单元格：文本限制在列宽内（保留列间隔），--no-truncate 时输出完整文本
+fn fit(s: &str, column_width: usize) -> String {
+    let text = display_text(s);
+    let text = if NO_TRUNCATE.load(Ordering::Relaxed) {
+        text.into_owned()
+    } else {
+        truncate_string(&text, column_width.saturating_sub(2))
+    };
+    pad_end(&text, column_width)
+}
+
+/// 按显示宽度在右侧补空格（宽字符占两列，`{:<N}` 按字符计数会错位）
+fn pad_end(s: &str, width: usize) -> String {
+    format!("{}{}", s, " ".repeat(width.saturating_sub(s.width())))
+}
+
+/// `--tee` 指定的文件：结果在输出到终端的同时追加写入该文件
+static TEE_FILE: OnceLock<File> = OnceLock::new();
+
+/// 打开 `--tee` 文件（追加模式，保留之前的记录），之后的结果输出同时写入该文件
+pub fn set_tee_file(path: &str) -> AppResult<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| AppError::invalid_parameter(format!("Failed to open tee file '{}': {}", path, e)))?;
+    let _ = TEE_FILE.set(file);
+    Ok(())
+}
+
+/// 将写入同时复制到两个目标的 `Write` 适配器；第二个目标可选
+pub struct Tee<A: Write, B: Write> {
+    primary: A,
+    secondary: Option<B>,
+}
+
+impl<A: Write, B: Write> Tee<A, B> {
+    pub fn new(primary: A, secondary: Option<B>) -> Self {
+        Self { primary, secondary }
+    }
+}
+
+impl<A: Write, B: Write> Write for Tee<A, B> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.primary.write_all(buf)?;
+        if let Some(secondary) = &mut self.secondary {
+            secondary.write_all(buf)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.primary.flush()?;
+        if let Some(secondary) = &mut self.secondary {
+            secondary.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// 结果输出目标：标准输出，设置了 `--tee` 时同时写入文件
+pub fn output_writer() -> Tee<std::io::Stdout, &'static File> {
+    Tee::new(std::io::stdout(), TEE_FILE.get())
+}
+
+/// 与 `println!` 相同，但写入 `output_writer()`；需要在返回 `AppResult` 的函数中使用
+macro_rules! outln {
+    () => {
+        ::std::io::Write::write_all(&mut $crate::output::output_writer(), b"\n")?
+    };
+    ($($arg:tt)*) => {
+        ::std::io::Write::write_fmt(
+            &mut $crate::output::output_writer(),
+            format_args!("{}\n", format_args!($($arg)*)),
+        )?
+    };
+}
+pub(crate) use outln;
+
 // 输出策略 trait
 trait OutputStrategy<T> {
     fn display(&self, data: &[T]) -> AppResult<()>;
 }
 
 // 进程信息输出策略
-struct ProcessTableStrategy {
+struct ProcessTableStrategy<'a> {
     verbose: bool,
+    /// `--watch` 时每个进程相对上一轮的内存变化（字节），与进程一一对应
+    deltas: Option<&'a [i64]>,
 }
 
-impl OutputStrategy<&ProcessInfo> for ProcessTableStrategy {
+impl OutputStrategy<&ProcessInfo> for ProcessTableStrategy<'_> {
     fn display(&self, processes: &[&ProcessInfo]) -> AppResult<()> {
-        println!("Found {} matching processes:", processes.len());
+        outln!("Found {} matching processes:", processes.len());
         
+        // 标题列占用 PID、名称、内存等固定列之外的宽度
+        let delta_width = if self.deltas.is_some() { 14 } else { 0 };
+        let tw = flex_width(30, if self.verbose { 58 } else { 43 } + delta_width);
         if self.verbose {
-            println!("{:<8} {:<20} {:<30} {:<12} {}", 
-                     "PID", "Name", "Title", "Memory", "Window");
+            outln!("{:<8} {:<20} {:<tw$} {:>12} {:>8} Window", 
+                     "PID", "Name", "Title", "Memory", "Mem%");
+        } else if self.deltas.is_some() {
+            outln!("{:<8} {:<20} {:<tw$} {:>12} {:>13}",
+                     "PID", "Name", "Title", "Memory", "Delta");
         } else {
-            println!("{:<8} {:<20} {:<30} {}", 
+            outln!("{:<8} {:<20} {:<tw$} {:>12}",
                      "PID", "Name", "Title", "Memory");
         }
 
-        for process in processes {
-            let memory_mb = process.memory_usage as f64 / 1024.0 / 1024.0;
+        for (i, process) in processes.iter().enumerate() {
+            let delta = self.deltas.and_then(|deltas| deltas.get(i)).map(|&delta| format_memory_delta(delta as f64));
+            let memory = format_memory(process.memory_usage as f64, 2);
             
+            // --include-parents 加入的祖先进程在行尾标注
+            let marker = if process.ancestor { " (ancestor)" } else { "" };
             if self.verbose {
-                println!(
-                    "{:<8} {:<20} {:<30} {:<11.2} MB {}",
+                outln!(
+                    "{:<8} {} {} {:>12} {:>8} {}{}",
+                    process.pid,
+                    fit(&process.name, 20),
+                    fit(&process.title, tw),
+                    memory,
+                    format!("{:.2}%", process.memory_percent),
+                    if process.has_window { "Yes" } else { "No" },
+                    marker
+                );
+            } else if let Some(delta) = &delta {
+                outln!(
+                    "{:<8} {} {} {:>12} {:>13}{}",
                     process.pid,
-                    truncate_string(&process.name, 18),
-                    truncate_string(&process.title, 28),
-                    memory_mb,
-                    if process.has_window { "Yes" } else { "No" }
+                    fit(&process.name, 20),
+                    fit(&process.title, tw),
+                    memory,
+                    delta,
+                    marker
                 );
             } else {
-                println!(
-                    "{:<8} {:<20} {:<30} {:.2} MB",
+                outln!(
+                    "{:<8} {} {} {:>12}{}",
                     process.pid,
-                    truncate_string(&process.name, 18),
-                    truncate_string(&process.title, 28),
-                    memory_mb
+                    fit(&process.name, 20),
+                    fit(&process.title, tw),
+                    memory,
+                    marker
                 );
             }
 
             if self.verbose {
-                println!("    PID: {}", process.pid);
-                println!("    Name: {}", process.name);
-                println!("    Title: {}", process.title);
-                println!("    Memory: {:.2} MB", memory_mb);
-                println!("    Has Window: {}", if process.has_window { "Yes" } else { "No" });
-                println!("    {}", "-".repeat(50));
+                outln!("    PID: {}", process.pid);
+                outln!("    Name: {}", display_text(&process.name));
+                outln!("    Title: {}", display_text(&process.title));
+                outln!("    Memory: {} ({:.2}%)", memory, process.memory_percent);
+                if let Some(delta) = &delta {
+                    outln!("    Delta: {}", delta);
+                }
+                outln!("    Has Window: {}", if process.has_window { "Yes" } else { "No" });
+                outln!("    User: {}", display_text(process.user.as_deref().unwrap_or("Unknown")));
+                outln!("    {}", "-".repeat(50));
             }
         }
         
@@ -75,8 +457,8 @@ struct ProcessJsonStrategy;
 impl OutputStrategy<&ProcessInfo> for ProcessJsonStrategy {
     fn display(&self, processes: &[&ProcessInfo]) -> AppResult<()> {
         let output: Vec<ProcessOutput> = processes.iter().map(|p| ProcessOutput::from(*p)).collect();
-        let json = serde_json::to_string_pretty(&output)?;
-        println!("{}", json);
+        let json = to_json(&output)?;
+        outln!("{}", json);
         Ok(())
     }
 }
@@ -87,28 +469,36 @@ impl OutputStrategy<&ProcessInfo> for ProcessYamlStrategy {
     fn display(&self, processes: &[&ProcessInfo]) -> AppResult<()> {
         let output: Vec<ProcessOutput> = processes.iter().map(|p| ProcessOutput::from(*p)).collect();
         let yaml = serde_yaml::to_string(&output)?;
-        println!("{}", yaml);
+        outln!("{}", yaml);
         Ok(())
     }
 }
 
-struct ProcessCsvStrategy;
+struct ProcessCsvStrategy {
+    delimiter: u8,
+}
 
 impl OutputStrategy<&ProcessInfo> for ProcessCsvStrategy {
     fn display(&self, processes: &[&ProcessInfo]) -> AppResult<()> {
-        let mut wtr = csv::Writer::from_writer(std::io::stdout());
+        let mut wtr = delimited_writer(self.delimiter);
         
-        wtr.write_record(&["PID", "Name", "Title", "MemoryUsage", "MemoryUsageMB", "HasWindow"])?;
+        wtr.write_record(["PID", "Name", "Title", "MemoryUsage", "MemoryUsageMB", "HasWindow", "MemoryPercent", "User", "Bitness", "Service", "ParentPID", "Ancestor"])?;
         
         for process in processes {
             let output = ProcessOutput::from(*process);
-            wtr.write_record(&[
+            wtr.write_record([
                 &output.pid,
                 &output.name,
                 &output.title,
                 &output.memory_usage.to_string(),
                 &format!("{:.2}", output.memory_usage_mb),
                 &output.has_window.to_string(),
+                &format!("{:.2}", output.memory_percent),
+                output.user.as_deref().unwrap_or(""),
+                output.bitness.as_deref().unwrap_or(""),
+                output.service.as_deref().unwrap_or(""),
+                &output.parent_pid.map(|pid| pid.to_string()).unwrap_or_default(),
+                &output.ancestor.to_string(),
             ])?;
         }
         
@@ -122,13 +512,13 @@ struct ProcessSimpleStrategy;
 impl OutputStrategy<&ProcessInfo> for ProcessSimpleStrategy {
     fn display(&self, processes: &[&ProcessInfo]) -> AppResult<()> {
         for process in processes {
-            let memory_mb = process.memory_usage as f64 / 1024.0 / 1024.0;
-            println!(
-                "{}: {} ({} MB) - {}",
+            outln!(
+                "{}: {} ({}) - {}{}",
                 process.pid,
-                process.name,
-                format!("{:.1}", memory_mb),
-                if process.has_window { "Has Window" } else { "No Window" }
+                display_text(&process.name),
+                format_memory(process.memory_usage as f64, 1),
+                if process.has_window { "Has Window" } else { "No Window" },
+                if process.ancestor { " (ancestor)" } else { "" }
             );
         }
         Ok(())
@@ -140,15 +530,26 @@ struct ProcessDetailedStrategy;
 impl OutputStrategy<&ProcessInfo> for ProcessDetailedStrategy {
     fn display(&self, processes: &[&ProcessInfo]) -> AppResult<()> {
         for (i, process) in processes.iter().enumerate() {
-            let memory_mb = process.memory_usage as f64 / 1024.0 / 1024.0;
-            println!("Process #{}:", i + 1);
-            println!("  PID:          {}", process.pid);
-            println!("  Name:         {}", process.name);
-            println!("  Title:        {}", process.title);
-            println!("  Memory:       {:.2} MB", memory_mb);
-            println!("  Raw Memory:   {} bytes", process.memory_usage);
-            println!("  Has Window:   {}", if process.has_window { "Yes" } else { "No" });
-            println!();
+            outln!("Process #{}:", i + 1);
+            outln!("  PID:          {}", process.pid);
+            outln!("  Name:         {}", display_text(&process.name));
+            outln!("  Title:        {}", display_text(&process.title));
+            outln!("  Memory:       {}", format_memory(process.memory_usage as f64, 2));
+            outln!("  Raw Memory:   {} bytes", process.memory_usage);
+            outln!("  Memory %:     {:.2}%", process.memory_percent);
+            outln!("  Has Window:   {}", if process.has_window { "Yes" } else { "No" });
+            outln!("  User:         {}", display_text(process.user.as_deref().unwrap_or("Unknown")));
+            outln!("  Bitness:      {}", process.bitness.as_deref().unwrap_or("Unknown"));
+            if let Some(service) = &process.service {
+                outln!("  Service:      {}", display_text(service));
+            }
+            if let Some(parent_pid) = process.parent_pid {
+                outln!("  Parent PID:   {}", parent_pid);
+            }
+            if process.ancestor {
+                outln!("  Ancestor:     Yes");
+            }
+            outln!();
         }
         Ok(())
     }
@@ -156,23 +557,24 @@ impl OutputStrategy<&ProcessInfo> for ProcessDetailedStrategy {
 
 // 窗口信息输出策略
 struct WindowTableStrategy<'a> {
-    process_names: &'a [(u32, String)],
+    process_names: &'a ProcessNameIndex,
 }
 
 impl<'a> OutputStrategy<WindowInfo> for WindowTableStrategy<'a> {
     fn display(&self, windows: &[WindowInfo]) -> AppResult<()> {
-        println!("Found {} windows:", windows.len());
-        println!("{:<8} {:<20} {:<30} {:<15} {:<12}", 
+        outln!("Found {} windows:", windows.len());
+        let tw = flex_width(30, 57);
+        outln!("{:<8} {:<20} {:<tw$} {:<15} {:<12}", 
                  "PID", "Name", "Title", "Size", "Position");
         
         for window in windows {
             let process_name = self.get_process_name(window.pid);
             
-            println!(
-                "{:<8} {:<20} {:<30} {:<8}x{:<6} +{}+{}",
+            outln!(
+                "{:<8} {} {} {:<8}x{:<6} +{}+{}",
                 window.pid,
-                truncate_string(process_name, 18),
-                truncate_string(&window.title, 28),
+                fit(process_name, 20),
+                fit(&window.title, tw),
                 window.rect.width,
                 window.rect.height,
                 window.rect.x,
@@ -186,86 +588,69 @@ impl<'a> OutputStrategy<WindowInfo> for WindowTableStrategy<'a> {
 
 impl<'a> WindowTableStrategy<'a> {
     fn get_process_name(&self, pid: u32) -> &str {
-        self.process_names
-            .iter()
-            .find(|(process_pid, _)| *process_pid == pid)
-            .map(|(_, name)| name.as_str())
-            .unwrap_or("Unknown")
+        self.process_names.get(pid).unwrap_or("Unknown")
     }
 }
 
 struct WindowJsonStrategy<'a> {
-    process_names: &'a [(u32, String)],
+    process_names: &'a ProcessNameIndex,
+    nested_rect: bool,
 }
 
 impl<'a> OutputStrategy<WindowInfo> for WindowJsonStrategy<'a> {
     fn display(&self, windows: &[WindowInfo]) -> AppResult<()> {
-        let output: Vec<WindowOutput> = windows.iter()
-            .map(|window| {
-                let mut output = WindowOutput::from(window);
-                output.name = self.get_process_name(window.pid);
-                output
-            })
+        let output: Vec<WindowRecord> = windows.iter()
+            .map(|window| WindowRecord::new(window, &self.get_process_name(window.pid), self.nested_rect))
             .collect();
-        let json = serde_json::to_string_pretty(&output)?;
-        println!("{}", json);
+        let json = to_json(&output)?;
+        outln!("{}", json);
         Ok(())
     }
 }
 
 impl<'a> WindowJsonStrategy<'a> {
     fn get_process_name(&self, pid: u32) -> String {
-        self.process_names
-            .iter()
-            .find(|(process_pid, _)| *process_pid == pid)
-            .map(|(_, name)| name.clone())
-            .unwrap_or_else(|| "Unknown".to_string())
+        self.process_names.get(pid).unwrap_or("Unknown").to_string()
     }
 }
 
 struct WindowYamlStrategy<'a> {
-    process_names: &'a [(u32, String)],
+    process_names: &'a ProcessNameIndex,
+    nested_rect: bool,
 }
 
 impl<'a> OutputStrategy<WindowInfo> for WindowYamlStrategy<'a> {
     fn display(&self, windows: &[WindowInfo]) -> AppResult<()> {
-        let output: Vec<WindowOutput> = windows.iter()
-            .map(|window| {
-                let mut output = WindowOutput::from(window);
-                output.name = self.get_process_name(window.pid);
-                output
-            })
+        let output: Vec<WindowRecord> = windows.iter()
+            .map(|window| WindowRecord::new(window, &self.get_process_name(window.pid), self.nested_rect))
             .collect();
         let yaml = serde_yaml::to_string(&output)?;
-        println!("{}", yaml);
+        outln!("{}", yaml);
         Ok(())
     }
 }
 
 impl<'a> WindowYamlStrategy<'a> {
     fn get_process_name(&self, pid: u32) -> String {
-        self.process_names
-            .iter()
-            .find(|(process_pid, _)| *process_pid == pid)
-            .map(|(_, name)| name.clone())
-            .unwrap_or_else(|| "Unknown".to_string())
+        self.process_names.get(pid).unwrap_or("Unknown").to_string()
     }
 }
 
 struct WindowCsvStrategy<'a> {
-    process_names: &'a [(u32, String)],
+    process_names: &'a ProcessNameIndex,
+    delimiter: u8,
 }
 
 impl<'a> OutputStrategy<WindowInfo> for WindowCsvStrategy<'a> {
     fn display(&self, windows: &[WindowInfo]) -> AppResult<()> {
-        let mut wtr = csv::Writer::from_writer(std::io::stdout());
+        let mut wtr = delimited_writer(self.delimiter);
         
-        wtr.write_record(&["PID", "Name", "Title", "X", "Y", "Width", "Height", "Dimensions"])?;
+        wtr.write_record(["PID", "Name", "Title", "X", "Y", "Width", "Height", "Dimensions", "Desktop", "Responding"])?;
         
         for window in windows {
             let process_name = self.get_process_name(window.pid);
             
-            wtr.write_record(&[
+            wtr.write_record([
                 &window.pid.to_string(),
                 process_name,
                 &window.title,
@@ -274,6 +659,8 @@ impl<'a> OutputStrategy<WindowInfo> for WindowCsvStrategy<'a> {
                 &window.rect.width.to_string(),
                 &window.rect.height.to_string(),
                 &window.rect.to_string(),
+                &window.desktop.map(|d| d.to_string()).unwrap_or_default(),
+                &window.responding.map(|r| r.to_string()).unwrap_or_default(),
             ])?;
         }
         
@@ -284,16 +671,12 @@ impl<'a> OutputStrategy<WindowInfo> for WindowCsvStrategy<'a> {
 
 impl<'a> WindowCsvStrategy<'a> {
     fn get_process_name(&self, pid: u32) -> &str {
-        self.process_names
-            .iter()
-            .find(|(process_pid, _)| *process_pid == pid)
-            .map(|(_, name)| name.as_str())
-            .unwrap_or("Unknown")
+        self.process_names.get(pid).unwrap_or("Unknown")
     }
 }
 
 struct WindowSimpleStrategy<'a> {
-    process_names: &'a [(u32, String)],
+    process_names: &'a ProcessNameIndex,
 }
 
 impl<'a> OutputStrategy<WindowInfo> for WindowSimpleStrategy<'a> {
@@ -301,11 +684,11 @@ impl<'a> OutputStrategy<WindowInfo> for WindowSimpleStrategy<'a> {
         for window in windows {
             let process_name = self.get_process_name(window.pid);
             
-            println!(
+            outln!(
                 "{}: {} - {} ({}x{} at +{}+{})",
                 window.pid,
-                process_name,
-                window.title,
+                display_text(process_name),
+                display_text(&window.title),
                 window.rect.width,
                 window.rect.height,
                 window.rect.x,
@@ -318,16 +701,12 @@ impl<'a> OutputStrategy<WindowInfo> for WindowSimpleStrategy<'a> {
 
 impl<'a> WindowSimpleStrategy<'a> {
     fn get_process_name(&self, pid: u32) -> &str {
-        self.process_names
-            .iter()
-            .find(|(process_pid, _)| *process_pid == pid)
-            .map(|(_, name)| name.as_str())
-            .unwrap_or("Unknown")
+        self.process_names.get(pid).unwrap_or("Unknown")
     }
 }
 
 struct WindowDetailedStrategy<'a> {
-    process_names: &'a [(u32, String)],
+    process_names: &'a ProcessNameIndex,
 }
 
 impl<'a> OutputStrategy<WindowInfo> for WindowDetailedStrategy<'a> {
@@ -335,14 +714,23 @@ impl<'a> OutputStrategy<WindowInfo> for WindowDetailedStrategy<'a> {
         for (i, window) in windows.iter().enumerate() {
             let process_name = self.get_process_name(window.pid);
             
-            println!("Window #{}:", i + 1);
-            println!("  PID:        {}", window.pid);
-            println!("  Name:       {}", process_name);
-            println!("  Title:      {}", window.title);
-            println!("  Size:       {}x{}", window.rect.width, window.rect.height);
-            println!("  Position:   +{}+{}", window.rect.x, window.rect.y);
-            println!("  Dimensions: {}", window.rect.to_string());
-            println!();
+            outln!("Window #{}:", i + 1);
+            outln!("  PID:        {}", window.pid);
+            outln!("  Name:       {}", display_text(process_name));
+            outln!("  Title:      {}", display_text(&window.title));
+            outln!("  Size:       {}x{}", window.rect.width, window.rect.height);
+            outln!("  Position:   +{}+{}", window.rect.x, window.rect.y);
+            outln!("  Dimensions: {}", window.rect);
+            if let Some(service) = self.process_names.service(window.pid) {
+                outln!("  Service:    {}", display_text(service));
+            }
+            if let Some(desktop) = window.desktop {
+                outln!("  Desktop:    {}", desktop);
+            }
+            if let Some(responding) = window.responding {
+                outln!("  Responding: {}", if responding { "Yes" } else { "No" });
+            }
+            outln!();
         }
         Ok(())
     }
@@ -350,57 +738,493 @@ impl<'a> OutputStrategy<WindowInfo> for WindowDetailedStrategy<'a> {
 
 impl<'a> WindowDetailedStrategy<'a> {
     fn get_process_name(&self, pid: u32) -> &str {
-        self.process_names
-            .iter()
-            .find(|(process_pid, _)| *process_pid == pid)
-            .map(|(_, name)| name.as_str())
-            .unwrap_or("Unknown")
+        self.process_names.get(pid).unwrap_or("Unknown")
     }
 }
 
 // 公共接口函数
+/// 输出进程列表；`deltas` 为 `--watch` 时的内存变化，只在表格格式中显示
 pub fn display_processes(
     processes: &[&ProcessInfo], 
     format: OutputFormat,
-    verbose: bool
+    verbose: bool,
+    deltas: Option<&[i64]>,
 ) -> AppResult<()> {
+    // --strip-control 时先清理标题，对所有格式生效
+    let cleaned: Vec<ProcessInfo>;
+    let cleaned_refs: Vec<&ProcessInfo>;
+    let processes = if STRIP_CONTROL.load(Ordering::Relaxed) {
+        cleaned = processes
+            .iter()
+            .map(|p| ProcessInfo { title: strip_control(&p.title).into_owned(), ..(*p).clone() })
+            .collect();
+        cleaned_refs = cleaned.iter().collect();
+        cleaned_refs.as_slice()
+    } else {
+        processes
+    };
     match format {
-        OutputFormat::Table => ProcessTableStrategy { verbose }.display(processes),
+        OutputFormat::Auto | OutputFormat::Table => ProcessTableStrategy { verbose, deltas }.display(processes),
         OutputFormat::Json => ProcessJsonStrategy.display(processes),
         OutputFormat::Yaml => ProcessYamlStrategy.display(processes),
-        OutputFormat::Csv => ProcessCsvStrategy.display(processes),
+        OutputFormat::Csv | OutputFormat::Tsv => ProcessCsvStrategy { delimiter: format.delimiter() }.display(processes),
         OutputFormat::Simple => ProcessSimpleStrategy.display(processes),
         OutputFormat::Detailed => ProcessDetailedStrategy.display(processes),
     }
 }
 
+/// 输出窗口列表；`nested_rect` 为 true 时 JSON/YAML 将位置和尺寸嵌套在 `rect` 对象中
 pub fn display_windows(
     windows: &[WindowInfo],
-    process_names: &[(u32, String)],
+    process_names: &ProcessNameIndex,
     format: OutputFormat,
+    nested_rect: bool,
 ) -> AppResult<()> {
+    let cleaned: Vec<WindowInfo>;
+    let windows = if STRIP_CONTROL.load(Ordering::Relaxed) {
+        cleaned = windows
+            .iter()
+            .map(|w| WindowInfo { title: strip_control(&w.title).into_owned(), ..w.clone() })
+            .collect();
+        cleaned.as_slice()
+    } else {
+        windows
+    };
     match format {
-        OutputFormat::Table => WindowTableStrategy { process_names }.display(windows),
-        OutputFormat::Json => WindowJsonStrategy { process_names }.display(windows),
-        OutputFormat::Yaml => WindowYamlStrategy { process_names }.display(windows),
-        OutputFormat::Csv => WindowCsvStrategy { process_names }.display(windows),
+        OutputFormat::Auto | OutputFormat::Table => WindowTableStrategy { process_names }.display(windows),
+        OutputFormat::Json => WindowJsonStrategy { process_names, nested_rect }.display(windows),
+        OutputFormat::Yaml => WindowYamlStrategy { process_names, nested_rect }.display(windows),
+        OutputFormat::Csv | OutputFormat::Tsv => WindowCsvStrategy { process_names, delimiter: format.delimiter() }.display(windows),
         OutputFormat::Simple => WindowSimpleStrategy { process_names }.display(windows),
         OutputFormat::Detailed => WindowDetailedStrategy { process_names }.display(windows),
     }
 }
 
-// 通用的字符串截断函数
+/// 输出按进程汇总的窗口数量
+pub fn display_window_summary(summary: &[WindowCount], format: OutputFormat) -> AppResult<()> {
+    match format {
+        OutputFormat::Auto | OutputFormat::Table => {
+            let total: usize = summary.iter().map(|s| s.count).sum();
+            outln!("Found {} windows in {} processes:", total, summary.len());
+            let nw = flex_width(30, 9);
+            outln!("{:<nw$} {:>8}", "Name", "Windows");
+            for entry in summary {
+                outln!("{} {:>8}", fit(&entry.name, nw), entry.count);
+            }
+        }
+        OutputFormat::Json => outln!("{}", to_json(summary)?),
+        OutputFormat::Yaml => outln!("{}", serde_yaml::to_string(summary)?),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut wtr = delimited_writer(format.delimiter());
+            wtr.write_record(["Name", "Count"])?;
+            for entry in summary {
+                wtr.write_record([entry.name.as_str(), &entry.count.to_string()])?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Simple | OutputFormat::Detailed => {
+            for entry in summary {
+                outln!("{}: {} windows", display_text(&entry.name), entry.count);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 输出按进程名汇总的进程数量和内存占用
+pub fn display_process_name_summary(summary: &[ProcessNameCount], format: OutputFormat) -> AppResult<()> {
+    match format {
+        OutputFormat::Auto | OutputFormat::Table => {
+            let total: usize = summary.iter().map(|s| s.count).sum();
+            outln!("Found {} processes with {} distinct names:", total, summary.len());
+            let nw = flex_width(30, 24);
+            outln!("{:<nw$} {:>8} {:>14}", "Name", "Count", "Memory");
+            for entry in summary {
+                outln!("{} {:>8} {:>14}", fit(&entry.name, nw), entry.count, format_memory(entry.total_memory_mb * 1024.0 * 1024.0, 2));
+            }
+        }
+        OutputFormat::Json => outln!("{}", to_json(summary)?),
+        OutputFormat::Yaml => outln!("{}", serde_yaml::to_string(summary)?),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut wtr = delimited_writer(format.delimiter());
+            wtr.write_record(["Name", "Count", "Total Memory (MB)"])?;
+            for entry in summary {
+                wtr.write_record([entry.name.as_str(), &entry.count.to_string(), &format!("{:.2}", entry.total_memory_mb)])?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Simple | OutputFormat::Detailed => {
+            for entry in summary {
+                outln!("{}: {} processes, {}", display_text(&entry.name), entry.count, format_memory(entry.total_memory_mb * 1024.0 * 1024.0, 2));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 输出每个显示器上的窗口数量
+pub fn display_monitor_counts(counts: &[MonitorWindowCount], format: OutputFormat) -> AppResult<()> {
+    let label = |c: &MonitorWindowCount| c.monitor.map_or("-".to_string(), |n| n.to_string());
+    let bounds = |c: &MonitorWindowCount| c.bounds.as_ref().map_or("(off-screen)".to_string(), |b| b.to_string());
+    match format {
+        OutputFormat::Auto | OutputFormat::Table => {
+            let total: usize = counts.iter().map(|c| c.count).sum();
+            outln!("Found {} windows on {} monitors:", total, counts.iter().filter(|c| c.monitor.is_some()).count());
+            outln!("{:<8} {:<24} {:>8}", "Monitor", "Bounds", "Windows");
+            for entry in counts {
+                outln!("{:<8} {:<24} {:>8}", label(entry), bounds(entry), entry.count);
+            }
+        }
+        OutputFormat::Json => outln!("{}", to_json(counts)?),
+        OutputFormat::Yaml => outln!("{}", serde_yaml::to_string(counts)?),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut wtr = delimited_writer(format.delimiter());
+            wtr.write_record(["Monitor", "X", "Y", "Width", "Height", "Count"])?;
+            for entry in counts {
+                let rect = entry.bounds.as_ref().map_or([String::new(), String::new(), String::new(), String::new()], |b| {
+                    [b.x.to_string(), b.y.to_string(), b.width.to_string(), b.height.to_string()]
+                });
+                let monitor = entry.monitor.map(|n| n.to_string()).unwrap_or_default();
+                wtr.write_record([monitor.as_str(), &rect[0], &rect[1], &rect[2], &rect[3], &entry.count.to_string()])?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Simple | OutputFormat::Detailed => {
+            for entry in counts {
+                outln!("Monitor {} ({}): {} windows", label(entry), bounds(entry), entry.count);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 输出窗口状态（`windows/state`）；无法查询的属性显示为 `-`（CSV 中为空）
+pub fn display_window_states(states: &[WindowStateRecord], format: OutputFormat) -> AppResult<()> {
+    let yes_no = |value: bool| if value { "yes" } else { "no" };
+    let state = |s: &WindowStateRecord| s.state.map(|state| state.to_string());
+    let topmost = |s: &WindowStateRecord| s.topmost.map(|topmost| yes_no(topmost).to_string());
+    let opacity = |s: &WindowStateRecord| s.opacity.map(|opacity| format!("{}%", opacity));
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    match format {
+        OutputFormat::Auto | OutputFormat::Table => {
+            outln!("Found {} windows:", states.len());
+            let tw = flex_width(40, 49);
+            outln!("{:<8} {:<tw$} {:<10} {:<8} {:>7} Responding", "PID", "Title", "State", "Topmost", "Opacity");
+            for s in states {
+                outln!("{:<8} {} {:<10} {:<8} {:>7} {}",
+                    s.pid, fit(&s.title, tw), or_dash(state(s)), or_dash(topmost(s)), or_dash(opacity(s)), yes_no(s.responding));
+            }
+        }
+        OutputFormat::Json => outln!("{}", to_json(states)?),
+        OutputFormat::Yaml => outln!("{}", serde_yaml::to_string(states)?),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut wtr = delimited_writer(format.delimiter());
+            wtr.write_record(["PID", "Title", "State", "Topmost", "Opacity", "Responding"])?;
+            for s in states {
+                let opacity = s.opacity.map(|o| o.to_string()).unwrap_or_default();
+                let topmost = s.topmost.map(|t| t.to_string()).unwrap_or_default();
+                wtr.write_record([
+                    s.pid.to_string(), s.title.clone(), state(s).unwrap_or_default(), topmost, opacity, s.responding.to_string(),
+                ])?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Simple | OutputFormat::Detailed => {
+            for s in states {
+                outln!("{} (PID: {}): {}, topmost {}, opacity {}, responding {}",
+                    display_text(&s.title), s.pid, or_dash(state(s)), or_dash(topmost(s)), or_dash(opacity(s)), yes_no(s.responding));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 单个窗口的操作结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationStatus {
+    /// 操作成功
+    Ok,
+    /// 窗口已处于目标状态，未修改
+    Unchanged,
+    /// 仅预览（--dry-run），未修改
+    DryRun,
+    /// 窗口未响应，按 --skip-hung 跳过
+    Skipped,
+    /// 操作失败
+    Failed,
+}
+
+/// 单个窗口的操作结果（`--format json|yaml|csv` 时输出）
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationResult {
+    pub pid: u32,
+    pub title: String,
+    pub operation: &'static str,
+    pub success: bool,
+    pub error: Option<String>,
+    /// 没有修改窗口时的说明："unchanged"、"dry_run" 或 "skipped"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<&'static str>,
+    /// 从 `throttle` 之后到记录结果的耗时（毫秒，微秒精度）；未执行操作（如跳过）时省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+    #[serde(skip)]
+    status: OperationStatus,
+}
+
+struct OperationJsonStrategy;
+
+impl OutputStrategy<OperationResult> for OperationJsonStrategy {
+    fn display(&self, results: &[OperationResult]) -> AppResult<()> {
+        outln!("{}", to_json(results)?);
+        Ok(())
+    }
+}
+
+struct OperationYamlStrategy;
+
+impl OutputStrategy<OperationResult> for OperationYamlStrategy {
+    fn display(&self, results: &[OperationResult]) -> AppResult<()> {
+        outln!("{}", serde_yaml::to_string(results)?);
+        Ok(())
+    }
+}
+
+struct OperationCsvStrategy {
+    delimiter: u8,
+}
+
+impl OutputStrategy<OperationResult> for OperationCsvStrategy {
+    fn display(&self, results: &[OperationResult]) -> AppResult<()> {
+        let mut wtr = delimited_writer(self.delimiter);
+        wtr.write_record(["PID", "Title", "Operation", "Success", "Error", "Note", "DurationMs"])?;
+        for result in results {
+            wtr.write_record([
+                &result.pid.to_string(),
+                &result.title,
+                result.operation,
+                &result.success.to_string(),
+                result.error.as_deref().unwrap_or(""),
+                result.note.unwrap_or(""),
+                &result.duration_ms.map(|ms| ms.to_string()).unwrap_or_default(),
+            ])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+/// 窗口操作命令共用的执行选项，由各命令的 `parse_cli` 解析后传给 `OperationReport`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationOptions {
+    /// 结束时把每个窗口的操作耗时输出到 stderr（--profile）
+    pub profile: bool,
+    /// 相邻两次窗口操作之间的等待时间（毫秒，全局参数 --delay）
+    pub delay: u64,
+    /// 操作前跳过未响应的窗口（全局参数 --skip-hung）
+    pub skip_hung: bool,
+}
+
+impl OperationOptions {
+    pub fn from_matches(matches: &clap::ArgMatches) -> Self {
+        Self {
+            profile: matches.get_flag("profile"),
+            delay: matches.get_one::<u64>("delay").copied().unwrap_or(0),
+            skip_hung: matches.get_flag("skip_hung"),
+        }
+    }
+}
+
+/// 窗口操作命令的结果：表格/简单/详细格式下逐行打印，json/yaml/csv 格式下在结束时输出所有结果
+pub struct OperationReport {
+    operation: &'static str,
+    format: OutputFormat,
+    options: OperationOptions,
+    results: Vec<OperationResult>,
+    /// 是否已经执行过窗口操作（第一次操作前不等待）
+    started: bool,
+    /// 当前窗口操作的开始时间，记录结果时计算耗时
+    window_start: Option<std::time::Instant>,
+}
+
+impl OperationReport {
+    pub fn new(operation: &'static str, format: OutputFormat, options: OperationOptions) -> Self {
+        Self { operation, format, options, results: Vec::new(), started: false, window_start: None }
+    }
+
+    /// 在每次窗口操作前调用：除第一次外，先等待 `--delay` 指定的时间，避免批量操作时桌面闪烁；
+    /// 等待之后开始计时，结构化结果中的 `duration_ms` 不含等待时间
+    pub fn throttle(&mut self) {
+        if self.started && self.options.delay > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(self.options.delay));
+        }
+        self.started = true;
+        self.window_start = Some(std::time::Instant::now());
+    }
+
+    /// 指定 `--skip-hung` 且窗口未响应时记录为跳过并返回 true，调用方应跳过该窗口，
+    /// 避免一个挂起的应用阻塞整个批量操作
+    pub fn skip_hung(&mut self, window: &WindowHandle) -> bool {
+        if !self.options.skip_hung || window.is_responding() {
+            return false;
+        }
+        if self.is_text() {
+            eprintln!("{}", clean_title(&format!("Skipped: {} (PID: {}) - window is not responding", window.title, window.pid)));
+        }
+        self.push(window.pid, &window.title, OperationStatus::Skipped, Some("window is not responding".to_string()));
+        true
+    }
+
+    /// 窗口操作命令共用的 `--format` 参数
+    pub fn format_arg() -> clap::Arg {
+        clap::Arg::new("format")
+            .short('f')
+            .long("format")
+            .value_name("FORMAT")
+            .value_parser(clap::value_parser!(OutputFormat))
+            .default_value("auto")
+            .help("Output format (auto: table on a terminal, json when piped); json, yaml, csv and tsv print one result record per window instead of progress lines")
+    }
+
+    /// 窗口操作命令共用的 `--profile` 参数
+    pub fn profile_arg() -> clap::Arg {
+        clap::Arg::new("profile")
+            .long("profile")
+            .action(clap::ArgAction::SetTrue)
+            .help("Print per-window operation latency to stderr")
+    }
+
+    /// 是否逐行输出人类可读文本
+    fn is_text(&self) -> bool {
+        matches!(self.format, OutputFormat::Auto | OutputFormat::Table | OutputFormat::Simple | OutputFormat::Detailed)
+    }
+
+    fn push(&mut self, pid: u32, title: &str, status: OperationStatus, error: Option<String>) {
+        let note = match status {
+            OperationStatus::Unchanged => Some("unchanged"),
+            OperationStatus::DryRun => Some("dry_run"),
+            OperationStatus::Skipped => Some("skipped"),
+            OperationStatus::Ok | OperationStatus::Failed => None,
+        };
+        self.results.push(OperationResult {
+            pid,
+            title: clean_title(title).into_owned(),
+            operation: self.operation,
+            success: !matches!(status, OperationStatus::Failed | OperationStatus::Skipped),
+            error,
+            note,
+            duration_ms: self.window_start.take().map(|start| start.elapsed().as_micros() as f64 / 1000.0),
+            status,
+        });
+    }
+
+    /// 记录一个窗口的成功结果；文本格式下立即打印 `message`
+    pub fn record(&mut self, pid: u32, title: &str, status: OperationStatus, message: String) -> AppResult<()> {
+        if self.is_text() {
+            outln!("{}", clean_title(&message));
+        }
+        self.push(pid, title, status, None);
+        Ok(())
+    }
+
+    /// 记录一个窗口的失败结果；文本格式下立即把 `message` 输出到 stderr
+    pub fn fail(&mut self, pid: u32, title: &str, error: impl std::fmt::Display, message: String) {
+        if self.is_text() {
+            eprintln!("{}", clean_title(&message));
+        }
+        self.push(pid, title, OperationStatus::Failed, Some(error.to_string()));
+    }
+
+    /// 指定状态的结果数量
+    pub fn count(&self, status: OperationStatus) -> usize {
+        self.results.iter().filter(|result| result.status == status).count()
+    }
+
+    /// 成功修改的窗口数量
+    pub fn modified(&self) -> usize {
+        self.count(OperationStatus::Ok)
+    }
+
+    /// 输出不属于单个窗口结果的进度信息，仅文本格式下输出
+    pub fn progress(&self, message: &str) -> AppResult<()> {
+        if self.is_text() {
+            outln!("{}", clean_title(message));
+        }
+        Ok(())
+    }
+
+    /// `--profile` 的耗时汇总：每个已执行窗口一行，最后一行为合计；没有计时结果时为空
+    fn profile_summary(&self) -> Vec<String> {
+        let timed: Vec<(&OperationResult, f64)> = self.results
+            .iter()
+            .filter_map(|result| result.duration_ms.map(|ms| (result, ms)))
+            .collect();
+        if timed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lines = vec![format!("Profile ({}):", self.operation)];
+        for (result, ms) in &timed {
+            lines.push(format!("  {:>10.3} ms  {} (PID: {})", ms, result.title, result.pid));
+        }
+        let total: f64 = timed.iter().map(|(_, ms)| ms).sum();
+        let max = timed.iter().map(|(_, ms)| *ms).fold(0.0, f64::max);
+        lines.push(format!(
+            "  total {:.3} ms, avg {:.3} ms, max {:.3} ms over {} call(s)",
+            total,
+            total / timed.len() as f64,
+            max,
+            timed.len()
+        ));
+        lines
+    }
+
+    /// 结束报告：文本格式下输出汇总，其余格式输出所有结果；
+    /// 所有窗口都失败时返回 `NoWindowsModified`（结构化结果仍会先输出）
+    pub fn finish(self, summary: String) -> AppResult<()> {
+        let succeeded = self.results.iter().any(|result| result.success);
+        if self.options.profile {
+            for line in self.profile_summary() {
+                eprintln!("{}", line);
+            }
+        }
+        match self.format {
+            OutputFormat::Json => OperationJsonStrategy.display(&self.results)?,
+            OutputFormat::Yaml => OperationYamlStrategy.display(&self.results)?,
+            OutputFormat::Csv | OutputFormat::Tsv => OperationCsvStrategy { delimiter: self.format.delimiter() }.display(&self.results)?,
+            OutputFormat::Auto | OutputFormat::Table | OutputFormat::Simple | OutputFormat::Detailed => {
+                if succeeded {
+                    outln!("{}", summary);
+                }
+                let skipped = self.count(OperationStatus::Skipped);
+                if skipped > 0 {
+                    eprintln!("Skipped {} non-responding window(s)", skipped);
+                }
+            }
+        }
+
+        if succeeded {
+            Ok(())
+        } else {
+            Err(AppError::NoWindowsModified)
+        }
+    }
+}
+
+// 通用的字符串截断函数，`max_length` 为终端显示宽度（中日韩等宽字符占两列）
 pub fn truncate_string(s: &str, max_length: usize) -> String {
-    if s.chars().count() <= max_length {
+    if s.width() <= max_length {
         s.to_string()
     } else {
         let mut result = String::new();
-        let mut count = 0;
+        let mut width = 0;
         
         for c in s.chars() {
-            if count + c.len_utf8() <= max_length.saturating_sub(3) {
+            // 按显示宽度累计，宽字符放不下时整个舍弃，避免超出列宽
+            let w = c.width().unwrap_or(0);
+            if width + w <= max_length.saturating_sub(3) {
                 result.push(c);
-                count += 1;
+                width += w;
             } else {
                 break;
             }
@@ -408,4 +1232,182 @@ pub fn truncate_string(s: &str, max_length: usize) -> String {
         
         format!("{}...", result)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WindowRect;
+
+    #[test]
+    fn test_tee_duplicates_writes() {
+        use std::io::Write;
+        let mut primary = Vec::new();
+        let mut secondary = Vec::new();
+        {
+            let mut tee = Tee::new(&mut primary, Some(&mut secondary));
+            writeln!(tee, "PID {}", 42).unwrap();
+        }
+        assert_eq!(primary, b"PID 42\n");
+        assert_eq!(secondary, b"PID 42\n");
+
+        let mut only = Vec::new();
+        writeln!(Tee::new(&mut only, None::<Vec<u8>>), "x").unwrap();
+        assert_eq!(only, b"x\n");
+    }
+
+    #[test]
+    fn test_flat_window_record() {
+        let window = WindowInfo { desktop: Some(1), ..WindowInfo::for_test(42, "Editor", WindowRect::new(10, 20, 800, 600)) };
+
+        // 扁平记录的每个字段都是标量
+        let flat = serde_json::to_value(WindowRecord::new(&window, "code", false)).unwrap();
+        let fields = flat.as_object().unwrap();
+        assert!(fields.values().all(|v| !v.is_object() && !v.is_array()));
+        assert_eq!(fields["x"], 10);
+        assert_eq!(fields["height"], 600);
+        // 未探测响应状态时不输出该字段
+        assert!(!fields.contains_key("responding"));
+
+        let nested = serde_json::to_value(WindowRecord::new(&window, "code", true)).unwrap();
+        assert!(nested["rect"].is_object());
+    }
+
+    #[test]
+    fn test_window_state_serialize() {
+        use crate::types::WindowShowState;
+        let state = WindowStateRecord {
+            pid: 7,
+            title: "Editor".to_string(),
+            state: Some(WindowShowState::Minimized),
+            topmost: Some(false),
+            opacity: None,
+            responding: true,
+        };
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["state"], "minimized");
+        assert!(json["opacity"].is_null());
+        assert_eq!(WindowShowState::Maximized.to_string(), "maximized");
+    }
+
+    #[test]
+    fn test_operation_results_serialize() {
+        let mut report = OperationReport::new("minimize", OutputFormat::Json, OperationOptions::default());
+        report.throttle();
+        report.push(1, "A", OperationStatus::Ok, None);
+        report.push(2, "B", OperationStatus::Unchanged, None);
+        report.push(3, "C", OperationStatus::Failed, Some("Access denied".into()));
+        report.push(4, "D", OperationStatus::Skipped, Some("window is not responding".into()));
+        assert_eq!(report.modified(), 1);
+
+        let json: serde_json::Value = serde_json::to_value(&report.results).unwrap();
+        assert_eq!(json[0]["operation"], "minimize");
+        assert_eq!(json[0]["success"], true);
+        assert!(json[0]["error"].is_null());
+        assert!(json[0].get("note").is_none());
+        assert_eq!(json[1]["note"], "unchanged");
+        assert_eq!(json[2]["success"], false);
+        assert_eq!(json[2]["error"], "Access denied");
+        assert_eq!(json[3]["success"], false);
+        assert_eq!(json[3]["note"], "skipped");
+        // 只有 throttle 之后执行的操作才有耗时
+        assert!(json[0]["duration_ms"].as_f64().is_some_and(|ms| ms >= 0.0));
+        assert!(json[3].get("duration_ms").is_none());
+
+        // --profile 只汇总有耗时的结果
+        let profile = report.profile_summary();
+        assert_eq!(profile.len(), 3);
+        assert_eq!(profile[0], "Profile (minimize):");
+        assert!(profile[1].ends_with("ms  A (PID: 1)"));
+        assert!(profile[2].ends_with("over 1 call(s)"));
+    }
+
+    #[test]
+    fn test_memory_unit_format() {
+        let mb = 1024.0 * 1024.0;
+        assert_eq!(MemoryUnit::Mb.format(1.5 * mb, 2, false), "1.50 MB");
+        assert_eq!(MemoryUnit::B.format(1.5 * mb, 2, false), "1572864");
+        assert_eq!(MemoryUnit::Kb.format(2048.0, 1, false), "2.0 KB");
+        assert_eq!(MemoryUnit::Gb.format(512.0 * mb, 2, false), "0.50 GB");
+        assert_eq!(MemoryUnit::Auto.format(512.0, 2, false), "512 B");
+        assert_eq!(MemoryUnit::Auto.format(3.0 * 1024.0 * mb, 2, false), "3.00 GB");
+        assert_eq!(MemoryUnit::Auto.format(-2.0 * mb, 2, true), "-2.00 MB");
+        assert_eq!(MemoryUnit::B.format(300.0, 2, true), "+300");
+    }
+
+    #[test]
+    fn test_error_record_serialize() {
+        let error = AppError::NoSuchProcess("42".to_string());
+        let record = ErrorRecord { error: error.kind(), message: error.to_string(), exit_code: error.exit_code() };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"error":"no_such_process","message":"No process found with PID 42","exit_code":2}"#
+        );
+        assert_eq!(AppError::invalid_parameter("x").exit_code(), 4);
+    }
+
+    #[test]
+    fn test_strip_control_in_titles() {
+        let title = "Line one\r\nLine two\tend";
+        assert_eq!(strip_control(title), "Line oneLine twoend");
+        assert!(matches!(strip_control("Notepad"), Cow::Borrowed(_)));
+
+        // 不去掉控制字符时，csv 的引号转义同样能保持一条记录
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        wtr.write_record(["1", title]).unwrap();
+        let data = wtr.into_inner().unwrap();
+        let records = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(data.as_slice())
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(&records[0][1], title);
+    }
+
+    #[test]
+    fn test_auto_format_resolves() {
+        assert!(matches!(OutputFormat::Csv.resolve(), OutputFormat::Csv));
+        assert!(matches!(OutputFormat::Auto.resolve(), OutputFormat::Table | OutputFormat::Json));
+    }
+
+    #[test]
+    fn test_tsv_delimiter() {
+        assert_eq!(OutputFormat::Tsv.delimiter(), b'\t');
+        assert_eq!(OutputFormat::Csv.delimiter(), b',');
+
+        // TSV 中含逗号的标题不需要引号
+        let mut wtr = csv::WriterBuilder::new()
+            .delimiter(OutputFormat::Tsv.delimiter())
+            .quote_style(csv::QuoteStyle::Necessary)
+            .from_writer(Vec::new());
+        wtr.write_record(["1", "Hello, world", "a\tb"]).unwrap();
+        assert_eq!(String::from_utf8(wtr.into_inner().unwrap()).unwrap(), "1\tHello, world\t\"a\tb\"\n");
+    }
+
+    #[test]
+    fn test_to_ascii_replaces_non_ascii() {
+        assert_eq!(to_ascii("Notepad"), "Notepad");
+        assert_eq!(to_ascii("记事本 - a.txt"), "??? - a.txt");
+    }
+
+    #[test]
+    fn test_truncate_string_uses_display_width() {
+        assert_eq!(truncate_string("短标题", 10), "短标题");
+        // 宽字符占两列：8 列中留 3 列给省略号，只能放下两个汉字
+        assert_eq!(truncate_string("一二三四五六七八九十", 8), "一二...");
+        assert_eq!(truncate_string("abcdefghij", 8), "abcde...");
+        // 混合宽度：放不下的宽字符整个舍弃
+        assert_eq!(truncate_string("ab记事本cd", 8), "ab记...");
+        assert!(truncate_string("ab记事本cd", 8).width() <= 8);
+    }
+
+    #[test]
+    fn test_pad_end_uses_display_width() {
+        assert_eq!(pad_end("abc", 6), "abc   ");
+        assert_eq!(pad_end("记事本", 8), "记事本  ");
+        assert_eq!(pad_end("a记b", 8).width(), 8);
+        // 超出列宽时不补空格
+        assert_eq!(pad_end("记事本", 4), "记事本");
+    }
+}
@@ -1,30 +1,56 @@
 // src/platform/interface.rs
-use crate::types::WindowInfo;
-use crate::error::{AppError, AppResult};
+use crate::types::{CapturedImage, RectMode, WindowInfo, WindowRect, WindowShowState};
+use crate::error::AppResult;
+use crate::filter::{WindowFilter, ProcessNameIndex};
 
 /// 平台窗口句柄的通用接口
 pub trait PlatformWindow {
     fn minimize(&self) -> AppResult<()>;
     fn maximize(&self) -> AppResult<()>;
     fn restore(&self) -> AppResult<()>;
+    fn hide(&self) -> AppResult<()>;
+    fn show(&self) -> AppResult<()>;
+    /// 请求窗口关闭（与点击关闭按钮相同，应用可以提示保存或拒绝）；目前只有 `tui` 使用
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    fn close(&self) -> AppResult<()>;
+    /// 窗口是否在短时间内响应消息（`--skip-hung` 用于跳过挂起的窗口）
+    fn is_responding(&self) -> bool;
     fn set_position(&self, x: i32, y: i32) -> AppResult<()>;
     fn set_always_on_top(&self, on_top: bool) -> AppResult<()>;
     fn is_always_on_top(&self) -> AppResult<bool>;
     fn set_transparency(&self, opacity: u8) -> AppResult<()>;
+    /// 当前的不透明度百分比；分层窗口的属性无法读取时为 None
+    fn opacity(&self) -> AppResult<Option<u8>>;
+    /// 正常、最小化或最大化
+    fn show_state(&self) -> AppResult<WindowShowState>;
+    /// 还原位置和显示状态（`windows/undo` 保存）；最小化窗口也返回真实的还原位置
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    fn placement(&self) -> AppResult<(WindowRect, WindowShowState)>;
+    /// 恢复 `placement` 返回的还原位置和显示状态
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    fn set_placement(&self, rect: &WindowRect, state: WindowShowState) -> AppResult<()>;
     fn resize(&self, width: i32, height: i32, keep_position: bool, center: bool) -> AppResult<()>;
+    /// 一次调用同时设置位置和尺寸，避免先移动再缩放造成的两次重绘闪烁
+    fn set_bounds(&self, x: i32, y: i32, width: i32, height: i32) -> AppResult<()>;
+    fn capture(&self) -> AppResult<CapturedImage>;
+    /// 窗口图标（RGBA）
+    fn icon(&self) -> AppResult<CapturedImage>;
+    fn get_rect(&self) -> AppResult<WindowRect>;
+    /// 可见边框（不含阴影）的位置和尺寸
+    fn get_frame_rect(&self) -> AppResult<WindowRect>;
+    fn class_name(&self) -> AppResult<String>;
 
 }
 
 /// 平台接口 trait
 #[allow(dead_code)]
 pub trait PlatformInterface {
-    fn get_all_windows_with_size() -> Vec<WindowInfo>;
+    fn get_all_windows_with_size(rect_mode: RectMode, with_desktop: bool) -> Vec<WindowInfo>;
+    fn get_windows_by_pid(pid: u32, rect_mode: RectMode, with_desktop: bool) -> Vec<WindowInfo>;
     fn find_windows(
-        pid_filter: &Option<String>,
-        name_filter: &Option<String>,
-        title_filter: &Option<String>,
-        process_names: &[(u32, String)],
-    ) -> Vec<WindowHandle>;
+        filter: &WindowFilter,
+        process_names: &ProcessNameIndex,
+    ) -> AppResult<Vec<WindowHandle>>;
 }
 
 /// 统一的窗口句柄
@@ -32,13 +58,16 @@ pub trait PlatformInterface {
 pub struct WindowHandle {
     pub pid: u32,
     pub title: String,
+    /// 查找窗口时的位置和尺寸，用于按位置排序
+    pub rect: WindowRect,
     // 平台特定的句柄数据，但不暴露具体类型
     platform_data: PlatformData,
 }
 
 impl WindowHandle {
-    pub fn new(pid: u32, title: String, platform_data: PlatformData) -> Self {
-        Self { pid, title, platform_data }
+    #[allow(dead_code)]
+    pub fn new(pid: u32, title: String, rect: WindowRect, platform_data: PlatformData) -> Self {
+        Self { pid, title, rect, platform_data }
     }
 
     pub fn minimize(&self) -> AppResult<()> {
@@ -53,6 +82,14 @@ impl WindowHandle {
         self.platform_data.restore()
     }
 
+    pub fn hide(&self) -> AppResult<()> {
+        self.platform_data.hide()
+    }
+
+    pub fn show(&self) -> AppResult<()> {
+        self.platform_data.show()
+    }
+
     pub fn set_position(&self, x: i32, y: i32) -> AppResult<()> {
         self.platform_data.set_position(x, y)
     }
@@ -68,13 +105,76 @@ impl WindowHandle {
     pub fn set_transparency(&self, opacity: u8) -> AppResult<()> {
         self.platform_data.set_transparency(opacity)
     }
+
+    pub fn opacity(&self) -> AppResult<Option<u8>> {
+        self.platform_data.opacity()
+    }
+
+    pub fn show_state(&self) -> AppResult<WindowShowState> {
+        self.platform_data.show_state()
+    }
+
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn placement(&self) -> AppResult<(WindowRect, WindowShowState)> {
+        self.platform_data.placement()
+    }
+
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn set_placement(&self, rect: &WindowRect, state: WindowShowState) -> AppResult<()> {
+        self.platform_data.set_placement(rect, state)
+    }
+
+    /// 平台窗口句柄的数值（Windows 上为 HWND，其他平台为 0），用于在多次运行之间识别同一个窗口
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn raw_handle(&self) -> isize {
+        match &self.platform_data {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.hwnd,
+            #[cfg(unix)]
+            PlatformData::Unix(_) => 0,
+        }
+    }
     pub fn resize(&self, width: i32, height: i32, keep_position: bool, center: bool) -> AppResult<()> {
         self.platform_data.resize(width, height, keep_position, center)
     }
+
+    pub fn set_bounds(&self, x: i32, y: i32, width: i32, height: i32) -> AppResult<()> {
+        self.platform_data.set_bounds(x, y, width, height)
+    }
+
+    pub fn capture(&self) -> AppResult<CapturedImage> {
+        self.platform_data.capture()
+    }
+
+    pub fn icon(&self) -> AppResult<CapturedImage> {
+        self.platform_data.icon()
+    }
+
+    pub fn get_rect(&self) -> AppResult<WindowRect> {
+        self.platform_data.get_rect()
+    }
+
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub fn close(&self) -> AppResult<()> {
+        self.platform_data.close()
+    }
+
+    pub fn is_responding(&self) -> bool {
+        self.platform_data.is_responding()
+    }
+
+    pub fn get_frame_rect(&self) -> AppResult<WindowRect> {
+        self.platform_data.get_frame_rect()
+    }
+
+    pub fn class_name(&self) -> AppResult<String> {
+        self.platform_data.class_name()
+    }
 }
 
 /// 平台数据枚举，封装不同平台的实现
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 pub enum PlatformData {
     #[cfg(windows)]
     Windows(crate::platform::windows::WindowsWindowData),
@@ -110,6 +210,42 @@ impl PlatformWindow for PlatformData {
         }
     }
 
+    fn hide(&self) -> AppResult<()> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.hide(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.hide(),
+        }
+    }
+
+    fn show(&self) -> AppResult<()> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.show(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.show(),
+        }
+    }
+
+    fn close(&self) -> AppResult<()> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.close(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.close(),
+        }
+    }
+
+    fn is_responding(&self) -> bool {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.is_responding(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.is_responding(),
+        }
+    }
+
     fn set_position(&self, x: i32, y: i32) -> AppResult<()> {
         match self {
             #[cfg(windows)]
@@ -145,6 +281,42 @@ impl PlatformWindow for PlatformData {
             PlatformData::Unix(data) => data.set_transparency(opacity),
         }
     }
+
+    fn opacity(&self) -> AppResult<Option<u8>> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.opacity(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.opacity(),
+        }
+    }
+
+    fn show_state(&self) -> AppResult<WindowShowState> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.show_state(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.show_state(),
+        }
+    }
+
+    fn placement(&self) -> AppResult<(WindowRect, WindowShowState)> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.placement(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.placement(),
+        }
+    }
+
+    fn set_placement(&self, rect: &WindowRect, state: WindowShowState) -> AppResult<()> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.set_placement(rect, state),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.set_placement(rect, state),
+        }
+    }
     fn resize(&self, width: i32, height: i32, keep_position: bool, center: bool) -> AppResult<()> {
         match self {
             #[cfg(windows)]
@@ -153,4 +325,58 @@ impl PlatformWindow for PlatformData {
             PlatformData::Unix(data) => data.resize(width, height, keep_position, center),
         }
     }
+
+    fn set_bounds(&self, x: i32, y: i32, width: i32, height: i32) -> AppResult<()> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.set_bounds(x, y, width, height),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.set_bounds(x, y, width, height),
+        }
+    }
+
+    fn capture(&self) -> AppResult<CapturedImage> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.capture(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.capture(),
+        }
+    }
+
+    fn icon(&self) -> AppResult<CapturedImage> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.icon(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.icon(),
+        }
+    }
+
+    fn get_rect(&self) -> AppResult<WindowRect> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.get_rect(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.get_rect(),
+        }
+    }
+
+    fn get_frame_rect(&self) -> AppResult<WindowRect> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.get_frame_rect(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.get_frame_rect(),
+        }
+    }
+
+    fn class_name(&self) -> AppResult<String> {
+        match self {
+            #[cfg(windows)]
+            PlatformData::Windows(data) => data.class_name(),
+            #[cfg(unix)]
+            PlatformData::Unix(data) => data.class_name(),
+        }
+    }
 }
\ No newline at end of file
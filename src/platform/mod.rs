@@ -1,39 +1,212 @@
 // src/platform/mod.rs
 mod interface;
 #[cfg(windows)]
-mod windows;
+pub mod windows;
 #[cfg(unix)]
-mod unix;
+pub mod unix;
 
+#[allow(unused_imports)]
 pub use interface::{WindowHandle, PlatformData};
 
+use crate::filter::{WindowFilter, ProcessNameIndex};
+use crate::error::AppResult;
+use crate::types::RectMode;
+
 // 平台特定的实现函数
 #[cfg(windows)]
-pub fn get_all_windows_with_size() -> Vec<crate::types::WindowInfo> {
-    windows::get_all_windows_with_size()
+pub fn get_all_windows_with_size(rect_mode: RectMode, with_desktop: bool) -> Vec<crate::types::WindowInfo> {
+    windows::get_all_windows_with_size(rect_mode, with_desktop)
+}
+
+#[cfg(windows)]
+pub fn get_window_titles() -> Vec<(u32, String)> {
+    windows::get_window_titles()
+}
+
+#[cfg(windows)]
+pub fn get_windows_by_pid(pid: u32, rect_mode: RectMode, with_desktop: bool) -> Vec<crate::types::WindowInfo> {
+    windows::get_windows_by_pid(pid, rect_mode, with_desktop)
+}
+
+#[cfg(windows)]
+pub fn get_windows_by_thread(tid: u32, rect_mode: RectMode, with_desktop: bool) -> AppResult<Vec<crate::types::WindowInfo>> {
+    windows::get_windows_by_thread(tid, rect_mode, with_desktop)
 }
 
 #[cfg(windows)]
 pub fn find_windows(
-    pid_filter: &Option<String>,
-    name_filter: &Option<String>,
-    title_filter: &Option<String>,
-    process_names: &[(u32, String)],
-) -> Vec<WindowHandle> {
-    windows::find_windows(pid_filter, name_filter, title_filter, process_names)
+    filter: &WindowFilter,
+    process_names: &ProcessNameIndex,
+) -> AppResult<Vec<WindowHandle>> {
+    windows::find_windows(filter, process_names)
+}
+
+#[cfg(windows)]
+pub fn get_window_at_cursor(rect_mode: RectMode, with_desktop: bool) -> AppResult<crate::types::WindowInfo> {
+    windows::get_window_at_cursor(rect_mode, with_desktop)
+}
+
+#[cfg(windows)]
+pub fn get_monitor_work_areas() -> AppResult<Vec<crate::types::WindowRect>> {
+    windows::get_monitor_work_areas()
+}
+
+#[cfg(windows)]
+pub fn probe_window_support() -> AppResult<()> {
+    windows::probe_window_support()
+}
+
+#[cfg(windows)]
+pub fn lookup_account_name(user_id: &str) -> Option<String> {
+    windows::lookup_account_name(user_id)
+}
+
+#[cfg(windows)]
+pub fn process_bitness(pid: u32) -> Option<String> {
+    windows::process_bitness(pid)
+}
+
+#[cfg(windows)]
+pub fn process_services() -> std::collections::HashMap<u32, String> {
+    windows::process_services()
+}
+
+#[cfg(windows)]
+pub fn enable_utf8_console() {
+    windows::enable_utf8_console()
+}
+
+#[cfg(windows)]
+pub fn current_virtual_desktop() -> AppResult<u32> {
+    windows::current_virtual_desktop()
+}
+
+#[cfg(windows)]
+pub fn set_full_title(enabled: bool) {
+    windows::set_full_title(enabled)
+}
+
+#[cfg(windows)]
+pub fn set_probe_responding(enabled: bool) {
+    windows::set_probe_responding(enabled)
+}
+
+#[cfg(windows)]
+pub fn set_uia_query(enabled: bool) -> AppResult<()> {
+    windows::set_uia_query(enabled)
+}
+
+#[cfg(windows)]
+pub fn is_elevated() -> Option<bool> {
+    windows::is_elevated()
+}
+
+#[cfg(windows)]
+pub fn dpi_awareness() -> Option<String> {
+    windows::dpi_awareness()
+}
+
+#[cfg(windows)]
+#[cfg_attr(not(feature = "undo"), allow(dead_code))]
+pub fn session_id() -> Option<u32> {
+    windows::session_id()
 }
 
 #[cfg(unix)]
-pub fn get_all_windows_with_size() -> Vec<crate::types::WindowInfo> {
-    unix::get_all_windows_with_size()
+pub fn get_all_windows_with_size(rect_mode: RectMode, with_desktop: bool) -> Vec<crate::types::WindowInfo> {
+    unix::get_all_windows_with_size(rect_mode, with_desktop)
+}
+
+#[cfg(unix)]
+pub fn get_window_titles() -> Vec<(u32, String)> {
+    unix::get_window_titles()
+}
+
+#[cfg(unix)]
+pub fn get_windows_by_pid(pid: u32, rect_mode: RectMode, with_desktop: bool) -> Vec<crate::types::WindowInfo> {
+    unix::get_windows_by_pid(pid, rect_mode, with_desktop)
+}
+
+#[cfg(unix)]
+pub fn get_windows_by_thread(tid: u32, rect_mode: RectMode, with_desktop: bool) -> AppResult<Vec<crate::types::WindowInfo>> {
+    unix::get_windows_by_thread(tid, rect_mode, with_desktop)
 }
 
 #[cfg(unix)]
 pub fn find_windows(
-    pid_filter: &Option<String>,
-    name_filter: &Option<String>,
-    title_filter: &Option<String>,
-    process_names: &[(u32, String)],
-) -> Vec<WindowHandle> {
-    unix::find_windows(pid_filter, name_filter, title_filter, process_names)
-}
\ No newline at end of file
+    filter: &WindowFilter,
+    process_names: &ProcessNameIndex,
+) -> AppResult<Vec<WindowHandle>> {
+    unix::find_windows(filter, process_names)
+}
+
+#[cfg(unix)]
+pub fn get_window_at_cursor(rect_mode: RectMode, with_desktop: bool) -> AppResult<crate::types::WindowInfo> {
+    unix::get_window_at_cursor(rect_mode, with_desktop)
+}
+
+#[cfg(unix)]
+pub fn get_monitor_work_areas() -> AppResult<Vec<crate::types::WindowRect>> {
+    unix::get_monitor_work_areas()
+}
+
+#[cfg(unix)]
+pub fn process_bitness(pid: u32) -> Option<String> {
+    unix::process_bitness(pid)
+}
+
+#[cfg(unix)]
+pub fn process_services() -> std::collections::HashMap<u32, String> {
+    unix::process_services()
+}
+
+#[cfg(unix)]
+pub fn enable_utf8_console() {
+    unix::enable_utf8_console()
+}
+
+#[cfg(unix)]
+pub fn current_virtual_desktop() -> AppResult<u32> {
+    unix::current_virtual_desktop()
+}
+
+#[cfg(unix)]
+pub fn set_full_title(enabled: bool) {
+    unix::set_full_title(enabled)
+}
+
+#[cfg(unix)]
+pub fn set_probe_responding(enabled: bool) {
+    unix::set_probe_responding(enabled)
+}
+
+#[cfg(unix)]
+pub fn set_uia_query(enabled: bool) -> AppResult<()> {
+    unix::set_uia_query(enabled)
+}
+
+#[cfg(unix)]
+pub fn is_elevated() -> Option<bool> {
+    unix::is_elevated()
+}
+
+#[cfg(unix)]
+pub fn dpi_awareness() -> Option<String> {
+    unix::dpi_awareness()
+}
+
+#[cfg(unix)]
+#[cfg_attr(not(feature = "undo"), allow(dead_code))]
+pub fn session_id() -> Option<u32> {
+    unix::session_id()
+}
+
+#[cfg(unix)]
+pub fn probe_window_support() -> AppResult<()> {
+    unix::probe_window_support()
+}
+
+#[cfg(unix)]
+pub fn lookup_account_name(user_id: &str) -> Option<String> {
+    unix::lookup_account_name(user_id)
+}
@@ -1,14 +1,16 @@
 // src/platform/unix.rs
-use crate::types::{WindowInfo, WindowRect};
-use super::{WindowHandle, PlatformData};
+use crate::types::{CapturedImage, RectMode, WindowInfo, WindowRect, WindowShowState};
+use super::WindowHandle;
 use crate::platform::interface::PlatformWindow;
 use crate::error::{AppError, AppResult};
+use crate::filter::{WindowFilter, ProcessNameIndex};
 
 /// Unix 平台特定的窗口数据
 #[derive(Debug, Clone)]
 pub struct UnixWindowData;
 
 impl UnixWindowData {
+    #[allow(dead_code)]
     pub fn new() -> Self {
         Self
     }
@@ -25,6 +27,23 @@ impl UnixWindowData {
         Err(AppError::feature_not_supported("Window operations"))
     }
 
+    pub fn hide_impl(&self) -> AppResult<()> {
+        Err(AppError::feature_not_supported("Window operations"))
+    }
+
+    pub fn show_impl(&self) -> AppResult<()> {
+        Err(AppError::feature_not_supported("Window operations"))
+    }
+
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub fn close_impl(&self) -> AppResult<()> {
+        Err(AppError::feature_not_supported("Window operations"))
+    }
+
+    pub fn is_responding_impl(&self) -> bool {
+        true
+    }
+
     pub fn set_position_impl(&self, _x: i32, _y: i32) -> AppResult<()> {
         Err(AppError::feature_not_supported("Window position setting"))
     }
@@ -41,9 +60,51 @@ impl UnixWindowData {
         Err(AppError::feature_not_supported("Window transparency operations"))
     }
 
+    pub fn opacity_impl(&self) -> AppResult<Option<u8>> {
+        Err(AppError::feature_not_supported("Window transparency detection"))
+    }
+
+    pub fn show_state_impl(&self) -> AppResult<WindowShowState> {
+        Err(AppError::feature_not_supported("Window state detection"))
+    }
+
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn placement_impl(&self) -> AppResult<(WindowRect, WindowShowState)> {
+        Err(AppError::feature_not_supported("Window placement"))
+    }
+
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn set_placement_impl(&self, _rect: &WindowRect, _state: WindowShowState) -> AppResult<()> {
+        Err(AppError::feature_not_supported("Window placement"))
+    }
+
     pub fn resize_impl(&self, _width: i32, _height: i32, _keep_position: bool, _center: bool) -> AppResult<()> {
         Err(AppError::feature_not_supported("Window resizing"))
     }
+
+    pub fn set_bounds_impl(&self, _x: i32, _y: i32, _width: i32, _height: i32) -> AppResult<()> {
+        Err(AppError::feature_not_supported("Window bounds setting"))
+    }
+
+    pub fn capture_impl(&self) -> AppResult<CapturedImage> {
+        Err(AppError::feature_not_supported("Window capture"))
+    }
+
+    pub fn icon_impl(&self) -> AppResult<CapturedImage> {
+        Err(AppError::feature_not_supported("Window icon extraction"))
+    }
+
+    pub fn get_rect_impl(&self) -> AppResult<WindowRect> {
+        Err(AppError::feature_not_supported("Window geometry query"))
+    }
+
+    pub fn get_frame_rect_impl(&self) -> AppResult<WindowRect> {
+        Err(AppError::feature_not_supported("Window frame bounds query"))
+    }
+
+    pub fn class_name_impl(&self) -> AppResult<String> {
+        Err(AppError::feature_not_supported("Window class query"))
+    }
 }
 
 // 修复这里：避免递归调用
@@ -60,6 +121,22 @@ impl PlatformWindow for UnixWindowData {
         self.restore_impl()
     }
 
+    fn hide(&self) -> AppResult<()> {
+        self.hide_impl()
+    }
+
+    fn show(&self) -> AppResult<()> {
+        self.show_impl()
+    }
+
+    fn close(&self) -> AppResult<()> {
+        self.close_impl()
+    }
+
+    fn is_responding(&self) -> bool {
+        self.is_responding_impl()
+    }
+
     fn set_position(&self, x: i32, y: i32) -> AppResult<()> {
         self.set_position_impl(x, y)
     }
@@ -76,25 +153,162 @@ impl PlatformWindow for UnixWindowData {
         self.set_transparency_impl(opacity)
     }
 
+    fn opacity(&self) -> AppResult<Option<u8>> {
+        self.opacity_impl()
+    }
+
+    fn show_state(&self) -> AppResult<WindowShowState> {
+        self.show_state_impl()
+    }
+
+    fn placement(&self) -> AppResult<(WindowRect, WindowShowState)> {
+        self.placement_impl()
+    }
+
+    fn set_placement(&self, rect: &WindowRect, state: WindowShowState) -> AppResult<()> {
+        self.set_placement_impl(rect, state)
+    }
+
     fn resize(&self, width: i32, height: i32, keep_position: bool, center: bool) -> AppResult<()> {
         self.resize_impl(width, height, keep_position, center)
     }
+
+    fn set_bounds(&self, x: i32, y: i32, width: i32, height: i32) -> AppResult<()> {
+        self.set_bounds_impl(x, y, width, height)
+    }
+
+    fn capture(&self) -> AppResult<CapturedImage> {
+        self.capture_impl()
+    }
+
+    fn icon(&self) -> AppResult<CapturedImage> {
+        self.icon_impl()
+    }
+
+    fn get_rect(&self) -> AppResult<WindowRect> {
+        self.get_rect_impl()
+    }
+
+    fn get_frame_rect(&self) -> AppResult<WindowRect> {
+        self.get_frame_rect_impl()
+    }
+
+    fn class_name(&self) -> AppResult<String> {
+        self.class_name_impl()
+    }
 }
 
-pub fn get_all_windows_with_size() -> Vec<WindowInfo> {
+pub fn get_all_windows_with_size(_rect_mode: RectMode, _with_desktop: bool) -> Vec<WindowInfo> {
     // 在 Unix 系统上返回空向量或使用其他方法
     // 这里可以根据需要集成 x11 或 wayland 支持
     eprintln!("Warning: Window size detection is limited on non-Windows systems");
     Vec::new()
 }
 
+/// 所有窗口的 PID 和标题（用于进程列表）
+pub fn get_window_titles() -> Vec<(u32, String)> {
+    get_all_windows_with_size(RectMode::Restored, false)
+        .into_iter()
+        .map(|window| (window.pid, window.title))
+        .collect()
+}
+
+/// 只保留指定进程的窗口，与 Windows 上的单 PID 快速路径结果一致
+pub fn get_windows_by_pid(pid: u32, rect_mode: RectMode, with_desktop: bool) -> Vec<WindowInfo> {
+    let mut windows = get_all_windows_with_size(rect_mode, with_desktop);
+    windows.retain(|window| window.pid == pid);
+    windows
+}
+
+pub fn get_windows_by_thread(_tid: u32, _rect_mode: RectMode, _with_desktop: bool) -> AppResult<Vec<WindowInfo>> {
+    Err(AppError::feature_not_supported("Thread window enumeration (--tid)"))
+}
+
 pub fn find_windows(
-    _pid_filter: &Option<String>,
-    _name_filter: &Option<String>,
-    _title_filter: &Option<String>,
-    _process_names: &[(u32, String)],
-) -> Vec<WindowHandle> {
-    // 在 Unix 系统上返回空向量
-    eprintln!("Warning: Window operations are not supported on this platform");
-    Vec::new()
-}
\ No newline at end of file
+    _filter: &WindowFilter,
+    _process_names: &ProcessNameIndex,
+) -> AppResult<Vec<WindowHandle>> {
+    // 在 Unix 系统上无法枚举可操作的窗口（也就没有可记录的矩形）：返回“不支持”（退出码 5），
+    // 而不是空列表，避免被误报为“没有匹配的窗口”（退出码 2）
+    Err(AppError::feature_not_supported("Window operations"))
+}
+
+pub fn get_monitor_work_areas() -> AppResult<Vec<WindowRect>> {
+    Err(AppError::feature_not_supported("Monitor detection"))
+}
+
+pub fn enable_utf8_console() {
+    // Unix 终端通常已使用 UTF-8，无需处理
+}
+
+/// Unix 上没有窗口后端，无法定位鼠标指针下的窗口
+pub fn get_window_at_cursor(_rect_mode: RectMode, _with_desktop: bool) -> AppResult<WindowInfo> {
+    Err(AppError::feature_not_supported("Selecting the window under the cursor"))
+}
+
+/// 虚拟桌面查询依赖 Windows 的 IVirtualDesktopManager
+pub fn current_virtual_desktop() -> AppResult<u32> {
+    Err(AppError::feature_not_supported("Virtual desktop query"))
+}
+
+/// 运行时检查窗口枚举能力：只查看环境变量，不连接显示服务，避免阻塞或重试
+pub fn probe_window_support() -> AppResult<()> {
+    let has_display = ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()));
+
+    if has_display {
+        Err(AppError::feature_not_supported("Window enumeration (no X11/Wayland backend is built in)"))
+    } else {
+        Err(AppError::feature_not_supported("Window enumeration (no X11 or Wayland display available)"))
+    }
+}
+
+/// 读取 /proc/self/status 的有效 UID 判断是否以 root 运行，没有 procfs 时返回 None
+pub fn is_elevated() -> Option<bool> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let uids = status.lines().find_map(|line| line.strip_prefix("Uid:"))?;
+    let euid = uids.split_whitespace().nth(1)?;
+    Some(euid == "0")
+}
+
+/// Unix 上没有窗口后端，标题长度没有限制可调
+pub fn set_full_title(_enabled: bool) {}
+
+/// Unix 上没有窗口后端，不需要探测响应状态
+pub fn set_probe_responding(_enabled: bool) {}
+
+/// UI Automation 是 Windows 的辅助功能接口，其他平台不支持按 UIA 属性匹配窗口
+pub fn set_uia_query(enabled: bool) -> AppResult<()> {
+    if enabled {
+        return Err(AppError::feature_not_supported("UI Automation matching (--uia-name/--automation-id)"));
+    }
+    Ok(())
+}
+
+/// DPI 感知是 Windows 的概念，其他平台不报告
+pub fn dpi_awareness() -> Option<String> {
+    None
+}
+
+/// 读取 /proc/self/sessionid 的登录会话 ID；未设置（u32::MAX）或没有 procfs 时返回 None
+#[cfg_attr(not(feature = "undo"), allow(dead_code))]
+pub fn session_id() -> Option<u32> {
+    let id: u32 = std::fs::read_to_string("/proc/self/sessionid").ok()?.trim().parse().ok()?;
+    (id != u32::MAX).then_some(id)
+}
+
+/// 进程位数依赖 Windows 的 IsWow64Process，其他平台不报告
+pub fn process_bitness(_pid: u32) -> Option<String> {
+    None
+}
+
+/// 服务控制管理器仅存在于 Windows
+pub fn process_services() -> std::collections::HashMap<u32, String> {
+    std::collections::HashMap::new()
+}
+
+/// 用户列表中找不到的 uid 直接以数字显示
+pub fn lookup_account_name(uid: &str) -> Option<String> {
+    Some(uid.to_string())
+}
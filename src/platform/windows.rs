@@ -1,21 +1,155 @@
 // src/platform/windows.rs
-use windows::Win32::Foundation::{HWND, BOOL, LPARAM, COLORREF};
+use windows::Win32::Foundation::{HWND, BOOL, LPARAM, WPARAM, COLORREF, RECT, POINT};
+use windows::Win32::Graphics::Gdi::{EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO};
+use windows::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits, GetObjectW, ReleaseDC, SelectObject,
+    BITMAP, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP, SRCCOPY,
+};
+use windows::core::{GUID, PCWSTR, PWSTR};
+use windows::Win32::Foundation::{LocalFree, HLOCAL, PSID, GetLastError, SetLastError, WIN32_ERROR};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, ERROR_MORE_DATA};
+use windows::Win32::Security::{LookupAccountSidW, SID_NAME_USE};
+use windows::Win32::Security::{
+    GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, TokenIntegrityLevel, TOKEN_MANDATORY_LABEL, TOKEN_QUERY,
+};
+use windows::Win32::System::Threading::{GetCurrentProcess, IsWow64Process, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION};
+use windows::Win32::Security::Authorization::ConvertStringSidToSidW;
+use windows::Win32::System::Console::SetConsoleOutputCP;
+use windows::Win32::Globalization::CP_UTF8;
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_APARTMENTTHREADED};
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_BINARY};
+use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+use windows::Win32::System::Services::{
+    CloseServiceHandle, EnumServicesStatusExW, OpenSCManagerW, ENUM_SERVICE_STATUS_PROCESSW,
+    SC_ENUM_PROCESS_INFO, SC_MANAGER_ENUMERATE_SERVICE, SERVICE_ACTIVE, SERVICE_WIN32,
+};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::UI::HiDpi::{
+    GetAwarenessFromDpiAwarenessContext, GetThreadDpiAwarenessContext,
+    DPI_AWARENESS_PER_MONITOR_AWARE, DPI_AWARENESS_SYSTEM_AWARE, DPI_AWARENESS_UNAWARE,
+};
+use windows::Win32::UI::Shell::{IVirtualDesktopManager, VirtualDesktopManager};
+#[cfg(feature = "uia")]
+use windows::Win32::UI::Accessibility::{CUIAutomation, IUIAutomation};
+use windows::Win32::Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS, PW_CLIENTONLY};
 use windows::Win32::UI::WindowsAndMessaging::{
-    EnumWindows, GetWindowTextW, GetWindowThreadProcessId, GetWindowRect, 
-    SetWindowPos, ShowWindow, IsWindowVisible, GetClassNameW, GetWindowLongW,
-    SW_MINIMIZE, SW_MAXIMIZE, SW_RESTORE, SWP_NOZORDER, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
-    GWL_EXSTYLE, WS_EX_TOPMOST, HWND_TOPMOST, HWND_NOTOPMOST, WS_EX_LAYERED
+    EnumWindows, EnumThreadWindows, GetWindowTextW, GetWindowTextLengthW, GetWindowThreadProcessId, GetWindowRect, 
+    SetWindowPos, ShowWindow, IsWindow, IsWindowVisible, GetClassNameW, GetWindowLongW,
+    GetWindowPlacement, IsIconic, IsZoomed, WINDOWPLACEMENT, GetClientRect, PW_RENDERFULLCONTENT,
+    SW_MINIMIZE, SW_MAXIMIZE, SW_RESTORE, SW_HIDE, SW_SHOW, SW_SHOWNOACTIVATE, SW_SHOWMINNOACTIVE, SW_SHOWMAXIMIZED,
+    SetWindowPlacement, SWP_NOZORDER, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    GWL_EXSTYLE, WS_EX_TOPMOST, HWND_TOPMOST, HWND_NOTOPMOST, WS_EX_LAYERED,
+    SendMessageTimeoutW, PostMessageW, WM_NULL, WM_CLOSE, SMTO_ABORTIFHUNG, SMTO_BLOCK,
+    GetCursorPos, WindowFromPoint, GetAncestor, GA_ROOT,
+    GetIconInfo, HICON, ICONINFO, WM_GETICON, ICON_BIG, ICON_SMALL2, GCLP_HICON, GCLP_HICONSM, GET_CLASS_LONG_INDEX,
 };
+#[cfg(target_pointer_width = "64")]
+use windows::Win32::UI::WindowsAndMessaging::GetClassLongPtrW;
+#[cfg(not(target_pointer_width = "64"))]
+use windows::Win32::UI::WindowsAndMessaging::GetClassLongW;
 use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
-use windows::Win32::UI::WindowsAndMessaging::LWA_ALPHA;
+use windows::Win32::UI::WindowsAndMessaging::{LWA_ALPHA, GetLayeredWindowAttributes, LAYERED_WINDOW_ATTRIBUTES_FLAGS};
 use windows::Win32::UI::WindowsAndMessaging::SetWindowLongW;
 use windows::Win32::UI::WindowsAndMessaging::GetSystemMetrics;  // 新增导入
 use windows::Win32::UI::WindowsAndMessaging::SM_CXSCREEN;       // 新增导入
 use windows::Win32::UI::WindowsAndMessaging::SM_CYSCREEN;       // 新增导入
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use crate::platform::interface::PlatformWindow;
-use crate::types::{WindowInfo, WindowRect};
+use crate::types::{CapturedImage, RectMode, UiaProperties, WindowInfo, WindowRect, WindowShowState};
 use crate::error::{AppError, AppResult};
+use crate::filter::{WindowFilter, ProcessNameIndex};
+use crate::utils::alpha_to_opacity;
+
+/// 从 windows 错误中取出 Win32 错误码（HRESULT_FROM_WIN32 的低 16 位）
+fn win32_code(error: &windows::core::Error) -> u32 {
+    (error.code().0 as u32) & 0xFFFF
+}
+
+/// 读取 GetLastError，将错误码和 FormatMessage 描述附加到错误信息中
+fn last_error(msg: &str) -> AppError {
+    match unsafe { GetLastError() } {
+        Err(e) => AppError::win32(msg, win32_code(&e), e.message()),
+        Ok(()) => AppError::window_operation(msg),
+    }
+}
+
+/// 高完整性级别（管理员权限进程）的 RID
+const SECURITY_MANDATORY_HIGH_RID: u32 = 0x3000;
+
+/// 读取进程令牌的完整性级别（SECURITY_MANDATORY_*_RID），无法读取时返回 None
+unsafe fn process_integrity_level(process: HANDLE) -> Option<u32> {
+    let mut token = HANDLE::default();
+    OpenProcessToken(process, TOKEN_QUERY, &mut token).ok()?;
+
+    // TOKEN_MANDATORY_LABEL 后紧跟 SID 数据，使用 u64 缓冲区保证对齐
+    let mut buffer = [0u64; 16];
+    let mut length = 0u32;
+    let result = GetTokenInformation(
+        token,
+        TokenIntegrityLevel,
+        Some(buffer.as_mut_ptr().cast()),
+        std::mem::size_of_val(&buffer) as u32,
+        &mut length,
+    );
+    let _ = CloseHandle(token);
+    result.ok()?;
+
+    let label = &*(buffer.as_ptr() as *const TOKEN_MANDATORY_LABEL);
+    let count = *GetSidSubAuthorityCount(label.Label.Sid);
+    if count == 0 {
+        return None;
+    }
+    Some(*GetSidSubAuthority(label.Label.Sid, count as u32 - 1))
+}
+
+/// 目标进程的完整性级别是否高于 pscan（例如以管理员身份运行而 pscan 没有）
+///
+/// UIPI 会让对这类窗口的操作静默失败，因此在操作前检查。
+fn is_elevated_above_self(pid: u32) -> bool {
+    unsafe {
+        let Some(own_level) = process_integrity_level(GetCurrentProcess()) else {
+            return false;
+        };
+        if own_level >= SECURITY_MANDATORY_HIGH_RID {
+            return false;
+        }
+
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, BOOL(0), pid) else {
+            return false;
+        };
+        let target_level = process_integrity_level(process);
+        let _ = CloseHandle(process);
+
+        // 非提权进程无法读取提权进程的令牌，读取失败同样视为权限更高
+        target_level.is_none_or(|level| level > own_level)
+    }
+}
+
+/// pscan 所在的登录会话 ID（远程桌面等多会话环境下各不相同）
+#[cfg_attr(not(feature = "undo"), allow(dead_code))]
+pub fn session_id() -> Option<u32> {
+    let mut session = 0u32;
+    unsafe { ProcessIdToSessionId(std::process::id(), &mut session) }.ok().map(|()| session)
+}
+
+/// pscan 自身是否以管理员身份（高完整性级别）运行
+pub fn is_elevated() -> Option<bool> {
+    unsafe { process_integrity_level(GetCurrentProcess()).map(|level| level >= SECURITY_MANDATORY_HIGH_RID) }
+}
+
+/// 当前线程的 DPI 感知模式，决定窗口坐标是物理像素还是缩放后的逻辑像素
+pub fn dpi_awareness() -> Option<String> {
+    let awareness = unsafe { GetAwarenessFromDpiAwarenessContext(GetThreadDpiAwarenessContext()) };
+    let name = match awareness {
+        DPI_AWARENESS_UNAWARE => "unaware",
+        DPI_AWARENESS_SYSTEM_AWARE => "system-aware",
+        DPI_AWARENESS_PER_MONITOR_AWARE => "per-monitor-aware",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
 
 /// Windows 平台特定的窗口数据
 #[derive(Debug, Clone)]
@@ -28,18 +162,32 @@ impl WindowsWindowData {
         Self { hwnd }
     }
 
+    /// 目标窗口属于权限更高的进程时返回 PermissionDenied，避免操作静默失败
+    fn ensure_not_elevated(&self) -> AppResult<()> {
+        let mut pid = 0u32;
+        unsafe {
+            GetWindowThreadProcessId(HWND(self.hwnd), Some(&mut pid));
+        }
+        if pid != 0 && is_elevated_above_self(pid) {
+            return Err(AppError::elevation_required(pid));
+        }
+        Ok(())
+    }
+
     pub fn minimize(&self) -> AppResult<()> {
         unsafe {
             let hwnd = HWND(self.hwnd);
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
+            SetLastError(WIN32_ERROR(0));
             let result = ShowWindow(hwnd, SW_MINIMIZE);
             if result.0 != 0 {
                 Ok(())
             } else {
-                Err(AppError::window_operation("Failed to minimize window"))
+                Err(last_error("Failed to minimize window"))
             }
         }
     }
@@ -50,12 +198,14 @@ impl WindowsWindowData {
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
+            SetLastError(WIN32_ERROR(0));
             let result = ShowWindow(hwnd, SW_MAXIMIZE);
             if result.0 != 0 {
                 Ok(())
             } else {
-                Err(AppError::window_operation("Failed to maximize window"))
+                Err(last_error("Failed to maximize window"))
             }
         }
     }
@@ -66,41 +216,75 @@ impl WindowsWindowData {
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
+            SetLastError(WIN32_ERROR(0));
             let result = ShowWindow(hwnd, SW_RESTORE);
             if result.0 != 0 {
                 Ok(())
             } else {
-                Err(AppError::window_operation("Failed to restore window"))
+                Err(last_error("Failed to restore window"))
             }
         }
     }
 
-    pub fn set_position(&self, x: i32, y: i32) -> AppResult<()> {
+    pub fn hide(&self) -> AppResult<()> {
         unsafe {
             let hwnd = HWND(self.hwnd);
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
-            // 获取当前窗口大小
-            let mut rect = std::mem::zeroed();
-            if GetWindowRect(hwnd, &mut rect).is_err() {
-                return Err(AppError::platform("Failed to get window rect"));
+            // ShowWindow 返回的是调用前的可见状态，因此以调用后的可见性判断是否成功
+            SetLastError(WIN32_ERROR(0));
+            let _ = ShowWindow(hwnd, SW_HIDE);
+            if !IsWindowVisible(hwnd).as_bool() {
+                Ok(())
+            } else {
+                Err(last_error("Failed to hide window"))
             }
+        }
+    }
 
-            let width = rect.right - rect.left;
-            let height = rect.bottom - rect.top;
+    pub fn show(&self) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(AppError::window_operation("Invalid window handle"));
+            }
+            self.ensure_not_elevated()?;
+            
+            SetLastError(WIN32_ERROR(0));
+            let _ = ShowWindow(hwnd, SW_SHOW);
+            if IsWindowVisible(hwnd).as_bool() {
+                Ok(())
+            } else {
+                Err(last_error("Failed to show window"))
+            }
+        }
+    }
+
+    pub fn set_position(&self, x: i32, y: i32) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindowVisible(hwnd).as_bool() {
+                return Err(AppError::window_operation("Window not visible or invalid handle"));
+            }
+            self.ensure_not_elevated()?;
+            
+            // 获取当前窗口大小
+            let rect = self.get_rect()?;
             
             if SetWindowPos(
                 hwnd, 
                 HWND(0), 
-                x, y, width, height, 
+                x, y, rect.width, rect.height, 
                 SWP_NOZORDER | SWP_NOACTIVATE
             ).is_ok() {
                 Ok(())
             } else {
-                Err(AppError::window_operation("Failed to set window position"))
+                Err(last_error("Failed to set window position"))
             }
         }
     }
@@ -111,6 +295,7 @@ impl WindowsWindowData {
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
             let result = if on_top {
                 SetWindowPos(
@@ -131,7 +316,7 @@ impl WindowsWindowData {
             if result.is_ok() {
                 Ok(())
             } else {
-                Err(AppError::window_operation("Failed to set always on top state"))
+                Err(last_error("Failed to set always on top state"))
             }
         }
     }
@@ -155,6 +340,7 @@ impl WindowsWindowData {
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
             // 设置分层窗口样式
             let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
@@ -165,7 +351,7 @@ impl WindowsWindowData {
             // 确保窗口有分层样式
             let new_style = ex_style | WS_EX_LAYERED.0 as i32;
             if SetWindowLongW(hwnd, GWL_EXSTYLE, new_style) == 0 {
-                return Err(AppError::platform("Failed to set layered window style"));
+                return Err(last_error("Failed to set layered window style"));
             }
             
             // 计算透明度值 (0-255)
@@ -175,32 +361,123 @@ impl WindowsWindowData {
             let crkey = COLORREF(0);
             match SetLayeredWindowAttributes(hwnd, crkey, alpha as u8, LWA_ALPHA) {
                 Ok(()) => Ok(()),
-                Err(e) => Err(AppError::platform(format!("Failed to set window transparency: {}", e)))
+                Err(e) => Err(AppError::win32("Failed to set window transparency", win32_code(&e), e.message()))
             }
         }
     }
     
+    pub fn opacity(&self) -> AppResult<Option<u8>> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+            if ex_style == 0 {
+                return Err(AppError::platform("Failed to get window style"));
+            }
+
+            // 没有分层样式的窗口完全不透明
+            if ex_style & WS_EX_LAYERED.0 as i32 == 0 {
+                return Ok(Some(100));
+            }
+
+            // 用 UpdateLayeredWindow 绘制的窗口没有可读取的分层属性
+            let mut alpha: u8 = 255;
+            let mut flags = LAYERED_WINDOW_ATTRIBUTES_FLAGS(0);
+            if GetLayeredWindowAttributes(hwnd, None, Some(&mut alpha), Some(&mut flags)).is_err() {
+                return Ok(None);
+            }
+            Ok(Some(if flags.0 & LWA_ALPHA.0 != 0 { alpha_to_opacity(alpha) } else { 100 }))
+        }
+    }
+
+    pub fn show_state(&self) -> AppResult<WindowShowState> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(AppError::window_operation("Invalid window handle"));
+            }
+            Ok(if IsIconic(hwnd).as_bool() {
+                WindowShowState::Minimized
+            } else if IsZoomed(hwnd).as_bool() {
+                WindowShowState::Maximized
+            } else {
+                WindowShowState::Normal
+            })
+        }
+    }
+
+    /// 还原位置（`GetWindowPlacement` 的 `rcNormalPosition`）和显示状态
+    ///
+    /// 与 `window_rect` 的 `Restored` 模式相同，最小化窗口也能得到真实的位置而不是 (-32000, -32000)。
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn placement(&self) -> AppResult<(WindowRect, WindowShowState)> {
+        let state = self.show_state()?;
+        unsafe {
+            let mut placement = WINDOWPLACEMENT {
+                length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+                ..Default::default()
+            };
+            if GetWindowPlacement(HWND(self.hwnd), &mut placement).is_err() {
+                return Err(last_error("Failed to get window placement"));
+            }
+            let normal = placement.rcNormalPosition;
+            Ok((WindowRect::new(normal.left, normal.top, normal.right - normal.left, normal.bottom - normal.top), state))
+        }
+    }
+
+    /// 用 `SetWindowPlacement` 设置还原位置和显示状态（与 `placement` 使用相同的坐标），不激活窗口
+    #[cfg_attr(not(feature = "undo"), allow(dead_code))]
+    pub fn set_placement(&self, rect: &WindowRect, state: WindowShowState) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(AppError::window_operation("Invalid window handle"));
+            }
+            self.ensure_not_elevated()?;
+
+            let mut placement = WINDOWPLACEMENT {
+                length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+                ..Default::default()
+            };
+            if GetWindowPlacement(hwnd, &mut placement).is_err() {
+                return Err(last_error("Failed to get window placement"));
+            }
+            placement.rcNormalPosition = RECT {
+                left: rect.x,
+                top: rect.y,
+                right: rect.x + rect.width,
+                bottom: rect.y + rect.height,
+            };
+            placement.showCmd = match state {
+                WindowShowState::Normal => SW_SHOWNOACTIVATE,
+                WindowShowState::Minimized => SW_SHOWMINNOACTIVE,
+                WindowShowState::Maximized => SW_SHOWMAXIMIZED,
+            }.0 as u32;
+            if SetWindowPlacement(hwnd, &placement).is_err() {
+                return Err(last_error("Failed to set window placement"));
+            }
+            Ok(())
+        }
+    }
+
     pub fn resize(&self, width: i32, height: i32, keep_position: bool, center: bool) -> AppResult<()> {
         unsafe {
             let hwnd = HWND(self.hwnd);
             if !IsWindowVisible(hwnd).as_bool() {
                 return Err(AppError::window_operation("Window not visible or invalid handle"));
             }
+            self.ensure_not_elevated()?;
             
-            let mut rect = std::mem::zeroed();
-            if GetWindowRect(hwnd, &mut rect).is_err() {
-                return Err(AppError::platform("Failed to get window rect"));
-            }
+            let rect = self.get_rect()?;
 
             let (x, y) = if keep_position {
-                (rect.left, rect.top)
+                (rect.x, rect.y)
             } else if center {
                 // 获取屏幕尺寸并计算居中位置
                 let screen_width = GetSystemMetrics(SM_CXSCREEN);
                 let screen_height = GetSystemMetrics(SM_CYSCREEN);
                 ((screen_width - width) / 2, (screen_height - height) / 2)
             } else {
-                (rect.left, rect.top)
+                (rect.x, rect.y)
             };
             
             if SetWindowPos(
@@ -211,10 +488,171 @@ impl WindowsWindowData {
             ).is_ok() {
                 Ok(())
             } else {
-                Err(AppError::window_operation("Failed to resize window"))
+                Err(last_error("Failed to resize window"))
+            }
+        }
+    }
+
+    /// 单次 `SetWindowPos` 同时移动并缩放窗口
+    pub fn set_bounds(&self, x: i32, y: i32, width: i32, height: i32) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindowVisible(hwnd).as_bool() {
+                return Err(AppError::window_operation("Window not visible or invalid handle"));
+            }
+            self.ensure_not_elevated()?;
+
+            if SetWindowPos(
+                hwnd,
+                HWND(0),
+                x, y, width, height,
+                SWP_NOZORDER | SWP_NOACTIVATE
+            ).is_ok() {
+                Ok(())
+            } else {
+                Err(last_error("Failed to set window bounds"))
+            }
+        }
+    }
+
+    /// 截取窗口客户区，返回 RGBA 像素
+    pub fn capture(&self) -> AppResult<CapturedImage> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindowVisible(hwnd).as_bool() {
+                return Err(AppError::window_operation("Window not visible or invalid handle"));
+            }
+
+            let mut rect = RECT::default();
+            if GetClientRect(hwnd, &mut rect).is_err() {
+                return Err(AppError::platform("Failed to get window client rect"));
+            }
+
+            let width = rect.right - rect.left;
+            let height = rect.bottom - rect.top;
+            if width <= 0 || height <= 0 {
+                return Err(AppError::window_operation("Window client area is empty (is it minimized?)"));
+            }
+
+            let window_dc = GetDC(hwnd);
+            if window_dc.0 == 0 {
+                return Err(AppError::platform("Failed to get window device context"));
+            }
+            let memory_dc = CreateCompatibleDC(window_dc);
+            let bitmap = CreateCompatibleBitmap(window_dc, width, height);
+            let previous = SelectObject(memory_dc, bitmap);
+
+            // PrintWindow 可以截取被遮挡的窗口；失败时退回 BitBlt（只能截取屏幕上可见的部分）
+            let flags = PRINT_WINDOW_FLAGS(PW_CLIENTONLY.0 | PW_RENDERFULLCONTENT);
+            let drawn = PrintWindow(hwnd, memory_dc, flags).as_bool()
+                || BitBlt(memory_dc, 0, 0, width, height, window_dc, 0, 0, SRCCOPY).is_ok();
+
+            // 负高度表示自上而下的位图，32 位像素为 BGRA
+            let mut info = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    biHeight: -height,
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let mut pixels = vec![0u8; width as usize * height as usize * 4];
+            let lines = if drawn {
+                GetDIBits(memory_dc, bitmap, 0, height as u32, Some(pixels.as_mut_ptr() as *mut _), &mut info, DIB_RGB_COLORS)
+            } else {
+                0
+            };
+
+            SelectObject(memory_dc, previous);
+            let _ = DeleteObject(bitmap);
+            let _ = DeleteDC(memory_dc);
+            ReleaseDC(hwnd, window_dc);
+
+            if lines == 0 {
+                return Err(AppError::window_operation("Failed to capture window contents"));
+            }
+
+            // BGRA -> RGBA；GDI 不填写 alpha 通道，统一设为不透明
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+                pixel[3] = 255;
             }
+
+            Ok(CapturedImage {
+                width: width as u32,
+                height: height as u32,
+                rgba: pixels,
+            })
+        }
+    }
+
+    /// 读取窗口图标，返回 RGBA 像素
+    pub fn icon(&self) -> AppResult<CapturedImage> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(AppError::invalid_window_handle("Window no longer exists"));
+            }
+
+            let icon = window_icon(hwnd).ok_or_else(|| AppError::window_operation("Window has no icon"))?;
+            icon_to_rgba(icon)
+        }
+    }
+
+    /// 获取窗口当前的位置和尺寸（屏幕坐标）
+    pub fn get_rect(&self) -> AppResult<WindowRect> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(AppError::invalid_window_handle("Window no longer exists"));
+            }
+
+            let mut rect = RECT::default();
+            if GetWindowRect(hwnd, &mut rect).is_err() {
+                return Err(AppError::platform("Failed to get window rect"));
+            }
+
+            Ok(WindowRect::new(rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top))
+        }
+    }
+
+    /// 向窗口投递 `WM_CLOSE`，不等待窗口处理；应用可能弹出保存提示或拒绝关闭
+    pub fn close(&self) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(self.hwnd);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(AppError::window_operation("Invalid window handle"));
+            }
+            self.ensure_not_elevated()?;
+
+            if PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)).is_err() {
+                return Err(last_error("Failed to close window"));
+            }
+            Ok(())
+        }
+    }
+
+    /// 用 `WM_NULL` 探测窗口是否响应，最多等待 `RESPONDING_PROBE_TIMEOUT_MS`
+    pub fn is_responding(&self) -> bool {
+        unsafe { is_responding(HWND(self.hwnd)) }
+    }
+
+    /// 获取窗口可见边框（不含阴影）的位置和尺寸；DWM 不可用时与 `get_rect` 相同
+    pub fn get_frame_rect(&self) -> AppResult<WindowRect> {
+        match unsafe { frame_rect(HWND(self.hwnd)) } {
+            Some(frame) => Ok(frame),
+            None => self.get_rect(),
         }
     }
+
+    /// 获取窗口类名
+    pub fn class_name(&self) -> AppResult<String> {
+        unsafe { window_class(HWND(self.hwnd)) }.ok_or_else(|| last_error("Failed to get window class name"))
+    }
 }
 
 // 为 WindowsWindowData 实现 PlatformWindow trait
@@ -231,6 +669,22 @@ impl PlatformWindow for WindowsWindowData {
         self.restore()
     }
 
+    fn hide(&self) -> AppResult<()> {
+        self.hide()
+    }
+
+    fn show(&self) -> AppResult<()> {
+        self.show()
+    }
+
+    fn close(&self) -> AppResult<()> {
+        self.close()
+    }
+
+    fn is_responding(&self) -> bool {
+        self.is_responding()
+    }
+
     fn set_position(&self, x: i32, y: i32) -> AppResult<()> {
         self.set_position(x, y)
     }
@@ -246,52 +700,293 @@ impl PlatformWindow for WindowsWindowData {
     fn set_transparency(&self, opacity: u8) -> AppResult<()> {
         self.set_transparency(opacity)
     }
+
+    fn opacity(&self) -> AppResult<Option<u8>> {
+        self.opacity()
+    }
+
+    fn show_state(&self) -> AppResult<WindowShowState> {
+        self.show_state()
+    }
+
+    fn placement(&self) -> AppResult<(WindowRect, WindowShowState)> {
+        self.placement()
+    }
+
+    fn set_placement(&self, rect: &WindowRect, state: WindowShowState) -> AppResult<()> {
+        self.set_placement(rect, state)
+    }
     fn resize(&self, width: i32, height: i32, keep_position: bool, center: bool) -> AppResult<()> {
         self.resize(width, height, keep_position, center)
     }
+
+    fn set_bounds(&self, x: i32, y: i32, width: i32, height: i32) -> AppResult<()> {
+        self.set_bounds(x, y, width, height)
+    }
+
+    fn capture(&self) -> AppResult<CapturedImage> {
+        self.capture()
+    }
+
+    fn icon(&self) -> AppResult<CapturedImage> {
+        self.icon()
+    }
+
+    fn get_rect(&self) -> AppResult<WindowRect> {
+        self.get_rect()
+    }
+
+    fn get_frame_rect(&self) -> AppResult<WindowRect> {
+        self.get_frame_rect()
+    }
+
+    fn class_name(&self) -> AppResult<String> {
+        self.class_name()
+    }
 }
 
 // 主要的 Windows 平台实现函数
-pub fn get_all_windows_with_size() -> Vec<WindowInfo> {
-    let mut windows = Vec::new();
 
+/// `rect_mode` 决定报告的矩形：最小化窗口的还原位置、`GetWindowRect` 的原始值或 DWM 可见边框（见 `RectMode`）。
+/// `with_desktop` 为 true 时查询每个窗口所在的虚拟桌面（需要 COM 调用，默认不查询）。
+pub fn get_all_windows_with_size(rect_mode: RectMode, with_desktop: bool) -> Vec<WindowInfo> {
+    enumerate_windows(None, None, false, rect_mode, with_desktop, true)
+        .into_iter()
+        .map(|(window, _)| window)
+        .collect()
+}
+
+/// 所有可见顶层窗口的 PID 和标题（用于进程列表），不探测响应状态、不查询 UI Automation 属性
+pub fn get_window_titles() -> Vec<(u32, String)> {
+    enumerate_windows(None, None, false, RectMode::Restored, false, false)
+        .into_iter()
+        .map(|(window, _)| (window.pid, window.title))
+        .collect()
+}
+
+/// 只获取指定进程的窗口（单 PID 快速路径）
+pub fn get_windows_by_pid(pid: u32, rect_mode: RectMode, with_desktop: bool) -> Vec<WindowInfo> {
+    enumerate_windows(Some(pid), None, false, rect_mode, with_desktop, true)
+        .into_iter()
+        .map(|(window, _)| window)
+        .collect()
+}
+
+/// 只获取指定线程创建的顶层窗口（`--tid`，使用 `EnumThreadWindows`）
+pub fn get_windows_by_thread(tid: u32, rect_mode: RectMode, with_desktop: bool) -> AppResult<Vec<WindowInfo>> {
+    Ok(enumerate_windows(None, Some(tid), false, rect_mode, with_desktop, true)
+        .into_iter()
+        .map(|(window, _)| window)
+        .collect())
+}
+
+/// 获取鼠标指针下的顶层窗口（`--at-cursor`），不做可见性和标题检查
+pub fn get_window_at_cursor(rect_mode: RectMode, with_desktop: bool) -> AppResult<WindowInfo> {
+    let hwnd = window_under_cursor()?;
+    let desktops = if with_desktop { VirtualDesktops::load() } else { None };
+    Ok(unsafe { describe_window(hwnd, rect_mode, desktops.as_ref()) })
+}
+
+/// 鼠标指针下的窗口，子窗口向上取到顶层窗口（`GetAncestor(GA_ROOT)`）
+fn window_under_cursor() -> AppResult<HWND> {
     unsafe {
-        let _ = EnumWindows(Some(enum_window_callback), LPARAM(&mut windows as *mut _ as isize));
+        let mut point = POINT::default();
+        if GetCursorPos(&mut point).is_err() {
+            return Err(last_error("Failed to get cursor position"));
+        }
+
+        let hwnd = WindowFromPoint(point);
+        if hwnd.0 == 0 {
+            return Err(AppError::NoMatchingWindows);
+        }
+
+        let root = GetAncestor(hwnd, GA_ROOT);
+        Ok(if root.0 == 0 { hwnd } else { root })
+    }
+}
+
+/// 读取单个窗口的 PID、标题和矩形
+unsafe fn describe_window(hwnd: HWND, rect_mode: RectMode, desktops: Option<&VirtualDesktops>) -> WindowInfo {
+    let mut pid: u32 = 0;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+
+    WindowInfo {
+        pid,
+        title: window_title(hwnd),
+        rect: window_rect(hwnd, rect_mode).unwrap_or_else(|| WindowRect::new(0, 0, 0, 0)),
+        desktop: desktops.and_then(|desktops| desktops.index_of(hwnd)),
+        responding: probe_responding(hwnd),
+        ex_style: GetWindowLongW(hwnd, GWL_EXSTYLE) as u32,
+        class: window_class(hwnd).unwrap_or_default(),
+        uia: uia_properties(hwnd),
+    }
+}
+
+/// 读取窗口类名，失败时返回 None
+unsafe fn window_class(hwnd: HWND) -> Option<String> {
+    let mut class_name = [0u16; 256];
+    let class_len = GetClassNameW(hwnd, &mut class_name);
+    (class_len > 0).then(|| String::from_utf16_lossy(&class_name[..class_len as usize]))
+}
+
+/// 是否查询窗口的 UI Automation 属性（指定 --uia-name/--automation-id 时）
+static UIA_QUERY: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否查询 UI Automation 属性，启动时调用一次；未启用 `uia` 特性时请求查询返回“不支持”
+pub fn set_uia_query(enabled: bool) -> AppResult<()> {
+    if enabled && !cfg!(feature = "uia") {
+        return Err(AppError::feature_not_supported(
+            "UI Automation matching (--uia-name/--automation-id; build with --features uia)",
+        ));
+    }
+    UIA_QUERY.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+#[cfg(feature = "uia")]
+thread_local! {
+    /// UI Automation 客户端，每个线程创建一次；创建失败时为 None，所有窗口都不匹配 UIA 条件
+    static UI_AUTOMATION: Option<IUIAutomation> = unsafe {
+        // 当前线程已初始化 COM（包括线程模型不同）时返回的错误不影响后续调用
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        CoCreateInstance(&CUIAutomation, None, CLSCTX_ALL).ok()
+    };
+}
+
+/// 读取窗口的 UI Automation 名称和 AutomationId；未请求查询或查询失败时返回 None
+///
+/// 每个窗口需要跨进程调用，只在指定 UIA 条件时查询（见 `set_uia_query`）。
+#[cfg(feature = "uia")]
+fn uia_properties(hwnd: HWND) -> Option<UiaProperties> {
+    if !UIA_QUERY.load(Ordering::Relaxed) {
+        return None;
     }
+    UI_AUTOMATION.with(|automation| unsafe {
+        let element = automation.as_ref()?.ElementFromHandle(hwnd).ok()?;
+        Some(UiaProperties {
+            name: element.CurrentName().map(|name| name.to_string()).unwrap_or_default(),
+            automation_id: element.CurrentAutomationId().map(|id| id.to_string()).unwrap_or_default(),
+        })
+    })
+}
+
+/// 未启用 `uia` 特性时不查询（`set_uia_query` 已拒绝 UIA 条件）
+#[cfg(not(feature = "uia"))]
+fn uia_properties(_hwnd: HWND) -> Option<UiaProperties> {
+    None
+}
 
-    windows
+/// 默认的标题缓冲区长度（UTF-16 单元），更长的标题会被截断为前 511 个单元
+const TITLE_BUFFER_LEN: usize = 512;
+
+/// 是否读取完整标题（全局 --full-title）
+static FULL_TITLE: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否读取完整标题，启动时调用一次
+pub fn set_full_title(enabled: bool) {
+    FULL_TITLE.store(enabled, Ordering::Relaxed);
+}
+
+/// 读取窗口标题
+///
+/// 默认使用固定缓冲区，标题最长 `TITLE_BUFFER_LEN - 1` 个 UTF-16 单元；
+/// 指定 --full-title 时先用 `GetWindowTextLengthW` 取得长度再分配缓冲区，可读取任意长度的标题。
+unsafe fn window_title(hwnd: HWND) -> String {
+    let capacity = if FULL_TITLE.load(Ordering::Relaxed) {
+        // 长度可能只是上限（例如 ANSI/Unicode 混用时），以 GetWindowTextW 实际返回的长度为准
+        GetWindowTextLengthW(hwnd).max(0) as usize + 1
+    } else {
+        TITLE_BUFFER_LEN
+    };
+
+    let mut title = vec![0u16; capacity];
+    let title_len = GetWindowTextW(hwnd, &mut title).max(0) as usize;
+    String::from_utf16_lossy(&title[..title_len.min(capacity)])
+}
+
+/// 窗口枚举上下文，通过 LPARAM 传递给回调
+struct EnumContext {
+    target_pid: Option<u32>,
+    include_hidden: bool,
+    rect_mode: RectMode,
+    desktops: Option<VirtualDesktops>,
+    details: bool,
+    windows: Vec<(WindowInfo, isize)>,
+}
+
+/// 枚举顶层窗口，同时保存 HWND
+///
+/// 指定 `target_pid` 时，回调会先比较 PID 再读取标题和尺寸，
+/// 避免对无关窗口调用 `GetWindowTextW`/`GetClassNameW`/`GetWindowRect`。
+/// 指定 `target_tid` 时用 `EnumThreadWindows` 只枚举该线程的窗口。
+/// `include_hidden` 为 true 时同时返回不可见的窗口（用于 `windows/show`）。
+/// `rect_mode` 决定窗口矩形的来源（见 `window_rect`）。
+/// `with_desktop` 为 true 时记录窗口所在的虚拟桌面序号。
+/// `details` 为 false 时不做逐个窗口的跨进程查询（响应探测、UI Automation），只用于构建进程列表。
+fn enumerate_windows(
+    target_pid: Option<u32>,
+    target_tid: Option<u32>,
+    include_hidden: bool,
+    rect_mode: RectMode,
+    with_desktop: bool,
+    details: bool,
+) -> Vec<(WindowInfo, isize)> {
+    let mut context = EnumContext {
+        target_pid,
+        include_hidden,
+        rect_mode,
+        desktops: if with_desktop { VirtualDesktops::load() } else { None },
+        details,
+        windows: Vec::new(),
+    };
+
+    unsafe {
+        let lparam = LPARAM(&mut context as *mut _ as isize);
+        match target_tid {
+            // 线程不存在或没有窗口时返回 FALSE，结果为空列表
+            Some(tid) => {
+                let _ = EnumThreadWindows(tid, Some(enum_window_callback), lparam);
+            }
+            None => {
+                let _ = EnumWindows(Some(enum_window_callback), lparam);
+            }
+        }
+    }
+
+    context.windows
 }
 
 unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
-    let windows = &mut *(lparam.0 as *mut Vec<WindowInfo>);
+    let context = &mut *(lparam.0 as *mut EnumContext);
 
-    if IsWindowVisible(hwnd).as_bool() {
-        let mut title = [0u16; 512];
-        let title_len = GetWindowTextW(hwnd, &mut title);
-        
-        if title_len > 0 {
-            let title_str = String::from_utf16_lossy(&title[..title_len as usize]);
-            
-            // 跳过空标题或系统窗口
-            if !title_str.trim().is_empty() && !is_system_window(hwnd) {
-                let mut pid: u32 = 0;
-                GetWindowThreadProcessId(hwnd, Some(&mut pid));
-                
-                let mut rect = std::mem::zeroed();
-                if GetWindowRect(hwnd, &mut rect).is_ok() {
-                    let window_info = WindowInfo {
-                        pid,
-                        title: title_str,
-                        rect: WindowRect::new(
-                            rect.left,
-                            rect.top,
-                            rect.right - rect.left,
-                            rect.bottom - rect.top
-                        ),
-                    };
-                    
-                    windows.push(window_info);
-                }
+    // 先取 PID：GetWindowThreadProcessId 不会向目标窗口发送消息，开销很小
+    let mut pid: u32 = 0;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+
+    if let Some(target_pid) = context.target_pid {
+        if pid != target_pid {
+            return true.into(); // 跳过无关进程的窗口
+        }
+    }
+
+    if context.include_hidden || IsWindowVisible(hwnd).as_bool() {
+        let title_str = window_title(hwnd);
+
+        // 跳过空标题或系统窗口
+        if !title_str.trim().is_empty() && !is_system_window(hwnd) {
+            if let Some(rect) = window_rect(hwnd, context.rect_mode) {
+                let window_info = WindowInfo {
+                    pid,
+                    title: title_str,
+                    rect,
+                    desktop: context.desktops.as_ref().and_then(|desktops| desktops.index_of(hwnd)),
+                    responding: if context.details { probe_responding(hwnd) } else { None },
+                    ex_style: GetWindowLongW(hwnd, GWL_EXSTYLE) as u32,
+                    class: window_class(hwnd).unwrap_or_default(),
+                    uia: if context.details { uia_properties(hwnd) } else { None },
+                };
+
+                context.windows.push((window_info, hwnd.0));
             }
         }
     }
@@ -299,6 +994,288 @@ unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BO
     true.into() // Continue enumeration
 }
 
+/// 枚举时是否探测窗口是否响应（windows/get 需要按响应状态过滤或输出该字段时）
+static PROBE_RESPONDING: AtomicBool = AtomicBool::new(false);
+
+/// 设置枚举时是否探测窗口响应状态；未探测时 `responding` 为 None
+pub fn set_probe_responding(enabled: bool) {
+    PROBE_RESPONDING.store(enabled, Ordering::Relaxed);
+}
+
+/// 枚举时的响应探测：每个窗口都是一次跨进程调用，挂起的窗口要等到超时，因此只在需要时探测
+unsafe fn probe_responding(hwnd: HWND) -> Option<bool> {
+    PROBE_RESPONDING.load(Ordering::Relaxed).then(|| is_responding(hwnd))
+}
+
+/// 未响应探测的超时时间（毫秒）
+const RESPONDING_PROBE_TIMEOUT_MS: u32 = 200;
+
+/// 向窗口发送 `WM_NULL` 探测其是否响应消息
+///
+/// `SMTO_ABORTIFHUNG` 使系统已判定为挂起的窗口立即返回；超时或失败都视为未响应。
+unsafe fn is_responding(hwnd: HWND) -> bool {
+    let mut result = 0usize;
+    let ret = SendMessageTimeoutW(
+        hwnd,
+        WM_NULL,
+        WPARAM(0),
+        LPARAM(0),
+        SMTO_ABORTIFHUNG | SMTO_BLOCK,
+        RESPONDING_PROBE_TIMEOUT_MS,
+        Some(&mut result),
+    );
+    ret.0 != 0
+}
+
+/// 获取窗口图标句柄：依次尝试 `WM_GETICON`（大图标、小图标）和窗口类图标
+///
+/// 图标归窗口或窗口类所有，调用方不能销毁。
+unsafe fn window_icon(hwnd: HWND) -> Option<HICON> {
+    for kind in [ICON_BIG, ICON_SMALL2] {
+        let mut result = 0usize;
+        let ret = SendMessageTimeoutW(
+            hwnd,
+            WM_GETICON,
+            WPARAM(kind as usize),
+            LPARAM(0),
+            SMTO_ABORTIFHUNG | SMTO_BLOCK,
+            RESPONDING_PROBE_TIMEOUT_MS,
+            Some(&mut result),
+        );
+        if ret.0 != 0 && result != 0 {
+            return Some(HICON(result as isize));
+        }
+    }
+
+    [GCLP_HICON, GCLP_HICONSM]
+        .into_iter()
+        .map(|index| class_long(hwnd, index))
+        .find(|&handle| handle != 0)
+        .map(HICON)
+}
+
+#[cfg(target_pointer_width = "64")]
+unsafe fn class_long(hwnd: HWND, index: GET_CLASS_LONG_INDEX) -> isize {
+    GetClassLongPtrW(hwnd, index) as isize
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+unsafe fn class_long(hwnd: HWND, index: GET_CLASS_LONG_INDEX) -> isize {
+    GetClassLongW(hwnd, index) as isize
+}
+
+/// 将图标转换为 RGBA：32 位图标直接使用其 alpha 通道，旧式图标根据 AND 掩码计算透明度
+unsafe fn icon_to_rgba(icon: HICON) -> AppResult<CapturedImage> {
+    let mut info = ICONINFO::default();
+    if GetIconInfo(icon, &mut info).is_err() {
+        return Err(last_error("Failed to read window icon"));
+    }
+
+    let result = if info.hbmColor.0 == 0 {
+        Err(AppError::feature_not_supported("Monochrome window icons"))
+    } else {
+        let mut bitmap = BITMAP::default();
+        let size = std::mem::size_of::<BITMAP>() as i32;
+        if GetObjectW(info.hbmColor, size, Some(&mut bitmap as *mut BITMAP as *mut _)) == 0 {
+            Err(AppError::platform("Failed to read icon bitmap size"))
+        } else {
+            let (width, height) = (bitmap.bmWidth, bitmap.bmHeight);
+            let screen_dc = GetDC(HWND(0));
+            let color = bitmap_pixels(screen_dc, info.hbmColor, width, height);
+            let mask = bitmap_pixels(screen_dc, info.hbmMask, width, height);
+            ReleaseDC(HWND(0), screen_dc);
+
+            color
+                .map(|mut pixels| {
+                    // BGRA -> RGBA；所有 alpha 为 0 表示旧式图标，AND 掩码中白色的像素透明
+                    let has_alpha = pixels.chunks_exact(4).any(|pixel| pixel[3] != 0);
+                    for (i, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+                        pixel.swap(0, 2);
+                        if !has_alpha {
+                            let transparent = mask.as_ref().is_some_and(|mask| mask[i * 4] != 0);
+                            pixel[3] = if transparent { 0 } else { 255 };
+                        }
+                    }
+                    CapturedImage { width: width as u32, height: height as u32, rgba: pixels }
+                })
+                .ok_or_else(|| AppError::platform("Failed to read icon pixels"))
+        }
+    };
+
+    // GetIconInfo 返回的位图由调用方释放
+    let _ = DeleteObject(info.hbmColor);
+    let _ = DeleteObject(info.hbmMask);
+    result
+}
+
+/// 以 32 位自上而下的格式读取位图像素（BGRA）
+unsafe fn bitmap_pixels(hdc: HDC, bitmap: HBITMAP, width: i32, height: i32) -> Option<Vec<u8>> {
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+
+    let mut info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    let lines = GetDIBits(hdc, bitmap, 0, height as u32, Some(pixels.as_mut_ptr() as *mut _), &mut info, DIB_RGB_COLORS);
+    (lines != 0).then_some(pixels)
+}
+
+/// 获取窗口矩形
+///
+/// 最小化窗口的 `GetWindowRect` 为 (-32000, -32000) 之类的屏幕外坐标，
+/// 除非 `rect_mode` 为 `Raw`，否则改用 `GetWindowPlacement` 的还原位置。
+/// 注意 `rcNormalPosition` 使用工作区坐标，任务栏位于顶部/左侧时会有相应偏移。
+/// `Frame` 模式下使用 DWM 报告的可见边框，DWM 不可用时退回 `GetWindowRect`。
+unsafe fn window_rect(hwnd: HWND, rect_mode: RectMode) -> Option<WindowRect> {
+    if rect_mode != RectMode::Raw && IsIconic(hwnd).as_bool() {
+        let mut placement = WINDOWPLACEMENT {
+            length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+            ..Default::default()
+        };
+        if GetWindowPlacement(hwnd, &mut placement).is_ok() {
+            let normal = placement.rcNormalPosition;
+            return Some(WindowRect::new(normal.left, normal.top, normal.right - normal.left, normal.bottom - normal.top));
+        }
+    }
+
+    if rect_mode == RectMode::Frame {
+        if let Some(frame) = frame_rect(hwnd) {
+            return Some(frame);
+        }
+    }
+
+    let mut rect = std::mem::zeroed();
+    if GetWindowRect(hwnd, &mut rect).is_ok() {
+        Some(WindowRect::new(rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top))
+    } else {
+        None
+    }
+}
+
+/// DWM 扩展边框（`DWMWA_EXTENDED_FRAME_BOUNDS`）
+///
+/// Windows 10/11 的 `GetWindowRect` 包含左、右、下三边各约 7 像素的透明阴影边框，
+/// 该矩形不含阴影，与屏幕上可见的窗口边缘一致。
+unsafe fn frame_rect(hwnd: HWND) -> Option<WindowRect> {
+    let mut rect = RECT::default();
+    DwmGetWindowAttribute(
+        hwnd,
+        DWMWA_EXTENDED_FRAME_BOUNDS,
+        &mut rect as *mut RECT as *mut _,
+        std::mem::size_of::<RECT>() as u32,
+    )
+    .ok()?;
+    Some(WindowRect::new(rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top))
+}
+
+/// 虚拟桌面注册表键（HKCU）：`VirtualDesktopIDs` 按任务视图中的顺序保存各桌面的 GUID
+const VIRTUAL_DESKTOPS_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\VirtualDesktops";
+
+/// 虚拟桌面查询：`IVirtualDesktopManager` 只给出桌面 GUID，序号从注册表中的桌面列表换算
+struct VirtualDesktops {
+    manager: IVirtualDesktopManager,
+    ids: Vec<GUID>,
+}
+
+impl VirtualDesktops {
+    /// 创建虚拟桌面管理器并读取桌面列表；系统不支持或 Explorer 未运行时返回 None
+    fn load() -> Option<Self> {
+        unsafe {
+            // 当前线程已初始化 COM（包括线程模型不同）时返回的错误不影响后续调用
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+            let manager: IVirtualDesktopManager = CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL).ok()?;
+            // 只有一个桌面时注册表中可能没有桌面列表
+            let ids = read_registry_guids(VIRTUAL_DESKTOPS_KEY, "VirtualDesktopIDs").unwrap_or_default();
+            Some(Self { manager, ids })
+        }
+    }
+
+    /// 窗口所在虚拟桌面的序号（从 1 开始）；固定到所有桌面的窗口或查询失败时返回 None
+    fn index_of(&self, hwnd: HWND) -> Option<u32> {
+        let id = unsafe { self.manager.GetWindowDesktopId(hwnd) }.ok()?;
+        desktop_index(&self.ids, &id)
+    }
+}
+
+/// 根据桌面列表把 GUID 换算为序号（从 1 开始）；列表为空时只有一个桌面
+fn desktop_index(ids: &[GUID], id: &GUID) -> Option<u32> {
+    if *id == GUID::zeroed() {
+        return None;
+    }
+    if ids.is_empty() {
+        return Some(1);
+    }
+    ids.iter().position(|d| d == id).map(|i| i as u32 + 1)
+}
+
+/// 读取 HKCU 下的 REG_BINARY 值，按 16 字节一组解析为 GUID 列表
+unsafe fn read_registry_guids(subkey: &str, value: &str) -> Option<Vec<GUID>> {
+    let subkey: Vec<u16> = subkey.encode_utf16().chain(std::iter::once(0)).collect();
+    let value: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let mut size = 0u32;
+    RegGetValueW(HKEY_CURRENT_USER, PCWSTR(subkey.as_ptr()), PCWSTR(value.as_ptr()), RRF_RT_REG_BINARY, None, None, Some(&mut size)).ok()?;
+    let mut data = vec![0u8; size as usize];
+    RegGetValueW(
+        HKEY_CURRENT_USER,
+        PCWSTR(subkey.as_ptr()),
+        PCWSTR(value.as_ptr()),
+        RRF_RT_REG_BINARY,
+        None,
+        Some(data.as_mut_ptr().cast()),
+        Some(&mut size),
+    ).ok()?;
+
+    // 注册表中的 GUID 为内存布局（前三段小端序）
+    Some(data[..size as usize]
+        .chunks_exact(16)
+        .map(|b| GUID::from_values(
+            u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            u16::from_le_bytes([b[4], b[5]]),
+            u16::from_le_bytes([b[6], b[7]]),
+            [b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]],
+        ))
+        .collect())
+}
+
+/// 当前虚拟桌面的序号（从 1 开始）
+///
+/// Windows 11 将当前桌面保存在 `VirtualDesktops` 键下，Windows 10 保存在按会话区分的
+/// `SessionInfo\<会话 ID>\VirtualDesktops` 键下；两处都没有时说明只有一个桌面。
+pub fn current_virtual_desktop() -> AppResult<u32> {
+    unsafe {
+        let ids = read_registry_guids(VIRTUAL_DESKTOPS_KEY, "VirtualDesktopIDs").unwrap_or_default();
+
+        let mut session = 0u32;
+        let _ = ProcessIdToSessionId(std::process::id(), &mut session);
+        let session_key = format!(
+            r"Software\Microsoft\Windows\CurrentVersion\Explorer\SessionInfo\{}\VirtualDesktops", session
+        );
+
+        let current = read_registry_guids(VIRTUAL_DESKTOPS_KEY, "CurrentVirtualDesktop")
+            .or_else(|| read_registry_guids(&session_key, "CurrentVirtualDesktop"))
+            .and_then(|guids| guids.first().copied());
+
+        match current {
+            Some(id) => desktop_index(&ids, &id)
+                .ok_or_else(|| AppError::platform("Current virtual desktop is not in the desktop list")),
+            None if ids.len() <= 1 => Ok(1),
+            None => Err(AppError::platform("Failed to determine the current virtual desktop")),
+        }
+    }
+}
+
 fn is_system_window(hwnd: HWND) -> bool {
     unsafe {
         let mut class_name = [0u16; 256];
@@ -315,94 +1292,192 @@ fn is_system_window(hwnd: HWND) -> bool {
 
 // 修改 find_windows 函数来保存实际的 HWND
 pub fn find_windows(
-    pid_filter: &Option<String>,
-    name_filter: &Option<String>,
-    title_filter: &Option<String>,
-    process_names: &[(u32, String)],
-) -> Vec<crate::platform::WindowHandle> {
+    filter: &WindowFilter,
+    process_names: &ProcessNameIndex,
+) -> AppResult<Vec<crate::platform::WindowHandle>> {
     use crate::platform::{WindowHandle, PlatformData};
+
+    // --at-cursor：只操作鼠标指针下的窗口，不再匹配其他过滤条件
+    if filter.at_cursor {
+        let hwnd = window_under_cursor()?;
+        let window = unsafe { describe_window(hwnd, RectMode::Restored, None) };
+        let platform_data = PlatformData::Windows(WindowsWindowData::new(hwnd.0));
+        return Ok(vec![WindowHandle::new(window.pid, window.title, window.rect, platform_data)]);
+    }
     
-    let mut windows_with_handles: Vec<(WindowInfo, isize)> = Vec::new();
+    // 单一 PID 时只枚举该进程的窗口，指定 --tid 时只枚举该线程的窗口
+    let windows_with_handles = enumerate_windows(filter.single_pid(), filter.tid, filter.include_hidden, RectMode::Restored, false, true);
     
-    // 自定义枚举回调来保存 HWND
-    unsafe extern "system" fn enum_window_callback_with_handle(hwnd: HWND, lparam: LPARAM) -> BOOL {
-        let windows = &mut *(lparam.0 as *mut Vec<(WindowInfo, isize)>);
+    let handles = windows_with_handles
+        .into_iter()
+        .filter(|(window, _)| filter.matches(window, process_names))
+        .map(|(window, hwnd)| {
+            // 使用实际的 HWND 创建窗口句柄，保留枚举时的位置以便按位置排序
+            let platform_data = PlatformData::Windows(WindowsWindowData::new(hwnd));
+            WindowHandle::new(window.pid, window.title, window.rect, platform_data)
+        })
+        .collect();
 
-        if IsWindowVisible(hwnd).as_bool() {
-            let mut title = [0u16; 512];
-            let title_len = GetWindowTextW(hwnd, &mut title);
-            
-            if title_len > 0 {
-                let title_str = String::from_utf16_lossy(&title[..title_len as usize]);
-                
-                // 跳过空标题或系统窗口
-                if !title_str.trim().is_empty() && !is_system_window(hwnd) {
-                    let mut pid: u32 = 0;
-                    GetWindowThreadProcessId(hwnd, Some(&mut pid));
-                    
-                    let mut rect = std::mem::zeroed();
-                    if GetWindowRect(hwnd, &mut rect).is_ok() {
-                        let window_info = WindowInfo {
-                            pid,
-                            title: title_str,
-                            rect: WindowRect::new(
-                                rect.left,
-                                rect.top,
-                                rect.right - rect.left,
-                                rect.bottom - rect.top
-                            ),
-                        };
-                        
-                        windows.push((window_info, hwnd.0));
-                    }
-                }
-            }
-        }
+    Ok(handles)
+}
+
+/// 运行时检查窗口枚举能力：Win32 桌面总是可用
+pub fn probe_window_support() -> AppResult<()> {
+    Ok(())
+}
+
+thread_local! {
+    /// 已查询过的 SID -> 账户名（包括无法解析的 SID），--watch 等循环中不再重复查询
+    static ACCOUNT_NAMES: std::cell::RefCell<std::collections::HashMap<String, Option<String>>> = Default::default();
+}
 
-        true.into() // Continue enumeration
+/// 将 SID 字符串（如 "S-1-5-18"）解析为账户名，用于用户列表中没有的系统/服务账户
+pub fn lookup_account_name(sid: &str) -> Option<String> {
+    if let Some(name) = ACCOUNT_NAMES.with(|names| names.borrow().get(sid).cloned()) {
+        return name;
     }
-    
+    let name = query_account_name(sid);
+    ACCOUNT_NAMES.with(|names| names.borrow_mut().insert(sid.to_string(), name.clone()));
+    name
+}
+
+/// 通过 LookupAccountSidW 查询 SID 对应的账户名
+fn query_account_name(sid: &str) -> Option<String> {
+    let wide: Vec<u16> = sid.encode_utf16().chain(std::iter::once(0)).collect();
     unsafe {
-        let _ = EnumWindows(Some(enum_window_callback_with_handle), LPARAM(&mut windows_with_handles as *mut _ as isize));
+        let mut psid = PSID::default();
+        ConvertStringSidToSidW(PCWSTR(wide.as_ptr()), &mut psid).ok()?;
+
+        let mut name = [0u16; 256];
+        let mut name_len = name.len() as u32;
+        let mut domain = [0u16; 256];
+        let mut domain_len = domain.len() as u32;
+        let mut name_use = SID_NAME_USE(0);
+        let result = LookupAccountSidW(
+            PCWSTR::null(),
+            psid,
+            PWSTR(name.as_mut_ptr()),
+            &mut name_len,
+            PWSTR(domain.as_mut_ptr()),
+            &mut domain_len,
+            &mut name_use,
+        );
+        // ConvertStringSidToSidW 分配的内存需要用 LocalFree 释放
+        let _ = LocalFree(HLOCAL(psid.0));
+
+        result.ok()?;
+        Some(String::from_utf16_lossy(&name[..name_len as usize]))
     }
-    
-    let mut result = Vec::new();
-
-    for (window, hwnd) in windows_with_handles {
-        // PID filter
-        if let Some(pid_str) = pid_filter {
-            if let Ok(filter_pid) = pid_str.parse::<u32>() {
-                if window.pid != filter_pid {
-                    continue;
+}
+
+/// 进程位数："x86"（32 位）或 "x64"（64 位）；无法打开进程时返回 None
+pub fn process_bitness(pid: u32) -> Option<String> {
+    unsafe {
+        // 32 位的 pscan 自身运行在 WOW64 下时，说明系统是 64 位
+        let mut self_wow64 = BOOL(0);
+        let os_64bit = cfg!(target_pointer_width = "64")
+            || (IsWow64Process(GetCurrentProcess(), &mut self_wow64).is_ok() && self_wow64.as_bool());
+
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, BOOL(0), pid).ok()?;
+        let mut wow64 = BOOL(0);
+        let result = IsWow64Process(process, &mut wow64);
+        let _ = CloseHandle(process);
+        result.ok()?;
+
+        // 64 位系统上运行在 WOW64 下的进程为 32 位
+        Some(if os_64bit && !wow64.as_bool() { "x64" } else { "x86" }.to_string())
+    }
+}
+
+/// 查询服务控制管理器：PID -> 该进程承载的运行中服务名（多个服务以 ", " 连接）
+///
+/// 无法连接服务控制管理器时返回空表。
+pub fn process_services() -> std::collections::HashMap<u32, String> {
+    let mut services: std::collections::HashMap<u32, Vec<String>> = std::collections::HashMap::new();
+
+    unsafe {
+        let Ok(manager) = OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), SC_MANAGER_ENUMERATE_SERVICE) else {
+            return std::collections::HashMap::new();
+        };
+
+        // 以 u64 为单位分配缓冲区，保证 ENUM_SERVICE_STATUS_PROCESSW 的对齐
+        let mut buffer: Vec<u64> = Vec::new();
+        let mut resume = 0u32;
+        loop {
+            let mut needed = 0u32;
+            let mut returned = 0u32;
+            let bytes = std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8);
+            let more = match EnumServicesStatusExW(
+                manager,
+                SC_ENUM_PROCESS_INFO,
+                SERVICE_WIN32,
+                SERVICE_ACTIVE,
+                Some(bytes),
+                &mut needed,
+                &mut returned,
+                Some(&mut resume),
+                PCWSTR::null(),
+            ) {
+                Ok(()) => false,
+                Err(e) if win32_code(&e) == ERROR_MORE_DATA.0 => true,
+                Err(_) => break,
+            };
+
+            // 服务名字符串位于同一缓冲区内，需要在扩容前读取
+            let entries = std::slice::from_raw_parts(buffer.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESSW, returned as usize);
+            for entry in entries {
+                let pid = entry.ServiceStatusProcess.dwProcessId;
+                if let (true, Ok(name)) = (pid != 0, entry.lpServiceName.to_string()) {
+                    services.entry(pid).or_default().push(name);
                 }
             }
-        }
 
-        // Name filter
-        if let Some(name) = name_filter {
-            let process_name = process_names
-                .iter()
-                .find(|(process_pid, _)| *process_pid == window.pid)
-                .map(|(_, name)| name.to_lowercase())
-                .unwrap_or_default();
-            
-            if !process_name.contains(&name.to_lowercase()) {
-                continue;
+            if !more || (returned == 0 && needed as usize <= buffer.len() * 8) {
+                break;
             }
+            buffer.resize(buffer.len().max((needed as usize).div_ceil(8)), 0);
         }
 
-        // Title filter
-        if let Some(title) = title_filter {
-            if !window.title.to_lowercase().contains(&title.to_lowercase()) {
-                continue;
-            }
-        }
+        let _ = CloseServiceHandle(manager);
+    }
 
-        // 使用实际的 HWND 创建窗口句柄
-        let platform_data = PlatformData::Windows(WindowsWindowData::new(hwnd));
-        let handle = WindowHandle::new(window.pid, window.title, platform_data);
-        result.push(handle);
+    services.into_iter().map(|(pid, names)| (pid, names.join(", "))).collect()
+}
+
+/// 将控制台输出代码页设为 UTF-8，避免中文等 Unicode 标题乱码
+pub fn enable_utf8_console() {
+    // 输出被重定向（非控制台）时调用会失败，忽略即可
+    unsafe {
+        let _ = SetConsoleOutputCP(CP_UTF8);
     }
+}
 
-    result
-}
\ No newline at end of file
+/// 获取所有显示器的工作区（不含任务栏）
+pub fn get_monitor_work_areas() -> AppResult<Vec<WindowRect>> {
+    let mut areas: Vec<WindowRect> = Vec::new();
+
+    unsafe {
+        let _ = EnumDisplayMonitors(HDC(0), None, Some(enum_monitor_callback), LPARAM(&mut areas as *mut _ as isize));
+    }
+
+    if areas.is_empty() {
+        return Err(AppError::platform("Failed to enumerate monitors"));
+    }
+
+    Ok(areas)
+}
+
+unsafe extern "system" fn enum_monitor_callback(hmonitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, lparam: LPARAM) -> BOOL {
+    let areas = &mut *(lparam.0 as *mut Vec<WindowRect>);
+
+    let mut info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    if GetMonitorInfoW(hmonitor, &mut info).as_bool() {
+        let work = info.rcWork;
+        areas.push(WindowRect::new(work.left, work.top, work.right - work.left, work.bottom - work.top));
+    }
+
+    true.into() // Continue enumeration
+}
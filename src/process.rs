@@ -1,19 +1,54 @@
-use sysinfo::{System, Process};
-use crate::types::ProcessInfo;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use sysinfo::{System, Process, Pid, Users};
+use crate::types::{ProcessInfo, ProcessNameCount};
 use crate::window::get_all_windows;
+use crate::filter::WindowFilter;
 
-pub fn get_processes() -> Vec<ProcessInfo> {
+/// `get_processes` 需要额外查询的字段
+///
+/// 这些字段需要逐个进程或逐个窗口的系统调用，未请求时保持空值，
+/// 避免窗口操作和轮询循环每次都对整个系统做跨进程查询。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessFields {
+    /// 枚举桌面上的所有窗口，填充 `has_window` 和窗口标题；否则 `has_window` 为 false，标题为命令行
+    pub windows: bool,
+    /// 查询进程位数（Windows 上每个进程一次 OpenProcess + IsWow64Process）
+    pub bitness: bool,
+    /// 查询进程承载的服务（Windows 上枚举一次服务控制管理器中的所有服务）
+    pub services: bool,
+    /// 解析进程所属用户（Windows 上系统/服务账户需要按 SID 查询账户名）
+    pub user: bool,
+}
+
+pub fn get_processes(fields: ProcessFields) -> Vec<ProcessInfo> {
     let mut system = System::new_all();
     
     // Refresh process information
     system.refresh_all();
+    let total_memory = system.total_memory();
+    let users = if fields.user { Users::new_with_refreshed_list() } else { Users::new() };
+
+    // Linux 上 pscan 自身的工作线程（sysinfo 刷新时创建）会作为独立条目出现，始终忽略；
+    // 主进程本身保留，由 --exclude-self 决定是否排除
+    let own_pid = Pid::from_u32(std::process::id());
+    let own_threads = system
+        .process(own_pid)
+        .and_then(|p| p.tasks())
+        .cloned()
+        .unwrap_or_default();
     
     // First get all window information
-    let window_info = get_all_windows();
+    let window_info = if fields.windows { get_all_windows() } else { Vec::new() };
+    let services = if fields.services { crate::platform::process_services() } else { HashMap::new() };
     
     let mut processes = Vec::new();
 
     for (pid, process) in system.processes() {
+        if *pid != own_pid && own_threads.contains(pid) {
+            continue;
+        }
+
         let pid_str = pid.to_string();
         let pid_u32 = pid.as_u32();
         
@@ -27,10 +62,16 @@ pub fn get_processes() -> Vec<ProcessInfo> {
         
         let process_info = ProcessInfo {
             pid: pid_str,
-            name: process.name().to_string(),
+            name: executable_name(process.name(), process.exe()),
             title,
             memory_usage: process.memory(),
+            memory_percent: memory_percent(process.memory(), total_memory),
             has_window,
+            user: if fields.user { resolve_user(process, &users) } else { None },
+            bitness: if fields.bitness { crate::platform::process_bitness(pid_u32) } else { None },
+            service: services.get(&pid_u32).cloned(),
+            parent_pid: process.parent().map(|parent| parent.as_u32()),
+            ancestor: false,
         };
         
         processes.push(process_info);
@@ -39,11 +80,50 @@ pub fn get_processes() -> Vec<ProcessInfo> {
     processes
 }
 
+/// 进程名称：优先使用可执行文件的文件名
+///
+/// Windows 上 sysinfo 的 `process.name()` 有时被截断为 15 个字符或缺少 `.exe` 后缀，
+/// 而 `process.exe()` 给出完整路径；无法获取路径（如权限不足、内核线程）时回退到 `name`。
+fn executable_name(name: &str, exe: Option<&Path>) -> String {
+    exe.and_then(|path| path.file_name())
+        .and_then(|file_name| file_name.to_str())
+        .filter(|file_name| !file_name.is_empty())
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// 解析进程所属用户名：优先使用系统用户列表，找不到时交给平台层解析（Windows 为 SID 查询）
+fn resolve_user(process: &Process, users: &Users) -> Option<String> {
+    let uid = process.user_id()?;
+    match users.get_user_by_id(uid) {
+        Some(user) => Some(user.name().to_string()),
+        // Unix 上 Uid 为数字 uid，Windows 上为 SID 字符串（"S-1-5-..."）
+        None => crate::platform::lookup_account_name(&(**uid).to_string()),
+    }
+}
+
+/// 检查进程所属用户是否匹配（不区分大小写，忽略 Windows 域名前缀）
+fn matches_user(process_user: Option<&str>, filter: &str) -> bool {
+    process_user.is_some_and(|user| {
+        let name = user.rsplit('\\').next().unwrap_or(user);
+        user.eq_ignore_ascii_case(filter) || name.eq_ignore_ascii_case(filter)
+    })
+}
+
+/// 计算内存占用占系统总内存的百分比；总内存未知时返回 0
+fn memory_percent(memory: u64, total_memory: u64) -> f64 {
+    if total_memory == 0 {
+        0.0
+    } else {
+        memory as f64 / total_memory as f64 * 100.0
+    }
+}
+
 fn get_process_title_fallback(process: &Process) -> String {
     // Use command line arguments as fallback title
     let cmd = process.cmd();
     if !cmd.is_empty() {
-        return cmd.join(" ");
+        return quote_command_line(cmd);
     }
     
     // If no command line arguments, use executable path
@@ -54,48 +134,241 @@ fn get_process_title_fallback(process: &Process) -> String {
     "No Title".to_string()
 }
 
+/// 把命令行参数拼接为可直接复制粘贴的字符串，按平台规则给含空格或引号的参数加引号
+fn quote_command_line(args: &[String]) -> String {
+    let quote: fn(&str) -> String = if cfg!(windows) { quote_arg_windows } else { quote_arg_posix };
+    args.iter().map(|arg| quote(arg)).collect::<Vec<_>>().join(" ")
+}
+
+/// POSIX shell：含特殊字符的参数用单引号包裹，内部的单引号写作 `'\''`
+fn quote_arg_posix(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=,+@%".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Windows（`CommandLineToArgvW` 规则）：用双引号包裹，引号前及结尾的反斜杠需要加倍
+fn quote_arg_windows(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '"']) {
+        return arg.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+            }
+        }
+        if c != '\\' {
+            quoted.push(c);
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// 沿父进程链向上查找匹配进程的祖先（直到根进程），返回标记为祖先的副本
+///
+/// 已在匹配结果中的进程和重复出现的祖先只保留一份；PID 复用导致的环路会被截断。
+pub fn collect_ancestors(processes: &[ProcessInfo], matched: &[&ProcessInfo]) -> Vec<ProcessInfo> {
+    let by_pid: HashMap<&str, &ProcessInfo> = processes.iter().map(|p| (p.pid.as_str(), p)).collect();
+    let mut seen: HashSet<&str> = matched.iter().map(|p| p.pid.as_str()).collect();
+    let mut ancestors = Vec::new();
+
+    for process in matched {
+        let mut parent_pid = process.parent_pid;
+        while let Some(parent) = parent_pid.and_then(|pid| by_pid.get(pid.to_string().as_str())) {
+            if !seen.insert(parent.pid.as_str()) {
+                break;
+            }
+            ancestors.push(ProcessInfo { ancestor: true, ..(*parent).clone() });
+            parent_pid = parent.parent_pid;
+        }
+    }
+
+    ancestors
+}
+
+/// 按进程名汇总进程数量和内存占用，按首次出现的顺序排列
+pub fn summarize_by_name(processes: &[&ProcessInfo]) -> Vec<ProcessNameCount> {
+    let mut summary: Vec<ProcessNameCount> = Vec::new();
+    for process in processes {
+        let memory_mb = process.memory_usage as f64 / 1024.0 / 1024.0;
+        match summary.iter_mut().find(|entry| entry.name == process.name) {
+            Some(entry) => {
+                entry.count += 1;
+                entry.total_memory_mb += memory_mb;
+            }
+            None => summary.push(ProcessNameCount { name: process.name.clone(), count: 1, total_memory_mb: memory_mb }),
+        }
+    }
+    summary
+}
+
+/// 与上一轮（PID → 内存占用）相比的内存变化（字节），新出现的进程取完整值
+pub fn memory_deltas(previous: &HashMap<String, u64>, processes: &[&ProcessInfo]) -> Vec<i64> {
+    processes
+        .iter()
+        .map(|process| {
+            let before = previous.get(&process.pid).copied().unwrap_or(0);
+            process.memory_usage as i64 - before as i64
+        })
+        .collect()
+}
+
 pub fn filter_processes<'a>(
     processes: &'a [ProcessInfo],
-    pid_filter: &Option<String>,
-    name_filter: &Option<String>,
-    title_filter: &Option<String>,
+    filter: &WindowFilter,
     has_window_filter: bool,
     no_window_filter: bool,
+    min_memory_percent: Option<f64>,
+    user: Option<&str>,
+    bitness: Option<&str>,
 ) -> Vec<&'a ProcessInfo> {
     processes
         .iter()
         .filter(|p| {
-            // PID filter
-            if let Some(pid) = pid_filter {
-                if p.pid != *pid {
-                    return false;
-                }
+            // PID / name / title filter
+            if !filter.matches_process(p) {
+                return false;
             }
 
-            // Name filter
-            if let Some(name) = name_filter {
-                if !p.name.to_lowercase().contains(&name.to_lowercase()) {
-                    return false;
-                }
+            // Window presence filter
+            if has_window_filter && !p.has_window {
+                return false;
             }
 
-            // Title filter
-            if let Some(title) = title_filter {
-                if !p.title.to_lowercase().contains(&title.to_lowercase()) {
-                    return false;
-                }
+            if no_window_filter && p.has_window {
+                return false;
             }
 
-            // Window presence filter
-            if has_window_filter && !p.has_window {
+            // Memory threshold filter
+            if min_memory_percent.is_some_and(|min| p.memory_percent < min) {
                 return false;
             }
 
-            if no_window_filter && p.has_window {
+            // Owner filter
+            if let Some(user) = user {
+                if !matches_user(p.user.as_deref(), user) {
+                    return false;
+                }
+            }
+
+            // Architecture filter (processes with unknown bitness never match)
+            if bitness.is_some_and(|bitness| p.bitness.as_deref() != Some(bitness)) {
                 return false;
             }
 
             true
         })
         .collect()
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_percent() {
+        assert_eq!(memory_percent(0, 0), 0.0);
+        assert_eq!(memory_percent(512, 2048), 25.0);
+        assert_eq!(memory_percent(2048, 2048), 100.0);
+    }
+
+    #[test]
+    fn test_executable_name() {
+        // 截断的名称由可执行文件名补全
+        let exe = Path::new("C:/Program Files/App/VeryLongApplicationName.exe");
+        assert_eq!(executable_name("VeryLongApplica", Some(exe)), "VeryLongApplicationName.exe");
+        assert_eq!(executable_name("bash", Some(Path::new("/usr/bin/bash"))), "bash");
+        // 无法获取路径时回退到 sysinfo 的名称
+        assert_eq!(executable_name("System", None), "System");
+        assert_eq!(executable_name("kthreadd", Some(Path::new(""))), "kthreadd");
+    }
+
+    #[test]
+    fn test_quote_command_line() {
+        assert_eq!(quote_arg_posix("--flag=1"), "--flag=1");
+        assert_eq!(quote_arg_posix("/tmp/my file"), "'/tmp/my file'");
+        assert_eq!(quote_arg_posix("it's"), "'it'\\''s'");
+        assert_eq!(quote_arg_posix(""), "''");
+
+        assert_eq!(quote_arg_windows("C:\\app.exe"), "C:\\app.exe");
+        assert_eq!(quote_arg_windows("C:\\Program Files\\App"), "\"C:\\Program Files\\App\"");
+        assert_eq!(quote_arg_windows("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_arg_windows("dir\\ with\\"), "\"dir\\ with\\\\\"");
+        assert_eq!(quote_arg_windows(""), "\"\"");
+
+        let args = vec!["code".to_string(), "my project".to_string()];
+        let expected = if cfg!(windows) { "code \"my project\"" } else { "code 'my project'" };
+        assert_eq!(quote_command_line(&args), expected);
+    }
+
+    #[test]
+    fn test_collect_ancestors() {
+        let make = |pid: &str, parent_pid: Option<u32>| ProcessInfo { parent_pid, ..ProcessInfo::for_test(pid, &format!("p{}", pid)) };
+        // 1 <- 10 <- 100, 1 <- 10 <- 101；20 与 21 互为父进程（PID 复用）
+        let processes = [
+            make("1", None),
+            make("10", Some(1)),
+            make("100", Some(10)),
+            make("101", Some(10)),
+            make("20", Some(21)),
+            make("21", Some(20)),
+        ];
+
+        let matched = [&processes[2], &processes[3]];
+        let ancestors = collect_ancestors(&processes, &matched);
+        let pids: Vec<&str> = ancestors.iter().map(|p| p.pid.as_str()).collect();
+        assert_eq!(pids, vec!["10", "1"]);
+        assert!(ancestors.iter().all(|p| p.ancestor));
+
+        // 已匹配的进程不会作为祖先重复出现
+        assert!(collect_ancestors(&processes, &[&processes[2], &processes[1]]).iter().all(|p| p.pid == "1"));
+        assert_eq!(collect_ancestors(&processes, &[&processes[4]]).len(), 1);
+    }
+
+    #[test]
+    fn test_summarize_by_name() {
+        let make = |pid: &str, name: &str, memory_mb: u64| ProcessInfo { memory_usage: memory_mb * 1024 * 1024, ..ProcessInfo::for_test(pid, name) };
+        let processes = [make("1", "chrome", 100), make("2", "code", 300), make("3", "chrome", 50)];
+        let refs: Vec<&ProcessInfo> = processes.iter().collect();
+
+        let summary = summarize_by_name(&refs);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0], ProcessNameCount { name: "chrome".to_string(), count: 2, total_memory_mb: 150.0 });
+        assert_eq!(summary[1].count, 1);
+    }
+
+    #[test]
+    fn test_memory_deltas() {
+        let make = |pid: &str, memory_usage: u64| ProcessInfo { memory_usage, ..ProcessInfo::for_test(pid, "") };
+        let previous = HashMap::from([("1".to_string(), 1000), ("2".to_string(), 500), ("9".to_string(), 42)]);
+        let processes = [make("1", 1500), make("2", 200), make("3", 800)];
+        let refs: Vec<&ProcessInfo> = processes.iter().collect();
+
+        // 新进程取完整值，已退出的进程（PID 9）不出现
+        assert_eq!(memory_deltas(&previous, &refs), vec![500, -300, 800]);
+    }
+
+    #[test]
+    fn test_matches_user() {
+        assert!(matches_user(Some("alice"), "Alice"));
+        assert!(matches_user(Some("NT AUTHORITY\\SYSTEM"), "system"));
+        assert!(matches_user(Some("NT AUTHORITY\\SYSTEM"), "nt authority\\system"));
+        assert!(!matches_user(Some("alice"), "bob"));
+        assert!(!matches_user(None, "alice"));
+    }
+}
@@ -1,48 +1,83 @@
 // src/sorting.rs
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// 窗口命令 `--sort-position` 的默认值：不排序，保持枚举顺序（Z 序）
+pub const DEFAULT_SORT_POSITION: &str = "0|0";
+
+/// `windows/position/set` 的 `--sort-position` 默认值：X、Y 均升序
+///
+/// 布局中的第 N 个位置分配给排序后的第 N 个窗口，按位置排序可以让结果稳定、可预测；
+/// 若不排序，窗口分配到哪个布局位置取决于 Z 序，每次运行都可能不同。
+pub const LAYOUT_SORT_POSITION: &str = "1|1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SortOrder {
     Ascending,
     Descending,
+    #[default]
     None,
 }
 
-impl Default for SortOrder {
-    fn default() -> Self {
-        SortOrder::None
-    }
-}
-
 impl FromStr for SortOrder {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "1" => Ok(SortOrder::Ascending),
-            "-1" => Ok(SortOrder::Descending),
-            "0" => Ok(SortOrder::None),
-            _ => Err(format!("Invalid sort order: {}. Use 1 (ascending), -1 (descending), or 0 (none)", s)),
+        match s.trim().to_lowercase().as_str() {
+            "1" | "asc" => Ok(SortOrder::Ascending),
+            "-1" | "desc" => Ok(SortOrder::Descending),
+            "0" | "none" => Ok(SortOrder::None),
+            _ => Err(format!("Invalid sort order: {}. Use 1|asc (ascending), -1|desc (descending), or 0|none (none)", s)),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// 位置排序的主轴
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionAxis {
+    /// 先按 X 再按 Y（列优先）
+    #[default]
+    X,
+    /// 先按 Y 再按 X（行优先，即阅读顺序）
+    Y,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct PositionSort {
     pub x_order: SortOrder,
     pub y_order: SortOrder,
+    pub primary: PositionAxis,
+    /// 反转最终的比较结果（全局 `--reverse`），与各键的方向叠加
+    pub reverse: bool,
+    /// 只按一个键排序（全局 `--strict-sort`）：不再回退到标题和 PID，相等时保持原有顺序
+    pub strict: bool,
 }
 
-impl Default for PositionSort {
-    fn default() -> Self {
-        PositionSort {
-            x_order: SortOrder::Ascending,
-            y_order: SortOrder::Ascending,
+impl PositionSort {
+    /// 不排序，保持窗口的枚举顺序（全局 `--no-sort`，同时忽略 `--reverse`）
+    pub fn unsorted() -> Self {
+        Self::default()
+    }
+
+    /// 解析窗口命令的 `--sort-position`，并叠加全局 `--reverse`、`--strict-sort`、`--no-sort`
+    ///
+    /// 格式无效时警告并回退到 `default`（`DEFAULT_SORT_POSITION` 或 `LAYOUT_SORT_POSITION`）。
+    pub fn from_matches(matches: &clap::ArgMatches, default: &str) -> Self {
+        let fallback = || default.parse::<Self>().expect("default position sort is valid");
+        let mut sort_position = match matches.get_one::<String>("sort_position") {
+            Some(s) => s.parse().unwrap_or_else(|_| {
+                eprintln!("Warning: Invalid position sort format '{}', using default ({})", s, default);
+                fallback()
+            }),
+            None => fallback(),
+        };
+        sort_position.reverse = matches.get_flag("reverse");
+        sort_position.strict = matches.get_flag("strict_sort");
+        if matches.get_flag("no_sort") {
+            sort_position = Self::unsorted();
         }
+        sort_position
     }
-}
 
-impl PositionSort {
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<(), String> {
         if matches!(self.x_order, SortOrder::None) && matches!(self.y_order, SortOrder::None) {
@@ -50,7 +85,6 @@ impl PositionSort {
         }
         Ok(())
     }
-    #[allow(dead_code)]
     pub fn is_active(&self) -> bool {
         !matches!(self.x_order, SortOrder::None) || !matches!(self.y_order, SortOrder::None)
     }
@@ -59,19 +93,85 @@ impl PositionSort {
 impl FromStr for PositionSort {
     type Err = String;
 
+    /// 支持两种格式：
+    /// - 数字格式 `X_ORDER|Y_ORDER`，如 `1|-1`，X 为主轴
+    /// - 命名格式 `x:asc,y:desc`，未指定的轴视为 `none`，先写的轴为主轴
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split('|').collect();
-        if parts.len() != 2 {
-            return Err("Position sort format should be X_ORDER|Y_ORDER, e.g., 1|-1".to_string());
+        if s.contains('|') {
+            let parts: Vec<&str> = s.split('|').collect();
+            if parts.len() != 2 {
+                return Err("Position sort format should be X_ORDER|Y_ORDER, e.g., 1|-1".to_string());
+            }
+
+            let x_order = parts[0].parse()?;
+            let y_order = parts[1].parse()?;
+
+            return Ok(PositionSort { x_order, y_order, primary: PositionAxis::X, reverse: false, strict: false });
         }
 
-        let x_order = parts[0].parse()?;
-        let y_order = parts[1].parse()?;
+        let mut sort = PositionSort::default();
+        let mut seen_x = false;
+        let mut seen_y = false;
+
+        for (i, part) in s.split(',').enumerate() {
+            let (axis, order) = part.split_once(':').ok_or_else(|| {
+                format!("Invalid position sort '{}'. Use X_ORDER|Y_ORDER (e.g., 1|-1) or x:asc,y:desc", s)
+            })?;
+
+            match axis.trim().to_lowercase().as_str() {
+                "x" if !seen_x => {
+                    sort.x_order = order.parse()?;
+                    seen_x = true;
+                }
+                "y" if !seen_y => {
+                    sort.y_order = order.parse()?;
+                    seen_y = true;
+                    if i == 0 {
+                        sort.primary = PositionAxis::Y;
+                    }
+                }
+                "x" | "y" => return Err(format!("Duplicate axis in position sort: {}", axis.trim())),
+                _ => return Err(format!("Invalid axis in position sort: {}. Use x or y", axis.trim())),
+            }
+        }
 
-        Ok(PositionSort { x_order, y_order })
+        Ok(sort)
     }
 }
 
+/// `--sort` 表达式中可用的排序字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Pid,
+    Title,
+    /// 标题的字符数，便于找出标题为空或异常短的窗口
+    TitleLen,
+}
+
+/// `--sort` 表达式中的一个排序键，如 `titlelen:desc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub order: SortOrder,
+}
+
+/// 解析组合排序表达式 `KEY:ORDER[,KEY:ORDER...]`，先写的键优先；省略方向时为升序
+pub fn parse_sort_keys(s: &str) -> Result<Vec<SortKey>, String> {
+    s.split(',')
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            let (field, order) = part.split_once(':').unwrap_or((part, "asc"));
+            let field = match field.trim().to_lowercase().as_str() {
+                "pid" => SortField::Pid,
+                "title" => SortField::Title,
+                "titlelen" => SortField::TitleLen,
+                other => return Err(format!("Invalid sort key: {}. Use pid, title or titlelen", other)),
+            };
+            Ok(SortKey { field, order: order.parse()? })
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct SortConfig {
     pub pid: SortOrder,
@@ -90,11 +190,77 @@ impl Default for SortConfig {
     }
 }
 
+/// 进程列表排序配置
+///
+/// 多个键同时指定时，按名称、内存、PID 的顺序依次比较；
+/// 排序是稳定的，所有键都相等时保持原有顺序。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessSort {
+    pub pid: SortOrder,
+    pub name: SortOrder,
+    pub memory: SortOrder,
+    /// 反转最终的比较结果（全局 `--reverse`）
+    pub reverse: bool,
+}
+
+impl ProcessSort {
+    pub fn is_active(&self) -> bool {
+        !matches!(self.pid, SortOrder::None)
+            || !matches!(self.name, SortOrder::None)
+            || !matches!(self.memory, SortOrder::None)
+    }
+}
+
+/// 对进程列表排序
+pub fn apply_process_sorting(processes: &mut [&crate::types::ProcessInfo], sort: &ProcessSort) {
+    if !sort.is_active() {
+        return;
+    }
+
+    processes.sort_by(|a, b| {
+        let ordering = adjust_ordering(a.name.to_lowercase().cmp(&b.name.to_lowercase()), sort.name)
+            .then_with(|| adjust_ordering(a.memory_usage.cmp(&b.memory_usage), sort.memory))
+            .then_with(|| compare_pids(a.get_pid(), b.get_pid(), &sort.pid));
+        apply_reverse(ordering, sort.reverse)
+    });
+}
+
+/// 按窗口数量排序汇总结果；数量相同时保持原有顺序
+pub fn apply_count_sorting(summary: &mut [crate::types::WindowCount], order: SortOrder) {
+    summary.sort_by(|a, b| adjust_ordering(a.count.cmp(&b.count), order));
+}
+
+/// 按进程名汇总结果的排序依据（`--summary-sort`），均为降序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SummarySort {
+    /// 按进程数量
+    #[default]
+    Count,
+    /// 按内存占用之和
+    Memory,
+}
+
+/// 按数量或内存降序排列进程名汇总结果；相等时保持首次出现的顺序，`reverse` 改为升序
+pub fn apply_name_summary_sorting(summary: &mut [crate::types::ProcessNameCount], sort: SummarySort, reverse: bool) {
+    summary.sort_by(|a, b| {
+        let ordering = match sort {
+            SummarySort::Count => b.count.cmp(&a.count),
+            SummarySort::Memory => b.total_memory_mb.total_cmp(&a.total_memory_mb),
+        };
+        apply_reverse(ordering, reverse)
+    });
+}
+
 /// 可排序对象的统一接口
 pub trait Sortable {
     fn get_pid(&self) -> u32;
     fn get_position(&self) -> Option<(i32, i32)>;
     fn get_title(&self) -> &str;
+
+    /// 标题的字符数（不是字节数）
+    fn get_title_len(&self) -> usize {
+        self.get_title().chars().count()
+    }
 }
 
 // 为 WindowInfo 实现 Sortable
@@ -104,10 +270,24 @@ impl Sortable for crate::types::WindowInfo {
     fn get_title(&self) -> &str { &self.title }
 }
 
-// 为 WindowHandle 实现 Sortable
+// 为 ProcessInfo 实现 Sortable（PID 以字符串保存，无法解析时视为 0；进程没有位置）
+impl Sortable for crate::types::ProcessInfo {
+    fn get_pid(&self) -> u32 { self.pid.parse().unwrap_or(0) }
+    fn get_position(&self) -> Option<(i32, i32)> { None }
+    fn get_title(&self) -> &str { &self.title }
+}
+
+// 引用同样可排序（进程过滤结果为 Vec<&ProcessInfo>）
+impl<T: Sortable + ?Sized> Sortable for &T {
+    fn get_pid(&self) -> u32 { (**self).get_pid() }
+    fn get_position(&self) -> Option<(i32, i32)> { (**self).get_position() }
+    fn get_title(&self) -> &str { (**self).get_title() }
+}
+
+// 为 WindowHandle 实现 Sortable（位置取自 find_windows 枚举时记录的矩形）
 impl Sortable for crate::platform::WindowHandle {
     fn get_pid(&self) -> u32 { self.pid }
-    fn get_position(&self) -> Option<(i32, i32)> { None }
+    fn get_position(&self) -> Option<(i32, i32)> { Some((self.rect.x, self.rect.y)) }
     fn get_title(&self) -> &str { &self.title }
 }
 
@@ -124,10 +304,10 @@ pub fn apply_sorting<T: Sortable>(
     
     // 对小数据集使用简单排序，对大数据集考虑性能优化
     if items.len() < 100 {
-        items.sort_by(|a, b| compare_items(a, b, sort_pid, sort_position));
+        items.sort_by(|a, b| compare_items_with_reverse(a, b, sort_pid, sort_position));
     } else {
         // 对大数据集使用相同的排序逻辑，但可以在这里添加性能优化
-        items.sort_by(|a, b| compare_items(a, b, sort_pid, sort_position));
+        items.sort_by(|a, b| compare_items_with_reverse(a, b, sort_pid, sort_position));
     }
 }
 
@@ -150,7 +330,7 @@ pub fn apply_optimized_sorting<T: Sortable>(
         return;
     }
     
-    items.sort_by(|a, b| compare_items(a, b, sort_pid, sort_position));
+    items.sort_by(|a, b| compare_items_with_reverse(a, b, sort_pid, sort_position));
 }
 
 // 辅助函数：检查是否需要排序
@@ -160,6 +340,21 @@ fn should_skip_sorting(sort_pid: &SortOrder, sort_position: &PositionSort) -> bo
     matches!(sort_position.y_order, SortOrder::None)
 }
 
+// 在核心比较结果上应用全局 --reverse
+fn compare_items_with_reverse<T: Sortable>(
+    a: &T,
+    b: &T,
+    sort_pid: &SortOrder,
+    sort_position: &PositionSort,
+) -> std::cmp::Ordering {
+    apply_reverse(compare_items(a, b, sort_pid, sort_position), sort_position.reverse)
+}
+
+// 反转比较结果
+fn apply_reverse(ordering: std::cmp::Ordering, reverse: bool) -> std::cmp::Ordering {
+    if reverse { ordering.reverse() } else { ordering }
+}
+
 // 核心比较逻辑
 fn compare_items<T: Sortable>(
     a: &T,
@@ -167,6 +362,15 @@ fn compare_items<T: Sortable>(
     sort_pid: &SortOrder,
     sort_position: &PositionSort,
 ) -> std::cmp::Ordering {
+    // 严格模式：只比较一个键（指定了位置方向时为位置，否则为 PID）
+    if sort_position.strict {
+        return match (a.get_position(), b.get_position()) {
+            (Some(pos_a), Some(pos_b)) if sort_position.is_active() => compare_positions(pos_a, pos_b, sort_position),
+            _ if sort_position.is_active() => std::cmp::Ordering::Equal,
+            _ => compare_pids(a.get_pid(), b.get_pid(), sort_pid),
+        };
+    }
+
     // 1. 位置排序（如果可用）
     if let (Some(pos_a), Some(pos_b)) = (a.get_position(), b.get_position()) {
         let position_cmp = compare_positions(pos_a, pos_b, sort_position);
@@ -188,25 +392,31 @@ fn compare_items<T: Sortable>(
     compare_pids(a.get_pid(), b.get_pid(), sort_pid)
 }
 
-// 位置比较逻辑
+// 位置比较逻辑：先比较主轴，相等时再比较次轴
 fn compare_positions(
     (x1, y1): (i32, i32),
     (x2, y2): (i32, i32),
     sort_position: &PositionSort,
 ) -> std::cmp::Ordering {
-    let mut cmp = std::cmp::Ordering::Equal;
-    
-    if !matches!(sort_position.x_order, SortOrder::None) {
-        cmp = x1.cmp(&x2);
-        cmp = adjust_ordering(cmp, sort_position.x_order);
-    }
-    
-    if cmp == std::cmp::Ordering::Equal && !matches!(sort_position.y_order, SortOrder::None) {
-        cmp = y1.cmp(&y2);
-        cmp = adjust_ordering(cmp, sort_position.y_order);
+    let x_cmp = (x1.cmp(&x2), sort_position.x_order);
+    let y_cmp = (y1.cmp(&y2), sort_position.y_order);
+
+    let axes = match sort_position.primary {
+        PositionAxis::X => [x_cmp, y_cmp],
+        PositionAxis::Y => [y_cmp, x_cmp],
+    };
+
+    for (cmp, order) in axes {
+        if matches!(order, SortOrder::None) {
+            continue;
+        }
+        let cmp = adjust_ordering(cmp, order);
+        if cmp != std::cmp::Ordering::Equal {
+            return cmp;
+        }
     }
-    
-    cmp
+
+    std::cmp::Ordering::Equal
 }
 
 // PID 比较逻辑
@@ -227,6 +437,27 @@ fn adjust_ordering(ordering: std::cmp::Ordering, sort_order: SortOrder) -> std::
     }
 }
 
+/// 按 `--sort` 的排序键依次比较；排序是稳定的，所有键都相等时保持原有顺序
+pub fn apply_key_sorting<T: Sortable>(items: &mut [T], keys: &[SortKey], reverse: bool) {
+    if keys.is_empty() {
+        return;
+    }
+
+    items.sort_by(|a, b| {
+        let ordering = keys.iter().fold(std::cmp::Ordering::Equal, |ordering, key| {
+            ordering.then_with(|| {
+                let cmp = match key.field {
+                    SortField::Pid => a.get_pid().cmp(&b.get_pid()),
+                    SortField::Title => a.get_title().to_lowercase().cmp(&b.get_title().to_lowercase()),
+                    SortField::TitleLen => a.get_title_len().cmp(&b.get_title_len()),
+                };
+                adjust_ordering(cmp, key.order)
+            })
+        });
+        apply_reverse(ordering, reverse)
+    });
+}
+
 /// 保持向后兼容的窗口排序函数
 pub fn apply_window_sorting(
     windows: &mut [crate::types::WindowInfo], 
@@ -273,24 +504,129 @@ mod tests {
         assert_eq!("1".parse::<SortOrder>().unwrap(), SortOrder::Ascending);
         assert_eq!("-1".parse::<SortOrder>().unwrap(), SortOrder::Descending);
         assert_eq!("0".parse::<SortOrder>().unwrap(), SortOrder::None);
+        assert_eq!("asc".parse::<SortOrder>().unwrap(), SortOrder::Ascending);
+        assert_eq!("desc".parse::<SortOrder>().unwrap(), SortOrder::Descending);
+        assert_eq!("none".parse::<SortOrder>().unwrap(), SortOrder::None);
         assert!("2".parse::<SortOrder>().is_err());
     }
 
+    #[test]
+    fn test_count_sorting() {
+        use crate::types::WindowCount;
+        let count = |name: &str, count| WindowCount { name: name.to_string(), count };
+        let mut summary = vec![count("explorer", 3), count("chrome", 12), count("code", 3)];
+
+        apply_count_sorting(&mut summary, SortOrder::Descending);
+        let names: Vec<&str> = summary.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["chrome", "explorer", "code"]); // 数量相同时保持原有顺序
+
+        apply_count_sorting(&mut summary, SortOrder::Ascending);
+        assert_eq!(summary[2].name, "chrome");
+    }
+
+    #[test]
+    fn test_name_summary_sorting() {
+        use crate::types::ProcessNameCount;
+        let entry = |name: &str, count, total_memory_mb| ProcessNameCount { name: name.to_string(), count, total_memory_mb };
+        let mut summary = vec![entry("svchost", 40, 300.0), entry("chrome", 25, 2400.0), entry("code", 25, 900.0)];
+
+        apply_name_summary_sorting(&mut summary, SummarySort::Memory, false);
+        let names: Vec<&str> = summary.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["chrome", "code", "svchost"]);
+
+        apply_name_summary_sorting(&mut summary, SummarySort::Count, false);
+        let names: Vec<&str> = summary.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["svchost", "chrome", "code"]); // 数量相同时保持原有顺序
+
+        apply_name_summary_sorting(&mut summary, SummarySort::Count, true);
+        assert_eq!(summary[2].name, "svchost");
+    }
+
     #[test]
     fn test_position_sort_parsing() {
         let pos = "1|-1".parse::<PositionSort>().unwrap();
         assert_eq!(pos.x_order, SortOrder::Ascending);
         assert_eq!(pos.y_order, SortOrder::Descending);
+        assert_eq!(pos.primary, PositionAxis::X);
 
         assert!("1".parse::<PositionSort>().is_err());
         assert!("1|2|-1".parse::<PositionSort>().is_err());
     }
 
+    #[test]
+    fn test_default_sort_position_constants() {
+        let default = DEFAULT_SORT_POSITION.parse::<PositionSort>().unwrap();
+        assert_eq!(default.x_order, SortOrder::None);
+        assert_eq!(default.y_order, SortOrder::None);
+        assert!(!default.is_active());
+
+        let layout = LAYOUT_SORT_POSITION.parse::<PositionSort>().unwrap();
+        assert_eq!(layout.x_order, SortOrder::Ascending);
+        assert_eq!(layout.y_order, SortOrder::Ascending);
+        assert_eq!(layout.primary, PositionAxis::X);
+    }
+
+    #[test]
+    fn test_position_sort_from_matches() {
+        let parse = |args: &[&str]| {
+            let command = clap::Command::new("pscan")
+                .arg(clap::Arg::new("sort_position").long("sort-position"))
+                .arg(clap::Arg::new("reverse").long("reverse").action(clap::ArgAction::SetTrue))
+                .arg(clap::Arg::new("strict_sort").long("strict-sort").action(clap::ArgAction::SetTrue))
+                .arg(clap::Arg::new("no_sort").long("no-sort").action(clap::ArgAction::SetTrue));
+            PositionSort::from_matches(&command.get_matches_from(args), LAYOUT_SORT_POSITION)
+        };
+
+        let pos = parse(&["pscan", "--sort-position=-1|0", "--reverse", "--strict-sort"]);
+        assert_eq!(pos.x_order, SortOrder::Descending);
+        assert!(pos.reverse);
+        assert!(pos.strict);
+
+        // 格式无效或未指定时回退到默认值
+        assert_eq!(parse(&["pscan", "--sort-position", "bogus"]).x_order, SortOrder::Ascending);
+        assert_eq!(parse(&["pscan"]).y_order, SortOrder::Ascending);
+
+        // --no-sort 覆盖其余排序参数
+        let pos = parse(&["pscan", "--sort-position", "1|1", "--reverse", "--no-sort"]);
+        assert!(!pos.is_active());
+        assert!(!pos.reverse);
+    }
+
+    #[test]
+    fn test_position_sort_named_parsing() {
+        let pos = "x:asc,y:desc".parse::<PositionSort>().unwrap();
+        assert_eq!(pos.x_order, SortOrder::Ascending);
+        assert_eq!(pos.y_order, SortOrder::Descending);
+
+        let pos = "y:desc".parse::<PositionSort>().unwrap();
+        assert_eq!(pos.x_order, SortOrder::None);
+        assert_eq!(pos.y_order, SortOrder::Descending);
+        assert_eq!(pos.primary, PositionAxis::Y);
+
+        let pos = "X:None, Y:ASC".parse::<PositionSort>().unwrap();
+        assert_eq!(pos.primary, PositionAxis::X);
+        assert_eq!(pos.x_order, SortOrder::None);
+        assert_eq!(pos.y_order, SortOrder::Ascending);
+
+        // 数字与命名方向可以混用
+        let pos = "asc|-1".parse::<PositionSort>().unwrap();
+        assert_eq!(pos.x_order, SortOrder::Ascending);
+        assert_eq!(pos.y_order, SortOrder::Descending);
+
+        assert!("x:up".parse::<PositionSort>().is_err());
+        assert!("z:asc".parse::<PositionSort>().is_err());
+        assert!("x:asc,x:desc".parse::<PositionSort>().is_err());
+        assert!("asc".parse::<PositionSort>().is_err());
+    }
+
     #[test]
     fn test_position_sort_validation() {
         let valid_sort = PositionSort {
             x_order: SortOrder::Ascending,
             y_order: SortOrder::None,
+            primary: PositionAxis::X,
+            reverse: false,
+            strict: false,
         };
         assert!(valid_sort.validate().is_ok());
         assert!(valid_sort.is_active());
@@ -298,6 +634,9 @@ mod tests {
         let invalid_sort = PositionSort {
             x_order: SortOrder::None,
             y_order: SortOrder::None,
+            primary: PositionAxis::X,
+            reverse: false,
+            strict: false,
         };
         assert!(invalid_sort.validate().is_err());
         assert!(!invalid_sort.is_active());
@@ -306,21 +645,9 @@ mod tests {
     #[test]
     fn test_apply_window_sorting() {
         let mut windows = vec![
-            WindowInfo {
-                pid: 100,
-                title: "Window C".to_string(),
-                rect: WindowRect::new(300, 200, 800, 600),
-            },
-            WindowInfo {
-                pid: 200,
-                title: "Window A".to_string(),
-                rect: WindowRect::new(100, 100, 800, 600),
-            },
-            WindowInfo {
-                pid: 150,
-                title: "Window B".to_string(),
-                rect: WindowRect::new(200, 150, 800, 600),
-            },
+            WindowInfo::for_test(100, "Window C", WindowRect::new(300, 200, 800, 600)),
+            WindowInfo::for_test(200, "Window A", WindowRect::new(100, 100, 800, 600)),
+            WindowInfo::for_test(150, "Window B", WindowRect::new(200, 150, 800, 600)),
         ];
 
         // Test PID ascending sort
@@ -339,6 +666,9 @@ mod tests {
         let position_sort = PositionSort {
             x_order: SortOrder::Ascending,
             y_order: SortOrder::Ascending,
+            primary: PositionAxis::X,
+            reverse: false,
+            strict: false,
         };
         apply_window_sorting(&mut windows, &SortOrder::None, &position_sort);
         assert_eq!(windows[0].rect.x, 100);
@@ -346,19 +676,98 @@ mod tests {
         assert_eq!(windows[2].rect.x, 300);
     }
 
+    #[test]
+    fn test_key_sorting() {
+        let make = |pid, title: &str| WindowInfo::for_test(pid, title, WindowRect::new(0, 0, 800, 600));
+        let mut windows = vec![make(1, "Editor"), make(2, ""), make(3, "计算器"), make(4, "Ab")];
+
+        let keys = parse_sort_keys("titlelen:asc,pid:desc").unwrap();
+        assert_eq!(keys[0], SortKey { field: SortField::TitleLen, order: SortOrder::Ascending });
+        apply_key_sorting(&mut windows, &keys, false);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![2, 4, 3, 1]); // "计算器" 按字符数计为 3
+
+        apply_key_sorting(&mut windows, &parse_sort_keys("titlelen:desc").unwrap(), false);
+        assert_eq!(windows[0].pid, 1);
+        assert_eq!(windows[3].pid, 2);
+
+        assert_eq!(parse_sort_keys("title").unwrap()[0].order, SortOrder::Ascending);
+        assert!(parse_sort_keys("width:asc").is_err());
+        assert!(parse_sort_keys("titlelen:up").is_err());
+    }
+
+    #[test]
+    fn test_position_sort_primary_axis() {
+        let make = |pid, x, y| WindowInfo::for_test(pid, &format!("Window {}", pid), WindowRect::new(x, y, 800, 600));
+        let mut windows = vec![make(1, 500, 0), make(2, 0, 300), make(3, 0, 0)];
+
+        // 列优先：X 相同时按 Y
+        let column_order = "x:asc,y:asc".parse::<PositionSort>().unwrap();
+        apply_window_sorting(&mut windows, &SortOrder::None, &column_order);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![3, 2, 1]);
+
+        // 行优先（阅读顺序）：Y 相同时按 X
+        let row_order = "y:asc,x:asc".parse::<PositionSort>().unwrap();
+        apply_window_sorting(&mut windows, &SortOrder::None, &row_order);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![3, 1, 2]);
+
+        // --no-sort：保持当前（枚举）顺序
+        apply_window_sorting(&mut windows, &SortOrder::None, &PositionSort::unsorted());
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_reverse_sorting() {
+        let make = |pid, x| WindowInfo::for_test(pid, &format!("Window {}", pid), WindowRect::new(x, 0, 800, 600));
+        let mut windows = vec![make(2, 100), make(1, 300), make(3, 200)];
+
+        // 反转叠加在各键方向之上：PID 降序 + reverse = 升序
+        let reversed = PositionSort { reverse: true, ..Default::default() };
+        apply_window_sorting(&mut windows, &SortOrder::Descending, &reversed);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![1, 2, 3]);
+
+        let reversed = PositionSort { x_order: SortOrder::Ascending, reverse: true, ..Default::default() };
+        apply_window_sorting(&mut windows, &SortOrder::None, &reversed);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_strict_sorting() {
+        let make = |pid, x, title: &str| WindowInfo::for_test(pid, title, WindowRect::new(x, 0, 800, 600));
+        let original = vec![make(3, 100, "B"), make(1, 200, "C"), make(2, 100, "A")];
+
+        // 位置相同时不回退到标题，保持原有顺序
+        let mut windows = original.clone();
+        let strict = PositionSort { x_order: SortOrder::Ascending, strict: true, ..Default::default() };
+        apply_window_sorting(&mut windows, &SortOrder::Ascending, &strict);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![3, 2, 1]);
+
+        // 非严格模式下按标题打破平局
+        let mut windows = original.clone();
+        let loose = PositionSort { x_order: SortOrder::Ascending, ..Default::default() };
+        apply_window_sorting(&mut windows, &SortOrder::Ascending, &loose);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![2, 3, 1]);
+
+        // 未指定位置方向时只按 PID 排序
+        let mut windows = original;
+        let strict = PositionSort { strict: true, ..Default::default() };
+        apply_window_sorting(&mut windows, &SortOrder::Descending, &strict);
+        let pids: Vec<u32> = windows.iter().map(|w| w.pid).collect();
+        assert_eq!(pids, vec![3, 2, 1]);
+    }
+
     #[test]
     fn test_skip_sorting() {
         let mut windows = vec![
-            WindowInfo {
-                pid: 100,
-                title: "Window A".to_string(),
-                rect: WindowRect::new(100, 100, 800, 600),
-            },
-            WindowInfo {
-                pid: 200,
-                title: "Window B".to_string(),
-                rect: WindowRect::new(200, 200, 800, 600),
-            },
+            WindowInfo::for_test(100, "Window A", WindowRect::new(100, 100, 800, 600)),
+            WindowInfo::for_test(200, "Window B", WindowRect::new(200, 200, 800, 600)),
         ];
 
         let original_order: Vec<u32> = windows.iter().map(|w| w.pid).collect();
@@ -377,21 +786,16 @@ mod tests {
             position: PositionSort {
                 x_order: SortOrder::Descending,
                 y_order: SortOrder::Ascending,
+                primary: PositionAxis::X,
+                reverse: false,
+                strict: false,
             },
             fallback_to_title: true,
         };
 
         let mut windows = vec![
-            WindowInfo {
-                pid: 200,
-                title: "Window B".to_string(),
-                rect: WindowRect::new(100, 100, 800, 600),
-            },
-            WindowInfo {
-                pid: 100,
-                title: "Window A".to_string(),
-                rect: WindowRect::new(200, 200, 800, 600),
-            },
+            WindowInfo::for_test(200, "Window B", WindowRect::new(100, 100, 800, 600)),
+            WindowInfo::for_test(100, "Window A", WindowRect::new(200, 200, 800, 600)),
         ];
 
         apply_sorting_with_config(&mut windows, &config);
@@ -413,13 +817,33 @@ mod tests {
         assert!(create_sort_config("1", "invalid").is_err());
     }
 
+    #[test]
+    fn test_apply_process_sorting() {
+        use crate::types::ProcessInfo;
+
+        let make = |pid: &str, name: &str, memory_usage| ProcessInfo { memory_usage, ..ProcessInfo::for_test(pid, name) };
+        let processes = [make("20", "b", 300), make("3", "A", 100), make("100", "b", 200)];
+        let mut refs: Vec<&ProcessInfo> = processes.iter().collect();
+
+        // PID 按数值而不是字符串比较
+        apply_process_sorting(&mut refs, &ProcessSort { pid: SortOrder::Ascending, ..Default::default() });
+        let pids: Vec<&str> = refs.iter().map(|p| p.pid.as_str()).collect();
+        assert_eq!(pids, vec!["3", "20", "100"]);
+
+        // 名称优先（不区分大小写），名称相同时按内存降序
+        let sort = ProcessSort { name: SortOrder::Ascending, memory: SortOrder::Descending, ..Default::default() };
+        apply_process_sorting(&mut refs, &sort);
+        let pids: Vec<&str> = refs.iter().map(|p| p.pid.as_str()).collect();
+        assert_eq!(pids, vec!["3", "20", "100"]);
+
+        apply_process_sorting(&mut refs, &ProcessSort { memory: SortOrder::Ascending, ..Default::default() });
+        let pids: Vec<&str> = refs.iter().map(|p| p.pid.as_str()).collect();
+        assert_eq!(pids, vec!["3", "100", "20"]);
+    }
+
     #[test]
     fn test_sortable_trait_implementation() {
-        let window_info = WindowInfo {
-            pid: 123,
-            title: "Test Window".to_string(),
-            rect: WindowRect::new(100, 200, 800, 600),
-        };
+        let window_info = WindowInfo::for_test(123, "Test Window", WindowRect::new(100, 200, 800, 600));
 
         assert_eq!(window_info.get_pid(), 123);
         assert_eq!(window_info.get_position(), Some((100, 200)));
@@ -433,10 +857,30 @@ mod tests {
         #[cfg(unix)]
         let platform_data = PlatformData::Unix(crate::platform::unix::UnixWindowData::new());
         
-        let window_handle = WindowHandle::new(456, "Handle Window".to_string(), platform_data);
+        let window_handle = WindowHandle::new(456, "Handle Window".to_string(), WindowRect::new(30, 40, 100, 100), platform_data.clone());
         
         assert_eq!(window_handle.get_pid(), 456);
-        assert_eq!(window_handle.get_position(), None);
+        assert_eq!(window_handle.get_position(), Some((30, 40)));
         assert_eq!(window_handle.get_title(), "Handle Window");
+
+        // 句柄按枚举时记录的位置排序
+        let mut handles = vec![
+            WindowHandle::new(1, "Right".to_string(), WindowRect::new(800, 0, 100, 100), platform_data.clone()),
+            WindowHandle::new(2, "Left".to_string(), WindowRect::new(0, 0, 100, 100), platform_data),
+        ];
+        assert_eq!(handles[0].get_position(), Some((800, 0)));
+        apply_window_handle_sorting(&mut handles, &SortOrder::None, &"1|0".parse().unwrap());
+        assert_eq!(handles[0].title, "Left");
+
+        let process_info = crate::types::ProcessInfo { title: "Test Process".to_string(), ..crate::types::ProcessInfo::for_test("456", "test.exe") };
+
+        assert_eq!(process_info.get_pid(), 456);
+        assert_eq!(process_info.get_position(), None);
+        assert_eq!(process_info.get_title(), "Test Process");
+
+        // 通过引用排序（如 Vec<&ProcessInfo>）
+        let mut refs = vec![&process_info];
+        apply_sorting(&mut refs, &SortOrder::Ascending, &PositionSort::default());
+        assert_eq!(refs[0].get_pid(), 456);
     }
 }
\ No newline at end of file
@@ -1,22 +1,93 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ProcessInfo {
     pub pid: String,
     pub name: String,
     pub title: String,
     pub memory_usage: u64,
+    /// 内存占用占系统总内存的百分比
+    pub memory_percent: f64,
     pub has_window: bool,
+    /// 进程所属用户名（无法解析时为 None）
+    pub user: Option<String>,
+    /// 进程位数（"x86" 或 "x64"），仅 Windows 上可用
+    pub bitness: Option<String>,
+    /// 进程承载的服务名（多个服务以 ", " 分隔），仅 Windows 上可用
+    pub service: Option<String>,
+    /// 父进程 PID（根进程或无法确定时为 None）
+    pub parent_pid: Option<u32>,
+    /// 本身不匹配过滤条件，作为匹配进程的祖先加入结果（--include-parents）
+    pub ancestor: bool,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[cfg(test)]
+impl ProcessInfo {
+    /// 测试用进程：只指定 PID 和名称，其余字段取空值
+    pub fn for_test(pid: &str, name: &str) -> Self {
+        Self {
+            pid: pid.to_string(),
+            name: name.to_string(),
+            title: String::new(),
+            memory_usage: 0,
+            memory_percent: 0.0,
+            has_window: false,
+            user: None,
+            bitness: None,
+            service: None,
+            parent_pid: None,
+            ancestor: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct WindowInfo {
     pub pid: u32,
     pub title: String,
     pub rect: WindowRect,
+    /// 所在虚拟桌面的序号（从 1 开始）；未查询、固定到所有桌面或无法确定时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desktop: Option<u32>,
+    /// 窗口是否响应消息（`SendMessageTimeout(WM_NULL)` 探测，超时视为未响应）；只在需要时探测，未探测时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responding: Option<bool>,
+    /// 扩展窗口样式（`GWL_EXSTYLE`）；非 Windows 平台或从快照读取时为 0
+    #[serde(skip)]
+    pub ex_style: u32,
+    /// 窗口类名；非 Windows 平台或从快照读取时为空
+    #[serde(skip)]
+    pub class: String,
+    /// UI Automation 属性；只在指定 `--uia-name`/`--automation-id` 时查询，否则为 None
+    #[serde(skip)]
+    pub uia: Option<UiaProperties>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[cfg(test)]
+impl WindowInfo {
+    /// 测试用窗口：只指定 PID、标题和位置，其余字段与未额外查询时枚举到的窗口相同
+    pub fn for_test(pid: u32, title: &str, rect: WindowRect) -> Self {
+        Self {
+            pid,
+            title: title.to_string(),
+            rect,
+            desktop: None,
+            responding: None,
+            ex_style: 0,
+            class: String::new(),
+            uia: None,
+        }
+    }
+}
+
+/// 窗口的 UI Automation 名称和 AutomationId（标题为空或本地化时用于稳定地定位窗口）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct UiaProperties {
+    pub name: String,
+    pub automation_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WindowRect {
     pub x: i32,
     pub y: i32,
@@ -24,16 +95,68 @@ pub struct WindowRect {
     pub height: i32,
 }
 
+/// 报告窗口矩形的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RectMode {
+    /// `GetWindowRect`，最小化窗口改用还原后的位置和尺寸
+    #[default]
+    Restored,
+    /// `GetWindowRect` 的原始值（最小化窗口为屏幕外坐标，`--raw-rect`）
+    Raw,
+    /// DWM 扩展边框（`DWMWA_EXTENDED_FRAME_BOUNDS`）：不含 Windows 10/11 的透明阴影边框，
+    /// 与屏幕上可见的窗口边缘一致（`--frame-bounds`）
+    Frame,
+}
+
+/// `windows/get --duplicates-only` 判断重复窗口的依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DuplicateKey {
+    /// 标题相同
+    Title,
+    /// 标题和窗口类名都相同
+    TitleClass,
+}
+
 impl WindowRect {
+    #[allow(dead_code)]
     pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
         Self { x, y, width, height }
     }
-    
-    pub fn to_string(&self) -> String {
-        format!("{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+
+    /// 宽或高不为正（窗口正在创建/销毁时 `GetWindowRect` 可能返回这样的矩形）
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// 中心点坐标
+    pub fn center(&self) -> (i32, i32) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// 宽高比（宽 / 高）；矩形为空时返回 None
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.width as f64 / self.height as f64)
+        }
     }
 }
 
+impl std::fmt::Display for WindowRect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// 窗口截图或图标（RGBA，每像素 4 字节，自上而下）
+#[derive(Debug, Clone)]
+pub struct CapturedImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
 #[derive(Serialize)]
 pub struct ProcessOutput {
     pub pid: String,
@@ -41,19 +164,142 @@ pub struct ProcessOutput {
     pub title: String,
     pub memory_usage: u64,
     pub memory_usage_mb: f64,
+    pub memory_percent: f64,
     pub has_window: bool,
+    pub user: Option<String>,
+    pub bitness: Option<String>,
+    pub service: Option<String>,
+    pub parent_pid: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub ancestor: bool,
 }
 
-#[derive(Serialize)]
+/// 按进程名汇总的窗口数量（`windows/get --summary-only`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WindowCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// 窗口的显示状态（只在 Windows 上查询）
+#[cfg_attr(not(windows), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowShowState {
+    Normal,
+    Minimized,
+    Maximized,
+}
+
+impl std::fmt::Display for WindowShowState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WindowShowState::Normal => "normal",
+            WindowShowState::Minimized => "minimized",
+            WindowShowState::Maximized => "maximized",
+        })
+    }
+}
+
+/// 单个窗口的状态（`windows/state`）；无法查询的属性为 None
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowStateRecord {
+    pub pid: u32,
+    pub title: String,
+    pub state: Option<WindowShowState>,
+    pub topmost: Option<bool>,
+    /// 不透明度百分比（0-100），未设置分层属性的窗口为 100
+    pub opacity: Option<u8>,
+    pub responding: bool,
+}
+
+/// 按进程名汇总的进程数量和内存占用（`--group-by name --summary`）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessNameCount {
+    pub name: String,
+    pub count: usize,
+    pub total_memory_mb: f64,
+}
+
+/// 单个显示器上的窗口数量（`windows/get --count-windows-per-monitor`）
+///
+/// `monitor`（从 1 开始）和 `bounds` 为 None 时表示中心点不在任何显示器上的窗口。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MonitorWindowCount {
+    pub monitor: Option<usize>,
+    pub bounds: Option<WindowRect>,
+    pub count: usize,
+}
+
+/// 窗口输出的扁平形式（默认，`--flatten` 保证使用）：每个字段都是标量，不含嵌套对象或数组
+#[derive(Serialize, Deserialize)]
 pub struct WindowOutput {
     pub pid: String,
+    #[serde(default)]
     pub name: String,
     pub title: String,
     pub x: i32,
     pub y: i32,
     pub width: i32,
     pub height: i32,
+    #[serde(default)]
     pub dimensions: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desktop: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responding: Option<bool>,
+}
+
+/// 窗口输出的嵌套形式（`windows/get --nested-rect`）：位置和尺寸放在 `rect` 对象中
+#[derive(Serialize, Deserialize)]
+pub struct NestedWindowOutput {
+    pub pid: String,
+    #[serde(default)]
+    pub name: String,
+    pub title: String,
+    pub rect: WindowRect,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desktop: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responding: Option<bool>,
+}
+
+/// JSON/YAML 中的一条窗口记录，扁平或嵌套形式；读取快照时两种形式都接受
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WindowRecord {
+    Flat(WindowOutput),
+    Nested(NestedWindowOutput),
+}
+
+impl WindowRecord {
+    /// 构造窗口记录，`nested_rect` 为 true 时使用嵌套形式
+    pub fn new(window: &WindowInfo, name: &str, nested_rect: bool) -> Self {
+        if nested_rect {
+            WindowRecord::Nested(NestedWindowOutput {
+                pid: window.pid.to_string(),
+                name: name.to_string(),
+                title: window.title.clone(),
+                rect: window.rect.clone(),
+                desktop: window.desktop,
+                responding: window.responding,
+            })
+        } else {
+            let mut output = WindowOutput::from(window);
+            output.name = name.to_string();
+            WindowRecord::Flat(output)
+        }
+    }
+
+    /// 转换回窗口信息，PID 无法解析时返回原始 PID 字符串
+    pub fn into_window(self) -> Result<WindowInfo, String> {
+        let (pid, title, rect, desktop, responding) = match self {
+            WindowRecord::Flat(o) => (o.pid, o.title, WindowRect::new(o.x, o.y, o.width, o.height), o.desktop, o.responding),
+            WindowRecord::Nested(o) => (o.pid, o.title, o.rect, o.desktop, o.responding),
+        };
+        let pid = pid.parse().map_err(|_| pid)?;
+        Ok(WindowInfo { pid, title, rect, desktop, responding, ex_style: 0, class: String::new(), uia: None })
+    }
 }
 
 impl From<&WindowInfo> for WindowOutput {
@@ -67,6 +313,8 @@ impl From<&WindowInfo> for WindowOutput {
             width: window.rect.width,
             height: window.rect.height,
             dimensions: window.rect.to_string(),
+            desktop: window.desktop,
+            responding: window.responding,
         }
     }
 }
@@ -79,7 +327,13 @@ impl From<&ProcessInfo> for ProcessOutput {
             title: process.title.clone(),
             memory_usage: process.memory_usage,
             memory_usage_mb: (process.memory_usage as f64) / 1024.0 / 1024.0,
+            memory_percent: process.memory_percent,
             has_window: process.has_window,
+            user: process.user.clone(),
+            bitness: process.bitness.clone(),
+            service: process.service.clone(),
+            parent_pid: process.parent_pid,
+            ancestor: process.ancestor,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+// src/undo.rs
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::error::AppResult;
+use crate::platform::{self, WindowHandle};
+use crate::types::{WindowRect, WindowShowState};
+
+/// 修改前单个窗口的状态
+///
+/// 撤销时优先按保存的窗口句柄匹配（同一进程中标题相同的多个窗口也能区分），
+/// 窗口已重建时退回按 PID 和标题匹配（与 `--from-json` 相同）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowSnapshot {
+    /// 窗口句柄（Windows 上为 HWND）；无法获取时为 0，只按 PID 和标题匹配
+    #[serde(default)]
+    pub handle: isize,
+    pub pid: u32,
+    pub title: String,
+    /// 还原位置：有 `state` 时为 `GetWindowPlacement` 的还原位置（最小化窗口也是真实位置），否则为窗口矩形
+    pub rect: WindowRect,
+    /// 显示状态；无法读取还原位置时为 None，撤销时直接设置窗口矩形
+    #[serde(default)]
+    pub state: Option<WindowShowState>,
+    /// 不透明度百分比；无法读取时为 None，撤销时不修改
+    #[serde(default)]
+    pub opacity: Option<u8>,
+}
+
+/// 最近一次布局修改前的快照（`windows/undo` 恢复）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoSnapshot {
+    /// 产生快照的操作，如 "resize"
+    pub operation: String,
+    pub windows: Vec<WindowSnapshot>,
+}
+
+impl UndoSnapshot {
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 读取快照，文件不存在时返回 None
+    pub fn load(path: &Path) -> AppResult<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 记录窗口当前的状态；同一窗口只记录第一次，保留修改前的原始状态
+    fn push(&mut self, window: WindowSnapshot) {
+        if !self.windows.iter().any(|w| w.is_same_window(&window)) {
+            self.windows.push(window);
+        }
+    }
+
+    /// 为每个快照项选择当前的窗口（`candidates` 中的下标），`candidates` 为 (句柄, PID, 标题)
+    ///
+    /// 先按句柄匹配（同时校验 PID，避免句柄被其他进程的新窗口复用），剩下的再按 PID 和标题匹配；
+    /// 每个窗口最多分配给一个快照项。
+    pub fn match_windows(&self, candidates: &[(isize, u32, &str)]) -> Vec<Option<usize>> {
+        let mut used = vec![false; candidates.len()];
+        let mut matched: Vec<Option<usize>> = self.windows
+            .iter()
+            .map(|saved| {
+                let found = candidates.iter().enumerate().position(|(i, &(handle, pid, _))| {
+                    !used[i] && saved.handle != 0 && handle == saved.handle && pid == saved.pid
+                });
+                if let Some(i) = found {
+                    used[i] = true;
+                }
+                found
+            })
+            .collect();
+
+        for (saved, slot) in self.windows.iter().zip(matched.iter_mut()).filter(|(_, slot)| slot.is_none()) {
+            *slot = candidates.iter().enumerate().position(|(i, &(_, pid, title))| {
+                !used[i] && pid == saved.pid && title == saved.title
+            });
+            if let Some(i) = *slot {
+                used[i] = true;
+            }
+        }
+        matched
+    }
+}
+
+impl WindowSnapshot {
+    /// 两项都有句柄时按句柄和 PID 判断，否则按 PID 和标题判断
+    fn is_same_window(&self, other: &WindowSnapshot) -> bool {
+        if self.handle != 0 && other.handle != 0 {
+            self.handle == other.handle && self.pid == other.pid
+        } else {
+            self.pid == other.pid && self.title == other.title
+        }
+    }
+}
+
+/// 快照文件：系统临时目录下按登录会话区分，同一会话中的 pscan 共享最近一次快照
+pub fn snapshot_path() -> PathBuf {
+    let session = platform::session_id().map_or_else(|| "default".to_string(), |id| id.to_string());
+    std::env::temp_dir().join(format!("pscan-undo-{}.json", session))
+}
+
+/// 在修改位置、尺寸或透明度之前记录窗口状态，操作结束后保存为最近一次快照
+pub struct UndoRecorder {
+    snapshot: UndoSnapshot,
+}
+
+impl UndoRecorder {
+    pub fn new(operation: &str) -> Self {
+        Self { snapshot: UndoSnapshot { operation: operation.to_string(), windows: Vec::new() } }
+    }
+
+    /// 修改窗口前调用
+    pub fn capture(&mut self, window: &WindowHandle) {
+        // 还原位置不受最小化/最大化影响；读取失败时退回窗口矩形
+        let (rect, state) = match window.placement() {
+            Ok((rect, state)) => (rect, Some(state)),
+            Err(_) => (window.get_rect().unwrap_or_else(|_| window.rect.clone()), None),
+        };
+        self.snapshot.push(WindowSnapshot {
+            handle: window.raw_handle(),
+            pid: window.pid,
+            title: window.title.clone(),
+            rect,
+            state,
+            opacity: window.opacity().ok().flatten(),
+        });
+    }
+
+    /// 保存快照；没有记录任何窗口时保留上一次的快照，写入失败只输出警告，不影响已完成的操作
+    pub fn save(self) {
+        if self.snapshot.windows.is_empty() {
+            return;
+        }
+        if let Err(e) = self.snapshot.save(&snapshot_path()) {
+            eprintln!("Warning: Failed to save undo snapshot: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_snapshot_round_trip() {
+        let mut snapshot = UndoSnapshot { operation: "resize".to_string(), windows: Vec::new() };
+        let window = |x, opacity| WindowSnapshot {
+            handle: 7,
+            pid: 42,
+            title: "Editor".to_string(),
+            rect: WindowRect::new(x, 0, 800, 600),
+            state: Some(WindowShowState::Minimized),
+            opacity,
+        };
+        snapshot.push(window(0, Some(100)));
+        // 同一窗口再次记录时保留第一次的状态
+        snapshot.push(window(500, None));
+        assert_eq!(snapshot.windows, vec![window(0, Some(100))]);
+
+        let path = std::env::temp_dir().join(format!("pscan-undo-test-{}.json", std::process::id()));
+        snapshot.save(&path).unwrap();
+        assert_eq!(UndoSnapshot::load(&path).unwrap(), Some(snapshot));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(UndoSnapshot::load(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_undo_snapshot_same_title_windows() {
+        let window = |handle, x| WindowSnapshot {
+            handle,
+            pid: 42,
+            title: "Downloads".to_string(),
+            rect: WindowRect::new(x, 0, 800, 600),
+            state: Some(WindowShowState::Normal),
+            opacity: None,
+        };
+        let mut snapshot = UndoSnapshot { operation: "apply".to_string(), windows: Vec::new() };
+        // 同一进程中标题相同的两个窗口分别记录
+        snapshot.push(window(1, 0));
+        snapshot.push(window(2, 900));
+        assert_eq!(snapshot.windows.len(), 2);
+
+        // 按句柄匹配，与枚举顺序无关
+        assert_eq!(snapshot.match_windows(&[(2, 42, "Downloads"), (1, 42, "Downloads")]), vec![Some(1), Some(0)]);
+        // 窗口重建后按 PID 和标题匹配，每个窗口只分配一次
+        assert_eq!(snapshot.match_windows(&[(3, 42, "Downloads"), (2, 42, "Downloads")]), vec![Some(0), Some(1)]);
+        assert_eq!(snapshot.match_windows(&[(3, 42, "Downloads")]), vec![Some(0), None]);
+        // 句柄被其他进程复用时不匹配
+        assert_eq!(snapshot.match_windows(&[(1, 7, "Downloads")]), vec![None, None]);
+    }
+}
@@ -1,5 +1,6 @@
 // src/utils.rs
 use crate::error::{AppError, AppResult};
+use crate::types::{WindowInfo, WindowRect, MonitorWindowCount, DuplicateKey};
 
 /// 解析索引字符串，如 "1,2,3" -> [1, 2, 3]
 pub fn parse_indices(index_str: &str, max_index: usize) -> Vec<usize> {
@@ -31,7 +32,7 @@ pub fn validate_position_parameters(
     y_step: &Option<String>,
 ) -> AppResult<()> {
     let has_single_position = position.is_some();
-    let has_layout = layout.as_ref().map_or(false, |s| !s.trim().is_empty());
+    let has_layout = layout.as_ref().is_some_and(|s| !s.trim().is_empty());
     let has_grid = x_start.is_some() || y_start.is_some() || x_step.is_some() || y_step.is_some();
 
     let method_count = [has_single_position, has_layout, has_grid]
@@ -93,6 +94,194 @@ pub fn calculate_positions(
     }
 }
 
+/// 检查位置是否落在某个显示器工作区内；`clamp` 为 true 时将屏幕外的位置移到最近的工作区内，否则返回错误
+pub fn ensure_onscreen(position: (i32, i32), work_areas: &[WindowRect], clamp: bool) -> AppResult<(i32, i32)> {
+    let (x, y) = position;
+    let contains = |area: &WindowRect| {
+        x >= area.x && x < area.x + area.width && y >= area.y && y < area.y + area.height
+    };
+    if work_areas.iter().any(contains) {
+        return Ok(position);
+    }
+
+    if !clamp {
+        return Err(AppError::invalid_parameter(format!(
+            "Position {},{} is outside all monitor work areas (use --clamp to move it on screen)", x, y
+        )));
+    }
+
+    // 限制到每个工作区内，取移动距离最小的结果
+    work_areas
+        .iter()
+        .filter(|area| area.width > 0 && area.height > 0)
+        .map(|area| {
+            (
+                x.clamp(area.x, area.x + area.width - 1),
+                y.clamp(area.y, area.y + area.height - 1),
+            )
+        })
+        .min_by_key(|&(cx, cy)| (cx - x).unsigned_abs() as u64 + (cy - y).unsigned_abs() as u64)
+        .ok_or_else(|| AppError::platform("No monitor work area available"))
+}
+
+/// 返回窗口所在显示器的下标（0 起）：优先取包含窗口中心的显示器，否则取距离中心最近的显示器
+pub fn monitor_of(rect: &WindowRect, monitors: &[WindowRect]) -> Option<usize> {
+    let (cx, cy) = rect.center();
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.width > 0 && m.height > 0)
+        .min_by_key(|(_, m)| {
+            let dx = cx - cx.clamp(m.x, m.x + m.width - 1);
+            let dy = cy - cy.clamp(m.y, m.y + m.height - 1);
+            dx.unsigned_abs() as u64 + dy.unsigned_abs() as u64
+        })
+        .map(|(i, _)| i)
+}
+
+/// 返回中心点所在显示器的下标（0 起）；中心不在任何显示器内时返回 None
+pub fn monitor_containing(rect: &WindowRect, monitors: &[WindowRect]) -> Option<usize> {
+    let (cx, cy) = rect.center();
+    monitors.iter().position(|m| {
+        (m.x..m.x + m.width).contains(&cx) && (m.y..m.y + m.height).contains(&cy)
+    })
+}
+
+/// 窗口中心与任一显示器中心的距离是否不超过 `tolerance` 像素
+pub fn near_monitor_center(rect: &WindowRect, monitors: &[WindowRect], tolerance: u32) -> bool {
+    let (cx, cy) = rect.center();
+    monitors.iter().any(|m| {
+        let (mx, my) = m.center();
+        let (dx, dy) = ((cx - mx) as f64, (cy - my) as f64);
+        dx.hypot(dy) <= tolerance as f64
+    })
+}
+
+/// 按中心点统计每个显示器上的窗口数量；有窗口不在任何显示器上时，末尾追加一条无显示器的记录
+pub fn count_windows_per_monitor(windows: &[WindowInfo], monitors: &[WindowRect]) -> Vec<MonitorWindowCount> {
+    let mut counts = vec![0; monitors.len()];
+    let mut offscreen = 0;
+    for window in windows {
+        match monitor_containing(&window.rect, monitors) {
+            Some(i) => counts[i] += 1,
+            None => offscreen += 1,
+        }
+    }
+
+    let mut result: Vec<MonitorWindowCount> = monitors
+        .iter()
+        .zip(counts)
+        .enumerate()
+        .map(|(i, (bounds, count))| MonitorWindowCount { monitor: Some(i + 1), bounds: Some(bounds.clone()), count })
+        .collect();
+    if offscreen > 0 {
+        result.push(MonitorWindowCount { monitor: None, bounds: None, count: offscreen });
+    }
+    result
+}
+
+/// 检查窗口宽高比是否在 [min, max] 范围内；指定了范围时，空矩形（高度为 0）不匹配
+pub fn aspect_in_range(rect: &WindowRect, min: Option<f64>, max: Option<f64>) -> bool {
+    if min.is_none() && max.is_none() {
+        return true;
+    }
+    rect.aspect_ratio().is_some_and(|ratio| {
+        min.is_none_or(|min| ratio >= min) && max.is_none_or(|max| ratio <= max)
+    })
+}
+
+/// 只保留与其他窗口重复的窗口：按 `key` 分组，丢弃只有一个窗口的组，保持原有顺序
+pub fn retain_duplicates(windows: &mut Vec<WindowInfo>, key: DuplicateKey) {
+    let group_key = |window: &WindowInfo| match key {
+        DuplicateKey::Title => (window.title.clone(), String::new()),
+        DuplicateKey::TitleClass => (window.title.clone(), window.class.clone()),
+    };
+    let mut counts: std::collections::HashMap<(String, String), usize> = std::collections::HashMap::new();
+    for window in windows.iter() {
+        *counts.entry(group_key(window)).or_default() += 1;
+    }
+    windows.retain(|window| counts[&group_key(window)] > 1);
+}
+
+/// 计算窗口移到目标工作区后的位置
+///
+/// 默认保持窗口相对于原工作区左上角的偏移，`center` 为 true 时居中；
+/// 结果会被限制在目标工作区内，使窗口完整显示（窗口比工作区大时与左上角对齐）。
+pub fn relocate_to_monitor(rect: &WindowRect, from: &WindowRect, to: &WindowRect, center: bool) -> (i32, i32) {
+    let (x, y) = if center {
+        (to.x + (to.width - rect.width) / 2, to.y + (to.height - rect.height) / 2)
+    } else {
+        (to.x + rect.x - from.x, to.y + rect.y - from.y)
+    };
+    (
+        x.min(to.x + to.width - rect.width).max(to.x),
+        y.min(to.y + to.height - rect.height).max(to.y),
+    )
+}
+
+/// 解析时长字符串，如 "10s"、"500ms"、"2m"、"1h"；不带单位时按秒计算
+pub fn parse_duration(duration_str: &str) -> AppResult<std::time::Duration> {
+    let s = duration_str.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+
+    let value: u64 = number.parse()
+        .map_err(|_| AppError::parse(format!("Invalid duration: {}. Expected e.g. '10s', '500ms' or '2m'", duration_str)))?;
+
+    let millis = match unit.trim() {
+        "ms" => value,
+        "" | "s" => value.saturating_mul(1000),
+        "m" => value.saturating_mul(60_000),
+        "h" => value.saturating_mul(3_600_000),
+        other => return Err(AppError::parse(format!("Invalid duration unit '{}' in {}. Use ms, s, m or h", other, duration_str))),
+    };
+
+    Ok(std::time::Duration::from_millis(millis))
+}
+
+/// 破坏性批量操作的 `--yes` 参数：跳过确认提示
+pub fn confirm_arg() -> clap::Arg {
+    clap::Arg::new("yes")
+        .short('y')
+        .long("yes")
+        .action(clap::ArgAction::SetTrue)
+        .help("Skip the confirmation prompt when acting on several windows")
+}
+
+/// 对多个窗口执行破坏性操作前确认
+///
+/// 标准输入是终端时在 stderr 上提示并等待 y/N；非交互调用必须显式传入 `--yes`，否则拒绝执行。
+pub fn confirm_mass_action(action: &str, count: usize, yes: bool) -> AppResult<()> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    if yes || count <= 1 {
+        return Ok(());
+    }
+
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        return Err(AppError::invalid_parameter(format!(
+            "Refusing to {} {} windows without confirmation; pass --yes to proceed", action, count
+        )));
+    }
+
+    eprint!("About to {} {} windows, proceed? [y/N] ", action, count);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+
+    if is_confirmation(&answer) {
+        Ok(())
+    } else {
+        Err(AppError::invalid_parameter(format!("Aborted: did not {} {} windows", action, count)))
+    }
+}
+
+/// 只有明确的 y/yes（不区分大小写）视为确认，空输入按默认的 N 处理
+fn is_confirmation(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
 /// 解析单一位置字符串 "X,Y" -> (x, y)
 pub fn parse_position(position_str: &str) -> AppResult<(i32, i32)> {
     let parts: Vec<&str> = position_str.split(',').collect();
@@ -108,11 +297,109 @@ pub fn parse_position(position_str: &str) -> AppResult<(i32, i32)> {
     Ok((x, y))
 }
 
+/// `--position` 中的一个坐标：像素值，或工作区宽（高）的百分比
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coordinate {
+    /// 窗口左上角的绝对坐标
+    Pixels(i32),
+    /// 窗口中心在工作区中的相对位置（0-100）
+    Percent(f64),
+}
+
+impl Coordinate {
+    /// 解析为窗口左上角的坐标；`start`/`length` 为工作区在该轴上的起点和长度，`size` 为窗口在该轴上的尺寸
+    fn resolve(self, start: i32, length: i32, size: i32) -> i32 {
+        match self {
+            Coordinate::Pixels(value) => value,
+            Coordinate::Percent(percent) => start + (length as f64 * percent / 100.0).round() as i32 - size / 2,
+        }
+    }
+}
+
+/// 位置字符串中是否包含百分比坐标
+pub fn is_relative_position(position_str: &str) -> bool {
+    position_str.contains('%')
+}
+
+/// 解析可以包含百分比的位置字符串，如 "50%,25%" 或 "50%,100"
+pub fn parse_relative_position(position_str: &str) -> AppResult<(Coordinate, Coordinate)> {
+    let parts: Vec<&str> = position_str.split(',').collect();
+    if parts.len() != 2 {
+        return Err(AppError::parse(format!("Invalid position format: {}. Expected 'X,Y' or 'X%,Y%'", position_str)));
+    }
+
+    let parse = |part: &str, axis: &str| -> AppResult<Coordinate> {
+        let part = part.trim();
+        match part.strip_suffix('%') {
+            Some(percent) => {
+                let value: f64 = percent.trim().parse()
+                    .map_err(|_| AppError::parse(format!("Invalid {} percentage: {}", axis, part)))?;
+                if !(0.0..=100.0).contains(&value) {
+                    return Err(AppError::invalid_parameter(format!("{} percentage must be 0-100, got {}", axis, part)));
+                }
+                Ok(Coordinate::Percent(value))
+            }
+            None => part.parse().map(Coordinate::Pixels)
+                .map_err(|_| AppError::parse(format!("Invalid {} coordinate: {}", axis, part))),
+        }
+    };
+
+    Ok((parse(parts[0], "X")?, parse(parts[1], "Y")?))
+}
+
+/// 按窗口所在显示器的工作区解析位置：百分比坐标把窗口中心放在工作区的对应比例处，像素坐标保持不变
+pub fn resolve_relative_position(
+    (x, y): (Coordinate, Coordinate),
+    window: &WindowRect,
+    work_areas: &[WindowRect],
+) -> AppResult<(i32, i32)> {
+    let area = monitor_of(window, work_areas)
+        .map(|i| &work_areas[i])
+        .ok_or_else(|| AppError::platform("No monitor work area available"))?;
+    Ok((
+        x.resolve(area.x, area.width, window.width),
+        y.resolve(area.y, area.height, window.height),
+    ))
+}
+
+/// 解析矩形字符串 "X,Y,WIDTH,HEIGHT"；宽高必须为正数
+pub fn parse_rect(rect_str: &str) -> AppResult<WindowRect> {
+    let parts: Vec<&str> = rect_str.split(',').collect();
+    if parts.len() != 4 {
+        return Err(AppError::parse(format!("Invalid rect format: {}. Expected 'X,Y,WIDTH,HEIGHT'", rect_str)));
+    }
+
+    let values = parts
+        .iter()
+        .map(|part| part.trim().parse::<i32>().map_err(|_| AppError::parse(format!("Invalid rect value: {}", part))))
+        .collect::<AppResult<Vec<i32>>>()?;
+    let rect = WindowRect::new(values[0], values[1], values[2], values[3]);
+
+    if rect.is_empty() {
+        return Err(AppError::invalid_parameter("Width and height must be positive values"));
+    }
+    Ok(rect)
+}
+
+/// 把以可见边框表示的目标矩形换算为 `SetWindowPos` 使用的窗口矩形
+///
+/// `window` 和 `frame` 是同一窗口当前的 `GetWindowRect` 和 DWM 边框，两者之差即透明阴影边框的宽度。
+pub fn frame_to_window_rect(target: &WindowRect, window: &WindowRect, frame: &WindowRect) -> WindowRect {
+    let left = frame.x - window.x;
+    let top = frame.y - window.y;
+    WindowRect::new(
+        target.x - left,
+        target.y - top,
+        target.width + (window.width - frame.width),
+        target.height + (window.height - frame.height),
+    )
+}
+
 /// 解析布局字符串 "X1,Y1,X2,Y2,..." -> [(x1, y1), (x2, y2), ...]
 pub fn parse_layout(layout_str: &str, window_count: usize) -> AppResult<Vec<(i32, i32)>> {
     let coords: Vec<&str> = layout_str.split(',').collect();
     
-    if coords.len() % 2 != 0 {
+    if !coords.len().is_multiple_of(2) {
         return Err(AppError::parse(format!("Layout must have even number of coordinates, got {}", coords.len())));
     }
 
@@ -134,13 +421,131 @@ pub fn parse_layout(layout_str: &str, window_count: usize) -> AppResult<Vec<(i32
     Ok(positions)
 }
 
+/// 读取布局文件并转换为布局字符串 "X1,Y1,X2,Y2,..."，供 `calculate_positions` 使用
+pub fn read_layout_file(path: &str) -> AppResult<String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::invalid_parameter(format!("Failed to read layout file '{}': {}", path, e)))?;
+
+    let positions = parse_layout_file(&content)?;
+    if positions.is_empty() {
+        return Err(AppError::parse(format!("Layout file '{}' contains no positions", path)));
+    }
+
+    Ok(positions
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// 解析布局文件内容：JSON 数组（`[[x, y], ...]` 或 `[{"x": .., "y": ..}, ...]`），
+/// 或每行一个 "X,Y"（忽略空行和 `#` 注释）
+pub fn parse_layout_file(content: &str) -> AppResult<Vec<(i32, i32)>> {
+    if content.trim_start().starts_with('[') {
+        return parse_layout_json(content);
+    }
+
+    let mut positions = Vec::new();
+    for (line_no, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let position = parse_position(line)
+            .map_err(|e| AppError::parse(format!("Layout file line {}: {}", line_no + 1, e)))?;
+        positions.push(position);
+    }
+
+    Ok(positions)
+}
+
+/// 解析 JSON 格式的布局
+fn parse_layout_json(content: &str) -> AppResult<Vec<(i32, i32)>> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| AppError::parse(format!("Invalid layout JSON at line {}: {}", e.line(), e)))?;
+
+    let coord = |v: Option<&serde_json::Value>| -> Option<i32> {
+        v.and_then(|v| v.as_i64()).and_then(|v| i32::try_from(v).ok())
+    };
+
+    let items = value.as_array()
+        .ok_or_else(|| AppError::parse("Layout JSON must be an array"))?;
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let pair = match item {
+                serde_json::Value::Array(pair) if pair.len() == 2 => coord(pair.first()).zip(coord(pair.get(1))),
+                serde_json::Value::Object(_) => coord(item.get("x")).zip(coord(item.get("y"))),
+                _ => None,
+            };
+            pair.ok_or_else(|| AppError::parse(format!(
+                "Invalid layout JSON entry #{}: expected [x, y] or {{\"x\": .., \"y\": ..}}, got {}", i + 1, item
+            )))
+        })
+        .collect()
+}
+
+/// 在文件名后追加序号："shot.png" + 2 -> "shot_2.png"
+pub fn numbered_path(path: &str, number: usize) -> std::path::PathBuf {
+    let path = std::path::Path::new(path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("capture");
+    let file_name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_{}.{}", stem, number, ext),
+        None => format!("{}_{}", stem, number),
+    };
+    path.with_file_name(file_name)
+}
+
+/// 解析透明度渐变范围 "A:B" -> (min, max)，两端均为 0-100 的百分比，顺序无关
+pub fn parse_gradient(gradient_str: &str) -> AppResult<(u8, u8)> {
+    let (a, b) = gradient_str.split_once(':').ok_or_else(|| {
+        AppError::parse(format!("Invalid gradient format: {}. Expected 'START:END', e.g. 40:100", gradient_str))
+    })?;
+
+    let parse_percent = |s: &str| -> AppResult<u8> {
+        let value: u8 = s.trim().parse()
+            .map_err(|_| AppError::parse(format!("Invalid gradient percentage: {}", s)))?;
+        if value > 100 {
+            return Err(AppError::invalid_parameter(format!("Gradient percentage must be 0-100, got {}", value)));
+        }
+        Ok(value)
+    };
+
+    let (a, b) = (parse_percent(a)?, parse_percent(b)?);
+    Ok((a.min(b), a.max(b)))
+}
+
+/// 计算渐变透明度：第一个（最前面的）窗口最不透明，最后一个最透明，中间线性插值
+pub fn gradient_levels(min: u8, max: u8, count: usize) -> Vec<u8> {
+    if count <= 1 {
+        return vec![max; count];
+    }
+
+    let span = (max - min) as f64;
+    (0..count)
+        .map(|i| {
+            let ratio = i as f64 / (count - 1) as f64;
+            (max as f64 - span * ratio).round() as u8
+        })
+        .collect()
+}
+
+/// 把分层窗口的 alpha（0-255）换算为不透明度百分比，与 `set_transparency` 的换算互逆
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn alpha_to_opacity(alpha: u8) -> u8 {
+    ((alpha as u32 * 100 + 127) / 255) as u8
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_parse_indices() {
-        assert_eq!(parse_indices("", 5), vec![]);
+        assert_eq!(parse_indices("", 5), Vec::<usize>::new());
         assert_eq!(parse_indices("1,2,3", 5), vec![1, 2, 3]);
         assert_eq!(parse_indices("1, 2, 3", 5), vec![1, 2, 3]);
         assert_eq!(parse_indices("1,6,3", 5), vec![1, 3]); // 6 is out of bounds
@@ -156,6 +561,44 @@ mod tests {
         assert!(parse_position("abc,def").is_err());
     }
 
+    #[test]
+    fn test_relative_position() {
+        assert_eq!(parse_relative_position("50%,25%").unwrap(), (Coordinate::Percent(50.0), Coordinate::Percent(25.0)));
+        assert_eq!(parse_relative_position(" 12.5% , 100 ").unwrap(), (Coordinate::Percent(12.5), Coordinate::Pixels(100)));
+        assert!(parse_relative_position("50%").is_err());
+        assert!(parse_relative_position("150%,0%").is_err());
+        assert!(parse_relative_position("x%,0").is_err());
+        assert!(is_relative_position("50%,10"));
+        assert!(!is_relative_position("50,10"));
+
+        // 窗口中心落在所在显示器工作区的对应比例处
+        let work_areas = [WindowRect::new(0, 0, 1920, 1040), WindowRect::new(1920, 0, 1280, 984)];
+        let window = WindowRect::new(2000, 100, 400, 300);
+        let spec = parse_relative_position("50%,25%").unwrap();
+        assert_eq!(resolve_relative_position(spec, &window, &work_areas).unwrap(), (2360, 96));
+        let spec = parse_relative_position("0%,40").unwrap();
+        assert_eq!(resolve_relative_position(spec, &window, &work_areas).unwrap(), (1720, 40));
+        assert!(resolve_relative_position(spec, &window, &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rect() {
+        assert_eq!(parse_rect("-8,0, 960,1040").unwrap(), WindowRect::new(-8, 0, 960, 1040));
+        assert!(parse_rect("0,0,800").is_err());
+        assert!(parse_rect("0,0,800,x").is_err());
+        assert!(parse_rect("0,0,0,600").is_err());
+    }
+
+    #[test]
+    fn test_frame_to_window_rect() {
+        // Windows 11 典型情况：左、右、下各 7 像素阴影，顶部没有
+        let window = WindowRect::new(93, 100, 814, 607);
+        let frame = WindowRect::new(100, 100, 800, 600);
+        let target = WindowRect::new(0, 0, 960, 1040);
+        assert_eq!(frame_to_window_rect(&target, &window, &frame), WindowRect::new(-7, 0, 974, 1047));
+        assert_eq!(frame_to_window_rect(&target, &frame, &frame), target);
+    }
+
     #[test]
     fn test_parse_layout() {
         assert_eq!(parse_layout("100,200,150,250", 2).unwrap(), vec![(100, 200), (150, 250)]);
@@ -200,4 +643,180 @@ mod tests {
         let grid = calculate_positions(3, &None, "", &Some("0".to_string()), &Some("0".to_string()), &Some("100".to_string()), &Some("50".to_string())).unwrap();
         assert_eq!(grid, vec![(0, 0), (100, 50), (200, 100)]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_gradient() {
+        assert_eq!(parse_gradient("40:100").unwrap(), (40, 100));
+        assert_eq!(parse_gradient("100:40").unwrap(), (40, 100));
+        assert_eq!(parse_gradient(" 0 : 50 ").unwrap(), (0, 50));
+        assert!(parse_gradient("40").is_err());
+        assert!(parse_gradient("40:101").is_err());
+        assert!(parse_gradient("a:100").is_err());
+    }
+
+    #[test]
+    fn test_gradient_levels() {
+        assert_eq!(gradient_levels(40, 100, 4), vec![100, 80, 60, 40]);
+        assert_eq!(gradient_levels(40, 100, 1), vec![100]);
+        assert_eq!(gradient_levels(50, 50, 3), vec![50, 50, 50]);
+        assert!(gradient_levels(40, 100, 0).is_empty());
+    }
+
+    #[test]
+    fn test_alpha_to_opacity() {
+        assert_eq!(alpha_to_opacity(255), 100);
+        assert_eq!(alpha_to_opacity(0), 0);
+        // set_transparency 写入的 alpha 读回来得到原来的百分比
+        for opacity in 0..=100u32 {
+            assert_eq!(alpha_to_opacity((opacity * 255 / 100) as u8) as u32, opacity);
+        }
+    }
+
+    #[test]
+    fn test_parse_layout_file() {
+        let lines = "# main monitor\n100,100\n\n 200 , 150 \n";
+        assert_eq!(parse_layout_file(lines).unwrap(), vec![(100, 100), (200, 150)]);
+
+        let json = r#"[[0, 0], {"x": -1920, "y": 10}]"#;
+        assert_eq!(parse_layout_file(json).unwrap(), vec![(0, 0), (-1920, 10)]);
+
+        let err = parse_layout_file("100,100\n200\n").unwrap_err().to_string();
+        assert!(err.contains("line 2"), "{}", err);
+
+        assert!(parse_layout_file("[[1, 2, 3]]").is_err());
+        assert!(parse_layout_file("[[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_ensure_onscreen() {
+        let areas = [WindowRect::new(0, 0, 1920, 1040), WindowRect::new(1920, 0, 1280, 984)];
+
+        assert_eq!(ensure_onscreen((100, 100), &areas, false).unwrap(), (100, 100));
+        assert_eq!(ensure_onscreen((2000, 500), &areas, false).unwrap(), (2000, 500));
+        assert!(ensure_onscreen((-500, 100), &areas, false).is_err());
+        assert!(ensure_onscreen((2000, 990), &areas, false).is_err());
+
+        assert_eq!(ensure_onscreen((-500, 100), &areas, true).unwrap(), (0, 100));
+        assert_eq!(ensure_onscreen((2000, 990), &areas, true).unwrap(), (2000, 983));
+        assert_eq!(ensure_onscreen((5000, -20), &areas, true).unwrap(), (3199, 0));
+    }
+
+    #[test]
+    fn test_monitor_of() {
+        let monitors = [WindowRect::new(0, 0, 1920, 1040), WindowRect::new(1920, 0, 1280, 984)];
+
+        assert_eq!(monitor_of(&WindowRect::new(100, 100, 800, 600), &monitors), Some(0));
+        // 跨屏窗口按中心点归属
+        assert_eq!(monitor_of(&WindowRect::new(1500, 100, 1000, 600), &monitors), Some(1));
+        // 中心落在所有显示器之外时取最近的显示器
+        assert_eq!(monitor_of(&WindowRect::new(3000, 2000, 400, 300), &monitors), Some(1));
+        assert_eq!(monitor_of(&WindowRect::new(0, 0, 10, 10), &[]), None);
+    }
+
+    #[test]
+    fn test_near_monitor_center() {
+        let monitors = [WindowRect::new(0, 0, 1920, 1040), WindowRect::new(1920, 0, 1280, 984)];
+
+        // 居中的对话框：中心 (960, 520)
+        assert!(near_monitor_center(&WindowRect::new(760, 420, 400, 200), &monitors, 0));
+        assert!(near_monitor_center(&WindowRect::new(2355, 392, 400, 200), &monitors, 10));
+        assert!(near_monitor_center(&WindowRect::new(820, 420, 400, 200), &monitors, 60));
+        assert!(!near_monitor_center(&WindowRect::new(820, 500, 400, 200), &monitors, 60));
+        assert!(!near_monitor_center(&WindowRect::new(0, 0, 400, 200), &[], 1000));
+    }
+
+    #[test]
+    fn test_count_windows_per_monitor() {
+        let monitors = [WindowRect::new(0, 0, 1920, 1040), WindowRect::new(1920, 0, 1280, 984)];
+        let window = |x, y| WindowInfo::for_test(1, "", WindowRect::new(x, y, 400, 300));
+
+        assert_eq!(monitor_containing(&WindowRect::new(1800, 100, 400, 300), &monitors), Some(1));
+        assert_eq!(monitor_containing(&WindowRect::new(3000, 2000, 400, 300), &monitors), None);
+
+        let counts = count_windows_per_monitor(&[window(0, 0), window(100, 100), window(5000, 0)], &monitors);
+        assert_eq!(counts.iter().map(|c| (c.monitor, c.count)).collect::<Vec<_>>(), [(Some(1), 2), (Some(2), 0), (None, 1)]);
+        assert_eq!(counts[1].bounds, Some(monitors[1].clone()));
+        // 所有窗口都在显示器上时不输出无显示器的记录
+        assert_eq!(count_windows_per_monitor(&[window(0, 0)], &monitors).len(), 2);
+    }
+
+    #[test]
+    fn test_retain_duplicates() {
+        let window = |pid, title: &str, class: &str| WindowInfo { class: class.to_string(), ..WindowInfo::for_test(pid, title, WindowRect::new(0, 0, 400, 300)) };
+        let windows = vec![
+            window(1, "Error", "#32770"),
+            window(2, "Notes", "Notepad"),
+            window(3, "Error", "Chrome_WidgetWin_1"),
+            window(4, "Error", "#32770"),
+        ];
+
+        let mut by_title = windows.clone();
+        retain_duplicates(&mut by_title, DuplicateKey::Title);
+        assert_eq!(by_title.iter().map(|w| w.pid).collect::<Vec<_>>(), [1, 3, 4]);
+
+        let mut by_class = windows;
+        retain_duplicates(&mut by_class, DuplicateKey::TitleClass);
+        assert_eq!(by_class.iter().map(|w| w.pid).collect::<Vec<_>>(), [1, 4]);
+    }
+
+    #[test]
+    fn test_aspect_in_range() {
+        let landscape = WindowRect::new(0, 0, 1600, 900);
+        let portrait = WindowRect::new(0, 0, 600, 1000);
+
+        assert!(aspect_in_range(&landscape, Some(1.5), None));
+        assert!(!aspect_in_range(&portrait, Some(1.5), None));
+        assert!(aspect_in_range(&portrait, None, Some(1.0)));
+        assert!(!aspect_in_range(&landscape, Some(1.0), Some(1.5)));
+        // 高度为 0 时无法计算宽高比
+        assert!(!aspect_in_range(&WindowRect::new(0, 0, 800, 0), Some(0.0), None));
+        assert!(aspect_in_range(&WindowRect::new(0, 0, 800, 0), None, None));
+    }
+
+    #[test]
+    fn test_relocate_to_monitor() {
+        let left = WindowRect::new(0, 0, 1920, 1040);
+        let right = WindowRect::new(1920, 0, 1280, 984);
+
+        // 保持相对偏移
+        assert_eq!(relocate_to_monitor(&WindowRect::new(100, 50, 800, 600), &left, &right, false), (2020, 50));
+        // 超出目标工作区时限制在工作区内
+        assert_eq!(relocate_to_monitor(&WindowRect::new(1000, 500, 800, 600), &left, &right, false), (2400, 384));
+        // 居中
+        assert_eq!(relocate_to_monitor(&WindowRect::new(100, 50, 800, 600), &left, &right, true), (2160, 192));
+        // 窗口比工作区大时与左上角对齐
+        assert_eq!(relocate_to_monitor(&WindowRect::new(0, 0, 1920, 1040), &left, &right, false), (1920, 0));
+    }
+
+    #[test]
+    fn test_is_confirmation() {
+        assert!(is_confirmation("y\n"));
+        assert!(is_confirmation(" YES \r\n"));
+        assert!(!is_confirmation("\n"));
+        assert!(!is_confirmation("no"));
+        assert!(!is_confirmation("yess"));
+        assert!(confirm_mass_action("hide", 1, false).is_ok());
+        assert!(confirm_mass_action("hide", 14, true).is_ok());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+        assert_eq!(parse_duration("10s").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_duration("10").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("10d").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn test_numbered_path() {
+        use std::path::PathBuf;
+        assert_eq!(numbered_path("shot.png", 1), PathBuf::from("shot_1.png"));
+        assert_eq!(numbered_path("out/shot.png", 12), PathBuf::from("out/shot_12.png"));
+        assert_eq!(numbered_path("shot", 2), PathBuf::from("shot_2"));
+    }
+}
@@ -1,13 +1,37 @@
 // src/window.rs
 // 简化的窗口模块，只提供向后兼容的函数
+use std::time::{Duration, Instant};
 use crate::platform;
+use crate::filter::{WindowFilter, ProcessNameIndex};
+use crate::error::AppResult;
 
-/// 获取所有窗口的PID和标题（保持向后兼容）
+/// 获取所有窗口的PID和标题（保持向后兼容）；不探测响应状态、不查询 UI Automation 属性
 pub fn get_all_windows() -> Vec<(u32, String)> {
-    platform::get_all_windows_with_size()
-        .into_iter()
-        .map(|window| (window.pid, window.title))
-        .collect()
+    platform::get_window_titles()
+}
+
+/// 等待窗口出现时的轮询间隔
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 查找匹配的窗口；指定 `wait` 时每隔一段时间重新枚举进程和窗口，直到找到窗口或超时
+///
+/// 超时（或未指定 `wait`）仍没有匹配的窗口时返回 `WindowFilter::no_windows_error` 给出的错误。
+pub fn wait_for_windows(filter: &WindowFilter, wait: Option<Duration>) -> AppResult<Vec<WindowHandle>> {
+    let deadline = wait.map(|wait| Instant::now() + wait);
+    loop {
+        // 新启动的程序可能还不在进程列表中，每轮都重新获取进程名称
+        let process_names = ProcessNameIndex::collect(filter);
+        let windows = platform::find_windows(filter, &process_names)?;
+        if !windows.is_empty() {
+            return Ok(windows);
+        }
+
+        let now = Instant::now();
+        match deadline {
+            Some(deadline) if now < deadline => std::thread::sleep(WAIT_POLL_INTERVAL.min(deadline - now)),
+            _ => return Err(filter.no_windows_error(&process_names)),
+        }
+    }
 }
 
 /// 重新导出平台接口的主要功能